@@ -0,0 +1,4 @@
+drop table segment_effort_archive;
+drop table segment_effort;
+drop table route_segment_archive;
+drop table route_segment;
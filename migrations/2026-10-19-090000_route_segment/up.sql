@@ -0,0 +1,60 @@
+create table route_segment (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    route_id bigint not null references route on delete cascade,
+    name varchar(80) not null check (length(name) >= 2),
+    track "position"[] not null,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index route_segment__route_id__name__key
+    on route_segment (route_id, name) where deleted = false;
+
+create index route_segment__user_id__epoch__idx
+    on route_segment (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on route_segment
+    for each row execute function set_epoch_for_user();
+
+create table route_segment_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (route_segment);
+
+create trigger archive_route_segment
+    after insert or update of deleted or delete
+    on route_segment
+    for each row execute procedure archive_record();
+
+create table segment_effort (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    route_segment_id bigint not null references route_segment on delete cascade,
+    cardio_session_id bigint not null references cardio_session on delete cascade,
+    datetime timestamptz not null,
+    time integer not null check (time >= 0), -- milliseconds
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index segment_effort__route_segment_id__cardio_session_id__key
+    on segment_effort (route_segment_id, cardio_session_id) where deleted = false;
+
+create index segment_effort__user_id__epoch__idx
+    on segment_effort (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on segment_effort
+    for each row execute function set_epoch_for_user();
+
+create table segment_effort_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (segment_effort);
+
+create trigger archive_segment_effort
+    after insert or update of deleted or delete
+    on segment_effort
+    for each row execute procedure archive_record();
@@ -0,0 +1,4 @@
+alter table cardio_session
+    drop column uncorrected_ascent,
+    drop column uncorrected_descent,
+    drop column uncorrected_elevation;
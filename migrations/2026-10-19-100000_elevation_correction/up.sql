@@ -0,0 +1,4 @@
+alter table cardio_session
+    add column uncorrected_ascent integer check (uncorrected_ascent >= 0),
+    add column uncorrected_descent integer check (uncorrected_descent >= 0),
+    add column uncorrected_elevation double precision[];
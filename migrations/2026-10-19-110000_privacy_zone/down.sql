@@ -0,0 +1,5 @@
+alter table cardio_session
+    drop column share_token;
+
+drop table privacy_zone_archive;
+drop table privacy_zone;
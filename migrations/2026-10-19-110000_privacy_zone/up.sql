@@ -0,0 +1,33 @@
+create table privacy_zone (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    name varchar(80) not null check (length(name) >= 2),
+    latitude double precision not null check (latitude between -90 and 90),
+    longitude double precision not null check (longitude between -180 and 180),
+    radius integer not null check (radius > 0), -- meter
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index privacy_zone__user_id__name__key
+    on privacy_zone (user_id, name) where deleted = false;
+
+create index privacy_zone__user_id__epoch__idx
+    on privacy_zone (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on privacy_zone
+    for each row execute function set_epoch_for_user();
+
+create table privacy_zone_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (privacy_zone);
+
+create trigger archive_privacy_zone
+    after insert or update of deleted or delete
+    on privacy_zone
+    for each row execute procedure archive_record();
+
+alter table cardio_session
+    add column share_token text unique;
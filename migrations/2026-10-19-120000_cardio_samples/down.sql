@@ -0,0 +1,9 @@
+alter table cardio_session
+    drop column avg_power,
+    drop column max_power,
+    drop column normalized_power,
+    drop column power,
+    drop column speed,
+    drop column temperature;
+
+drop type sample;
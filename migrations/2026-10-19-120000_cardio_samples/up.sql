@@ -0,0 +1,12 @@
+create type sample as (
+    value double precision,
+    time integer -- milliseconds since start
+);
+
+alter table cardio_session
+    add column avg_power integer check (avg_power >= 0), -- watt
+    add column max_power integer check (max_power >= 0), -- watt
+    add column normalized_power integer check (normalized_power >= 0), -- watt
+    add column power sample[], -- watt
+    add column speed sample[], -- meter per second
+    add column temperature sample[]; -- degree celsius
@@ -0,0 +1,2 @@
+drop table cardio_lap_archive;
+drop table cardio_lap;
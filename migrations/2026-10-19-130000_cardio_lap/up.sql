@@ -0,0 +1,33 @@
+create table cardio_lap (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    cardio_session_id bigint not null references cardio_session on delete cascade,
+    lap_number integer not null check (lap_number >= 0),
+    start integer not null check (start >= 0), -- milliseconds since start of cardio session
+    time integer not null check (time > 0), -- milliseconds
+    distance integer check (distance >= 0),
+    avg_heart_rate integer check (avg_heart_rate > 0),
+    rest boolean not null default false,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index cardio_lap__cardio_session_id__lap_number__key
+    on cardio_lap (cardio_session_id, lap_number) where deleted = false;
+
+create index cardio_lap__user_id__epoch__idx
+    on cardio_lap (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on cardio_lap
+    for each row execute function set_epoch_for_user();
+
+create table cardio_lap_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (cardio_lap);
+
+create trigger archive_cardio_lap
+    after insert or update of deleted or delete
+    on cardio_lap
+    for each row execute procedure archive_record();
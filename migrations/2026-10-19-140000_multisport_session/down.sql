@@ -0,0 +1,4 @@
+drop table multisport_leg_archive;
+drop table multisport_leg;
+drop table multisport_session_archive;
+drop table multisport_session;
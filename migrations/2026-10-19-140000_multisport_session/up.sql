@@ -0,0 +1,59 @@
+create table multisport_session (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    datetime timestamptz not null default now(),
+    comments text,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create index multisport_session__user_id__epoch__idx
+    on multisport_session (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on multisport_session
+    for each row execute function set_epoch_for_user();
+
+create table multisport_session_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (multisport_session);
+
+create trigger archive_multisport_session
+    after insert or update of deleted or delete
+    on multisport_session
+    for each row execute procedure archive_record();
+
+create table multisport_leg (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    multisport_session_id bigint not null references multisport_session on delete cascade,
+    cardio_session_id bigint not null references cardio_session on delete cascade,
+    leg_number integer not null check (leg_number >= 0),
+    transition_time integer check (transition_time >= 0), -- milliseconds
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index multisport_leg__multisport_session_id__leg_number__key
+    on multisport_leg (multisport_session_id, leg_number) where deleted = false;
+
+create unique index multisport_leg__cardio_session_id__key
+    on multisport_leg (cardio_session_id) where deleted = false;
+
+create index multisport_leg__user_id__epoch__idx
+    on multisport_leg (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on multisport_leg
+    for each row execute function set_epoch_for_user();
+
+create table multisport_leg_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (multisport_leg);
+
+create trigger archive_multisport_leg
+    after insert or update of deleted or delete
+    on multisport_leg
+    for each row execute procedure archive_record();
@@ -0,0 +1,7 @@
+alter table cardio_session
+    drop column gear_id;
+
+drop table gear_archive;
+drop table gear;
+
+drop type gear_type;
@@ -0,0 +1,39 @@
+create type gear_type as enum('shoe', 'bike', 'ski', 'other');
+
+create table gear (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    name varchar(80) not null check (length(name) >= 2),
+    gear_type gear_type not null,
+    default_movement_id bigint references movement on delete set null,
+    retirement_distance integer check (retirement_distance > 0), -- meter
+    retired boolean not null default false,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index gear__user_id__name__key
+    on gear (user_id, name) where deleted = false;
+
+create unique index gear__user_id__default_movement_id__key
+    on gear (user_id, default_movement_id) where deleted = false and retired = false;
+
+create index gear__user_id__epoch__idx
+    on gear (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on gear
+    for each row execute function set_epoch_for_user();
+
+create table gear_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (gear);
+
+create trigger archive_gear
+    after insert or update of deleted or delete
+    on gear
+    for each row execute procedure archive_record();
+
+alter table cardio_session
+    add column gear_id bigint references gear on delete set null;
@@ -0,0 +1,6 @@
+alter table strength_set
+    drop column rpe,
+    drop column rir,
+    drop column tempo,
+    drop column rest,
+    drop column is_warmup;
@@ -0,0 +1,6 @@
+alter table strength_set
+    add column rpe real check (rpe between 1 and 10 and rpe * 2 = round(rpe * 2)),
+    add column rir integer check (rir >= 0),
+    add column tempo varchar(4) check (tempo ~ '^[0-9X]{4}$'),
+    add column rest integer check (rest >= 0), -- milliseconds
+    add column is_warmup boolean not null default false;
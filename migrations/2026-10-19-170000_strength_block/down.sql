@@ -0,0 +1,10 @@
+drop index strength_session__strength_block_id__block_position__key;
+
+alter table strength_session
+    drop column strength_block_id,
+    drop column block_position;
+
+drop table strength_block_archive;
+drop table strength_block;
+
+drop type strength_block_type;
@@ -0,0 +1,37 @@
+create type strength_block_type as enum('superset', 'circuit');
+
+create table strength_block (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    datetime timestamptz not null default now(),
+    block_type strength_block_type not null,
+    interval integer check (interval > 0), -- milliseconds
+    comments text,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create index strength_block__user_id__epoch__idx
+    on strength_block (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on strength_block
+    for each row execute function set_epoch_for_user();
+
+create table strength_block_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (strength_block);
+
+create trigger archive_strength_block
+    after insert or update of deleted or delete
+    on strength_block
+    for each row execute procedure archive_record();
+
+alter table strength_session
+    add column strength_block_id bigint references strength_block on delete set null,
+    add column block_position integer check (block_position >= 0),
+    add check ((strength_block_id is null) = (block_position is null));
+
+create unique index strength_session__strength_block_id__block_position__key
+    on strength_session (strength_block_id, block_position) where deleted = false;
@@ -0,0 +1,7 @@
+drop table user_eorm_archive;
+drop table user_eorm;
+
+alter table "user"
+    drop column eorm_formula;
+
+drop type eorm_formula;
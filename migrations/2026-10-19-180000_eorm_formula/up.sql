@@ -0,0 +1,33 @@
+create type eorm_formula as enum('table', 'epley', 'brzycki', 'lombardi', 'custom');
+
+alter table "user"
+    add column eorm_formula eorm_formula not null default 'table';
+
+create table user_eorm (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    reps integer not null check (reps >= 1),
+    percentage real not null check (percentage > 0 and percentage <= 1),
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index user_eorm__user_id__reps__key
+    on user_eorm (user_id, reps) where deleted = false;
+
+create index user_eorm__user_id__epoch__idx
+    on user_eorm (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on user_eorm
+    for each row execute function set_epoch_for_user();
+
+create table user_eorm_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (user_eorm);
+
+create trigger archive_user_eorm
+    after insert or update of deleted or delete
+    on user_eorm
+    for each row execute procedure archive_record();
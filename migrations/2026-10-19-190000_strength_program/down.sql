@@ -0,0 +1,4 @@
+drop table strength_program_set_archive;
+drop table strength_program_set;
+drop table strength_program_archive;
+drop table strength_program;
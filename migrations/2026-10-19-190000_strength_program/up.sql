@@ -0,0 +1,88 @@
+create table strength_program (
+    id bigint primary key,
+    user_id bigint references "user" on delete cascade,
+    name varchar(80) not null check (length(name) >= 2),
+    description text,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index strength_program__user_id__name__key
+    on strength_program (user_id, name) nulls not distinct where deleted = false;
+
+create index strength_program__user_id__epoch__idx
+    on strength_program (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on strength_program
+    for each row execute function set_epoch_for_user();
+
+create table strength_program_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (strength_program);
+
+create trigger archive_strength_program
+    after insert or update of deleted or delete
+    on strength_program
+    for each row execute procedure archive_record();
+
+create table strength_program_set (
+    id bigint primary key,
+    user_id bigint references "user" on delete cascade,
+    strength_program_id bigint not null references strength_program on delete cascade,
+    week integer not null check (week >= 1),
+    day integer not null check (day >= 1),
+    set_number integer not null check (set_number >= 0),
+    count integer not null check (count >= 1),
+    percentage real not null check (percentage > 0), -- of the training max
+    amrap boolean not null default false,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index strength_program_set__strength_program_id__user_id__week__day__set_number__key
+    on strength_program_set (strength_program_id, user_id, week, day, set_number)
+    nulls not distinct where deleted = false;
+
+create index strength_program_set__user_id__epoch__idx
+    on strength_program_set (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on strength_program_set
+    for each row execute function set_epoch_for_user();
+
+create table strength_program_set_archive (
+    primary key (id),
+    check (deleted = true)
+) inherits (strength_program_set);
+
+create trigger archive_strength_program_set
+    after insert or update of deleted or delete
+    on strength_program_set
+    for each row execute procedure archive_record();
+
+insert into strength_program (id, user_id, name, description) values
+    (1, null, '5/3/1', 'Four week cycle with a deload week. The training max is 90 % of the one rep max.'),
+    (2, null, 'Texas Method', 'Weekly cycle with a volume day, a recovery day and an intensity day.');
+
+insert into strength_program_set (id, strength_program_id, week, day, set_number, count, percentage, amrap) values
+    (1, 1, 1, 1, 0, 5, 0.65, false),
+    (2, 1, 1, 1, 1, 5, 0.75, false),
+    (3, 1, 1, 1, 2, 5, 0.85, true),
+    (4, 1, 2, 1, 0, 3, 0.70, false),
+    (5, 1, 2, 1, 1, 3, 0.80, false),
+    (6, 1, 2, 1, 2, 3, 0.90, true),
+    (7, 1, 3, 1, 0, 5, 0.75, false),
+    (8, 1, 3, 1, 1, 3, 0.85, false),
+    (9, 1, 3, 1, 2, 1, 0.95, true),
+    (10, 1, 4, 1, 0, 5, 0.40, false),
+    (11, 1, 4, 1, 1, 5, 0.50, false),
+    (12, 1, 4, 1, 2, 5, 0.60, false),
+    (13, 2, 1, 1, 0, 5, 0.80, false),
+    (14, 2, 1, 1, 1, 5, 0.80, false),
+    (15, 2, 1, 1, 2, 5, 0.80, false),
+    (16, 2, 1, 1, 3, 5, 0.80, false),
+    (17, 2, 1, 1, 4, 5, 0.80, false),
+    (18, 2, 1, 2, 0, 5, 0.65, false),
+    (19, 2, 1, 2, 1, 5, 0.65, false),
+    (20, 2, 1, 3, 0, 5, 0.90, false);
@@ -0,0 +1,8 @@
+alter table cardio_session drop column planned_workout_id;
+alter table metcon_session drop column planned_workout_id;
+alter table strength_session drop column planned_workout_id;
+
+drop table planned_workout_archive;
+drop table planned_workout;
+
+drop type planned_workout_type;
@@ -0,0 +1,48 @@
+create type planned_workout_type as enum('strength', 'metcon', 'cardio');
+
+create table planned_workout (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    datetime timestamptz not null,
+    planned_workout_type planned_workout_type not null,
+    metcon_id bigint references metcon on delete cascade,
+    movement_id bigint references movement on delete cascade,
+    distance integer check (distance > 0), -- meter
+    time integer check (time > 0), -- milliseconds
+    comments text,
+    epoch bigint not null,
+    deleted boolean not null default false,
+    check (
+        case planned_workout_type
+            when 'metcon' then metcon_id is not null and movement_id is null
+            else metcon_id is null and movement_id is not null
+        end
+    ),
+    check (planned_workout_type = 'cardio' or (distance is null and time is null))
+);
+
+create index planned_workout__user_id__epoch__idx
+    on planned_workout (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on planned_workout
+    for each row execute function set_epoch_for_user();
+
+create table planned_workout_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (planned_workout);
+
+create trigger archive_planned_workout
+    after insert or update of deleted or delete
+    on planned_workout
+    for each row execute procedure archive_record();
+
+alter table strength_session
+    add column planned_workout_id bigint references planned_workout on delete set null;
+
+alter table metcon_session
+    add column planned_workout_id bigint references planned_workout on delete set null;
+
+alter table cardio_session
+    add column planned_workout_id bigint references planned_workout on delete set null;
@@ -0,0 +1,14 @@
+alter table planned_workout
+    drop constraint planned_workout_check,
+    drop column strength_template_id,
+    add constraint planned_workout_check check (
+        case planned_workout_type
+            when 'metcon' then metcon_id is not null and movement_id is null
+            else metcon_id is null and movement_id is not null
+        end
+    );
+
+drop table strength_template_movement_archive;
+drop table strength_template_movement;
+drop table strength_template_archive;
+drop table strength_template;
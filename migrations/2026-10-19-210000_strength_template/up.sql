@@ -0,0 +1,78 @@
+create table strength_template (
+    id bigint primary key,
+    user_id bigint references "user" on delete cascade,
+    name varchar(80) not null check (length(name) >= 2),
+    description text,
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index strength_template__user_id__name__key
+    on strength_template (user_id, name) nulls not distinct where deleted = false;
+
+create index strength_template__user_id__epoch__idx
+    on strength_template (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on strength_template
+    for each row execute function set_epoch_for_user();
+
+create table strength_template_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (strength_template);
+
+create trigger archive_strength_template
+    after insert or update of deleted or delete
+    on strength_template
+    for each row execute procedure archive_record();
+
+create table strength_template_movement (
+    id bigint primary key,
+    user_id bigint references "user" on delete cascade,
+    strength_template_id bigint not null references strength_template on delete cascade,
+    movement_id bigint not null references movement on delete cascade,
+    movement_number integer not null check (movement_number >= 0),
+    sets integer not null check (sets >= 1),
+    count integer not null check (count >= 1),
+    weight real check (weight > 0),
+    percentage real check (percentage > 0), -- of the estimated one rep max
+    epoch bigint not null,
+    deleted boolean not null default false,
+    check (weight is null or percentage is null)
+);
+
+create unique index strength_template_movement__strength_template_id__user_id__movement_number__key
+    on strength_template_movement (strength_template_id, user_id, movement_number)
+    nulls not distinct where deleted = false;
+
+create index strength_template_movement__user_id__epoch__idx
+    on strength_template_movement (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on strength_template_movement
+    for each row execute function set_epoch_for_user();
+
+create table strength_template_movement_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (strength_template_movement);
+
+create trigger archive_strength_template_movement
+    after insert or update of deleted or delete
+    on strength_template_movement
+    for each row execute procedure archive_record();
+
+alter table planned_workout
+    add column strength_template_id bigint references strength_template on delete cascade,
+    drop constraint planned_workout_check,
+    add constraint planned_workout_check check (
+        case planned_workout_type
+            when 'metcon' then
+                metcon_id is not null and movement_id is null and strength_template_id is null
+            when 'strength' then
+                metcon_id is null and (movement_id is null) != (strength_template_id is null)
+            else
+                metcon_id is null and movement_id is not null and strength_template_id is null
+        end
+    );
@@ -0,0 +1,21 @@
+-- the new metcon types can not be represented by the old enum
+-- refuse to downgrade instead of deleting metcons and their sessions
+do $$
+begin
+    if exists (
+        select 1 from only metcon where metcon_type in ('tabata', 'death_by', 'ladder', 'chipper')
+    ) then
+        raise exception 'metcons of type tabata, death_by, ladder or chipper exist, delete or convert them before downgrading';
+    end if;
+end
+$$;
+
+-- deleted metcons of the new types are only kept in the archive and are dropped
+delete from metcon_archive where metcon_type in ('tabata', 'death_by', 'ladder', 'chipper');
+
+alter table metcon_session drop column round_reps;
+
+alter type metcon_type rename to metcon_type_old;
+create type metcon_type as enum('amrap', 'emom', 'for_time');
+alter table metcon alter column metcon_type type metcon_type using metcon_type::text::metcon_type;
+drop type metcon_type_old;
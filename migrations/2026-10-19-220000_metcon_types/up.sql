@@ -0,0 +1,7 @@
+alter type metcon_type add value 'tabata';
+alter type metcon_type add value 'death_by';
+alter type metcon_type add value 'ladder';
+alter type metcon_type add value 'chipper';
+
+alter table metcon_session
+    add column round_reps integer[] check (0 <= all(round_reps));
@@ -0,0 +1,6 @@
+drop table metcon_session_scaling_archive;
+drop table metcon_session_scaling;
+drop table metcon_movement_scaling_archive;
+drop table metcon_movement_scaling;
+
+drop type scaling_level;
@@ -0,0 +1,64 @@
+create type scaling_level as enum('rx', 'scaled', 'foundations');
+
+create table metcon_movement_scaling (
+    id bigint primary key,
+    user_id bigint references "user" on delete cascade,
+    metcon_movement_id bigint not null references metcon_movement on delete cascade,
+    scaling_level scaling_level not null check (scaling_level <> 'rx'),
+    movement_id bigint not null references movement on delete cascade,
+    distance_unit distance_unit,
+    count integer not null check (count >= 1),
+    male_weight real check (male_weight > 0),
+    female_weight real check (female_weight > 0),
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index metcon_movement_scaling__metcon_movement_id__scaling_level__key
+    on metcon_movement_scaling (metcon_movement_id, scaling_level) where deleted = false;
+
+create index metcon_movement_scaling__user_id__epoch__idx
+    on metcon_movement_scaling (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on metcon_movement_scaling
+    for each row execute function set_epoch_for_user();
+
+create table metcon_movement_scaling_archive (
+    primary key (id),
+    check (deleted = true)
+) inherits (metcon_movement_scaling);
+
+create trigger archive_metcon_movement_scaling
+    after insert or update of deleted or delete
+    on metcon_movement_scaling
+    for each row execute procedure archive_record();
+
+create table metcon_session_scaling (
+    id bigint primary key,
+    user_id bigint not null references "user" on delete cascade,
+    metcon_session_id bigint not null references metcon_session on delete cascade,
+    metcon_movement_id bigint not null references metcon_movement on delete cascade,
+    scaling_level scaling_level not null check (scaling_level <> 'rx'),
+    epoch bigint not null,
+    deleted boolean not null default false
+);
+
+create unique index metcon_session_scaling__metcon_session_id__metcon_movement_id__key
+    on metcon_session_scaling (metcon_session_id, metcon_movement_id) where deleted = false;
+
+create index metcon_session_scaling__user_id__epoch__idx
+    on metcon_session_scaling (user_id, epoch) where deleted = false;
+
+create trigger set_epoch before insert or update on metcon_session_scaling
+    for each row execute function set_epoch_for_user();
+
+create table metcon_session_scaling_archive (
+    primary key (id),
+    foreign key (user_id) references "user" on delete cascade,
+    check (deleted = true)
+) inherits (metcon_session_scaling);
+
+create trigger archive_metcon_session_scaling
+    after insert or update of deleted or delete
+    on metcon_session_scaling
+    for each row execute procedure archive_record();
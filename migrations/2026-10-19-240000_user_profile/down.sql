@@ -0,0 +1,11 @@
+alter table "user"
+    drop column sex,
+    drop column birthdate,
+    drop column weight_unit,
+    drop column distance_unit,
+    drop column timezone,
+    drop column max_heart_rate;
+
+drop type weight_unit;
+
+drop type sex;
@@ -0,0 +1,11 @@
+create type sex as enum('male', 'female');
+
+create type weight_unit as enum('kg', 'lb');
+
+alter table "user"
+    add column sex sex,
+    add column birthdate date,
+    add column weight_unit weight_unit not null default 'kg',
+    add column distance_unit distance_unit not null default 'km',
+    add column timezone varchar(80) not null default 'UTC',
+    add column max_heart_rate integer check (max_heart_rate > 0 and max_heart_rate <= 250);
@@ -0,0 +1,9 @@
+drop trigger set_route_bounds on route;
+
+drop function set_route_bounds;
+
+alter table route
+    drop column min_latitude,
+    drop column max_latitude,
+    drop column min_longitude,
+    drop column max_longitude;
@@ -0,0 +1,27 @@
+alter table route
+    add column min_latitude double precision,
+    add column max_latitude double precision,
+    add column min_longitude double precision,
+    add column max_longitude double precision;
+
+create index route__user_id__bounds__idx
+    on route (user_id, min_latitude, max_latitude, min_longitude, max_longitude)
+    where deleted = false;
+
+create function set_route_bounds()
+    returns trigger as $$
+    begin
+        select min(latitude), max(latitude), min(longitude), max(longitude)
+        into new.min_latitude, new.max_latitude, new.min_longitude, new.max_longitude
+        from unnest(new.track);
+        return new;
+    end;
+    $$ language plpgsql;
+
+create trigger set_route_bounds before insert or update of track on route
+    for each row execute function set_route_bounds();
+
+-- compute the bounds of existing routes without bumping their epochs
+alter table route disable trigger set_epoch;
+update route set track = track where track is not null;
+alter table route enable trigger set_epoch;
@@ -0,0 +1,4 @@
+alter table movement
+    drop column muscle_groups;
+
+drop type muscle_group;
@@ -0,0 +1,55 @@
+create type muscle_group as enum(
+    'chest',
+    'back',
+    'shoulders',
+    'arms',
+    'core',
+    'quadriceps',
+    'hamstrings',
+    'glutes'
+);
+
+alter table movement
+    add column muscle_groups muscle_group[] not null default '{}';
+
+-- the epoch of the predefined movements is updated so that clients fetch the muscle groups
+update movement set muscle_groups = '{quadriceps, glutes}'
+    where user_id is null and id in (22, 23, 24, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 85, 95, 96, 129, 130, 131, 93, 161);
+update movement set muscle_groups = '{quadriceps, glutes, shoulders, core}'
+    where user_id is null and id in (25, 26);
+update movement set muscle_groups = '{quadriceps, glutes, shoulders}'
+    where user_id is null and id in (37, 38, 39, 40, 69, 70, 124, 163);
+update movement set muscle_groups = '{hamstrings, glutes, back}'
+    where user_id is null and id in (41, 42);
+update movement set muscle_groups = '{hamstrings, glutes}'
+    where user_id is null and id in (43, 82, 83, 84, 127, 162);
+update movement set muscle_groups = '{hamstrings, glutes, shoulders}'
+    where user_id is null and id = 44;
+update movement set muscle_groups = '{hamstrings, back}'
+    where user_id is null and id = 45;
+update movement set muscle_groups = '{back, arms}'
+    where user_id is null and id in (46, 47, 94, 106, 107, 108, 109, 110, 111, 112, 118, 119, 120, 122, 123, 149, 159);
+update movement set muscle_groups = '{chest, arms}'
+    where user_id is null and id in (48, 49, 97, 98, 99, 117, 160);
+update movement set muscle_groups = '{shoulders, arms}'
+    where user_id is null and id in (50, 51, 101, 102, 103, 104, 105, 133, 134, 152, 165, 167);
+update movement set muscle_groups = '{shoulders, arms, quadriceps}'
+    where user_id is null and id in (52, 53, 54, 55, 56, 88);
+update movement set muscle_groups = '{quadriceps, hamstrings, glutes, back}'
+    where user_id is null and id in (57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 87, 125, 156, 164);
+update movement set muscle_groups = '{quadriceps, hamstrings, glutes, back, shoulders}'
+    where user_id is null and id in (67, 68, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 86, 89, 148, 166);
+update movement set muscle_groups = '{shoulders, core}'
+    where user_id is null and id in (81, 90, 135);
+update movement set muscle_groups = '{back, core}'
+    where user_id is null and id in (91, 92, 113, 114, 115, 116, 155);
+update movement set muscle_groups = '{chest, quadriceps}'
+    where user_id is null and id in (100, 132);
+update movement set muscle_groups = '{core}'
+    where user_id is null and id in (121, 128, 136, 137, 138, 139, 140, 141, 142, 143);
+update movement set muscle_groups = '{back, glutes}'
+    where user_id is null and id = 126;
+update movement set muscle_groups = '{hamstrings}'
+    where user_id is null and id in (157, 158);
+update movement set muscle_groups = '{arms}'
+    where user_id is null and id in (150, 151);
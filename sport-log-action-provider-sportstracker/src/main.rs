@@ -420,6 +420,8 @@ fn try_into_cardio_session(
         distance: Some(workout_stats.total_distance as i32),
         ascent: Some(workout_stats.total_ascent as i32),
         descent: Some(workout_stats.total_descent as i32),
+        uncorrected_ascent: None,
+        uncorrected_descent: None,
         time: Some(workout_stats.total_time as i32 * 1000),
         calories: Some(i32::from(workout_stats.energy_consumption)),
         track: Some(track),
@@ -427,7 +429,15 @@ fn try_into_cardio_session(
         cadence: None,
         avg_heart_rate: None,
         heart_rate: None,
+        avg_power: None,
+        max_power: None,
+        normalized_power: None,
+        power: None,
+        speed: None,
+        temperature: None,
         route_id: None,
+        gear_id: None,
+        planned_workout_id: None,
         comments: workout_stats.description,
         deleted: false,
     })
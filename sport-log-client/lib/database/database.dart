@@ -102,13 +102,73 @@ abstract final class AppDatabase {
     await db.execute(statement);
   }
 
+  /// Recreates the table with its current definition and copies the data over.
+  static Future<void> _recreateTable(
+    DatabaseExecutor db,
+    TableAccessor tableAccessor,
+  ) async {
+    // see: https://www.sqlite.org/lang_altertable.html
+    // "alter table drop column" only supported beginning with sqlite 3.35.0
+    // "alter table alter column drop not null" not supported
+    // therefore create new table and copy all relevant data over
+    final table = tableAccessor.table.name;
+    final newTable = "new_$table";
+
+    // create new table
+    final tableSetupSql = tableAccessor.table.withName(newTable).tableSetupSql;
+    await _execute(db, tableSetupSql);
+
+    // insert all columns of new table from old table into new table
+    final columns = tableAccessor.table.columns.map((c) => c.name).join(', ');
+    await _execute(
+      db,
+      "insert into $newTable ($columns) select $columns from $table;",
+    );
+
+    // drop old table
+    await _execute(db, "drop table $table;");
+
+    // rename new table to original name
+    await _execute(db, "alter table $newTable rename to $table;");
+
+    // create indices, triggers and rawSql for new renamed tabled
+    final setupSql = [
+      ...tableAccessor.table.uniqueIndicesSetupSql,
+      tableAccessor.table.triggerSetupSql,
+      ...tableAccessor.table.rawSql,
+    ];
+    for (final statement in setupSql) {
+      await _execute(db, statement);
+    }
+  }
+
+  /// Adds the columns with the names in `columnNames` as they are defined in
+  /// the current table definition.
+  ///
+  /// The columns must be nullable or have a default value.
+  static Future<void> _addColumns(
+    DatabaseExecutor db,
+    TableAccessor tableAccessor,
+    List<String> columnNames,
+  ) async {
+    final table = tableAccessor.table;
+    for (final column in table.columns) {
+      if (columnNames.contains(column.name)) {
+        await _execute(
+          db,
+          "alter table ${table.name} add column ${column.setUpSql()};",
+        );
+      }
+    }
+  }
+
   // ignore: long-method
   static Future<void> open() async {
     _logger.i("opening database");
     _database = await databaseFactory.openDatabase(
       Config.databaseName,
       options: OpenDatabaseOptions(
-        version: 2,
+        version: 3,
         onCreate: (db, version) async {
           for (final table in _tables) {
             _logger.i("creating table: ${table.tableName}");
@@ -177,44 +237,62 @@ abstract final class AppDatabase {
               ActionEventTable(), // drop user_id
             ];
             for (final tableAccessor in dropColumnTables) {
-              // see: https://www.sqlite.org/lang_altertable.html
-              // "alter table drop column" only supported beginning with sqlite 3.35.0
-              // "alter table alter column drop not null" not supported
-              // therefore create new table and copy all relevant data over
-              final table = tableAccessor.table.name;
-              final newTable = "new_$table";
-
-              // create new table
-              final tableSetupSql =
-                  tableAccessor.table.withName(newTable).tableSetupSql;
-              await _execute(db, tableSetupSql);
-
-              // insert all columns of new table from old table into new table
-              final columns =
-                  tableAccessor.table.columns.map((c) => c.name).join(', ');
-              await _execute(
-                db,
-                "insert into $newTable ($columns) select $columns from $table;",
-              );
-
-              // drop old table
-              await _execute(db, "drop table $table;");
-
-              // rename new table to original name
-              await _execute(db, "alter table $newTable rename to $table;");
-
-              // create indices, triggers and rawSql for new renamed tabled
-              final setupSql = [
-                ...tableAccessor.table.uniqueIndicesSetupSql,
-                tableAccessor.table.triggerSetupSql,
-                ...tableAccessor.table.rawSql,
-              ];
-              for (final statement in setupSql) {
-                await _execute(db, statement);
-              }
+              await _recreateTable(db, tableAccessor);
             }
             _logger.i("migration to version 2 done");
           }
+          if (oldVersion < 3 && newVersion >= 3) {
+            // allow the metcon types tabata, death by, ladder and chipper
+            await _recreateTable(db, MetconTable());
+            // add the power, speed and temperature samples and the elevation
+            // correction of cardio sessions
+            await _addColumns(db, CardioSessionTable(), [
+              Columns.uncorrectedAscent,
+              Columns.uncorrectedDescent,
+              Columns.avgPower,
+              Columns.maxPower,
+              Columns.normalizedPower,
+              Columns.power,
+              Columns.speed,
+              Columns.temperature,
+            ]);
+            // keep the gear of cardio sessions
+            await _addColumns(db, CardioSessionTable(), [Columns.gearId]);
+            // add the rpe, rir, tempo, rest and warm-up flag of strength sets
+            await _addColumns(db, StrengthSetTable(), [
+              Columns.rpe,
+              Columns.rir,
+              Columns.tempo,
+              Columns.rest,
+              Columns.isWarmup,
+            ]);
+            // keep the strength blocks of strength sessions
+            await _addColumns(db, StrengthSessionTable(), [
+              Columns.strengthBlockId,
+              Columns.blockPosition,
+            ]);
+            // keep the muscle groups of movements
+            await _addColumns(db, MovementTable(), [Columns.muscleGroups]);
+            // keep the planned workouts of sessions
+            await _addColumns(
+              db,
+              CardioSessionTable(),
+              [Columns.plannedWorkoutId],
+            );
+            await _addColumns(
+              db,
+              StrengthSessionTable(),
+              [Columns.plannedWorkoutId],
+            );
+            await _addColumns(
+              db,
+              MetconSessionTable(),
+              [Columns.plannedWorkoutId],
+            );
+            // keep the round reps of metcon sessions
+            await _addColumns(db, MetconSessionTable(), [Columns.roundReps]);
+            _logger.i("migration to version 3 done");
+          }
           _logger.i("database migration done");
         },
         //onDowngrade: null,
@@ -220,6 +220,8 @@ abstract class Columns {
   static const avgCadence = 'avg_cadence';
   static const avgCount = 'avg_count';
   static const avgHeartRate = 'avg_heart_rate';
+  static const avgPower = 'avg_power';
+  static const blockPosition = 'block_position';
   static const bodyweight = 'bodyweight';
   static const cadence = 'cadence';
   static const calories = 'calories';
@@ -240,39 +242,56 @@ abstract class Columns {
   static const eormPercentage = 'eorm_percentage';
   static const eormReps = 'eorm_reps';
   static const femaleWeight = 'female_weight';
+  static const gearId = 'gear_id';
   static const heartRate = 'heart_rate';
   static const id = 'id';
   static const interval = 'interval';
   static const isDefaultMetcon = 'is_default_metcon';
   static const isDefaultMovement = 'is_default_movement';
+  static const isWarmup = 'is_warmup';
   static const maleWeight = 'male_weight';
   static const markedPositions = 'marked_positions';
   static const maxCount = 'max_count';
   static const maxEorm = 'max_eorm';
+  static const maxPower = 'max_power';
   static const maxWeight = 'max_weight';
   static const metconId = 'metcon_id';
   static const metconType = 'metcon_type';
   static const minCount = 'min_count';
   static const movementId = 'movement_id';
   static const movementNumber = 'movement_number';
+  static const muscleGroups = 'muscle_groups';
   static const name = 'name';
+  static const normalizedPower = 'normalized_power';
   static const numSets = 'num_sets';
   static const password = 'password';
+  static const plannedWorkoutId = 'planned_workout_id';
   static const platformId = 'platform_id';
+  static const power = 'power';
   static const reps = 'reps';
+  static const rest = 'rest';
+  static const rir = 'rir';
+  static const roundReps = 'round_reps';
   static const rounds = 'rounds';
   static const roundsAndReps = 'rounds_and_reps';
   static const routeId = 'route_id';
+  static const rpe = 'rpe';
   static const rx = 'rx';
   static const setNumber = 'set_number';
+  static const speed = 'speed';
+  static const strengthBlockId = 'strength_block_id';
   static const strengthSessionId = 'strength_session_id';
   static const sumCount = 'sum_count';
   static const sumVolume = 'sum_volume';
   static const syncNeeded = 'sync_needed';
   static const syncStatus = 'sync_status';
+  static const temperature = 'temperature';
+  static const tempo = 'tempo';
   static const time = 'time';
   static const timecap = 'timecap';
   static const track = 'track';
+  static const uncorrectedAscent = 'uncorrected_ascent';
+  static const uncorrectedDescent = 'uncorrected_descent';
   static const username = 'username';
   static const weekday = 'weekday';
   static const weight = 'weight';
@@ -86,6 +86,12 @@ class CardioSessionTable extends TableAccessor<CardioSession> {
       Column.int(Columns.descent)
         ..nullable()
         ..checkGe(0),
+      Column.int(Columns.uncorrectedAscent)
+        ..nullable()
+        ..checkGe(0),
+      Column.int(Columns.uncorrectedDescent)
+        ..nullable()
+        ..checkGe(0),
       Column.int(Columns.time)
         ..nullable()
         ..checkGt(0),
@@ -101,9 +107,23 @@ class CardioSessionTable extends TableAccessor<CardioSession> {
         ..nullable()
         ..checkGt(0),
       Column.blob(Columns.heartRate)..nullable(),
+      Column.int(Columns.avgPower)
+        ..nullable()
+        ..checkGe(0),
+      Column.int(Columns.maxPower)
+        ..nullable()
+        ..checkGe(0),
+      Column.int(Columns.normalizedPower)
+        ..nullable()
+        ..checkGe(0),
+      Column.blob(Columns.power)..nullable(),
+      Column.blob(Columns.speed)..nullable(),
+      Column.blob(Columns.temperature)..nullable(),
       Column.int(Columns.routeId)
         ..nullable()
         ..references(Tables.route, onDelete: OnAction.setNull),
+      Column.int(Columns.gearId)..nullable(),
+      Column.int(Columns.plannedWorkoutId)..nullable(),
       Column.text(Columns.comments)..nullable(),
     ],
     uniqueColumns: [],
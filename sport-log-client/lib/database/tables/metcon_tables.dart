@@ -25,7 +25,7 @@ class MetconTable extends TableAccessor<Metcon> {
         ..checkIn(<int>[0, 1, 2]),
       Column.bool(Columns.isDefaultMetcon),
       Column.text(Columns.name)..checkLengthBetween(2, 80),
-      Column.int(Columns.metconType)..checkBetween(0, 2),
+      Column.int(Columns.metconType)..checkBetween(0, 6),
       Column.int(Columns.rounds)
         ..nullable()
         ..checkGe(1),
@@ -154,8 +154,10 @@ class MetconSessionTable extends TableAccessor<MetconSession> {
       Column.int(Columns.reps)
         ..nullable()
         ..checkGe(0),
+      Column.blob(Columns.roundReps)..nullable(),
       Column.bool(Columns.rx)..checkIn(<int>[0, 1]),
       Column.text(Columns.comments)..nullable(),
+      Column.int(Columns.plannedWorkoutId)..nullable(),
     ],
     uniqueColumns: [],
   );
@@ -198,7 +200,7 @@ class MetconSessionTable extends TableAccessor<MetconSession> {
       select 
         ${Tables.metconSession}.${Columns.metconId}, 
         min(${Tables.metconSession}.${Columns.time}) as ${Columns.time}, 
-        max(${Tables.metconSession}.${Columns.rounds} * ${MetconRecord.multiplier} + ${Tables.metconSession}.${Columns.reps}) as ${Columns.roundsAndReps}
+        max(ifnull(${Tables.metconSession}.${Columns.rounds}, 0) * ${MetconRecord.multiplier} + ifnull(${Tables.metconSession}.${Columns.reps}, 0)) as ${Columns.roundsAndReps}
       from ${Tables.metconSession}
       where ${TableAccessor.combineFilter([
             notDeleted,
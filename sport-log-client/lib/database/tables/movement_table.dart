@@ -27,6 +27,7 @@ class MovementTable extends TableAccessor<Movement> {
       Column.text(Columns.description)..nullable(),
       Column.bool(Columns.cardio),
       Column.int(Columns.dimension),
+      Column.int(Columns.muscleGroups)..withDefault('0'),
     ],
     uniqueColumns: [
       [Columns.name, Columns.dimension],
@@ -32,6 +32,11 @@ class StrengthSessionTable extends TableAccessor<StrengthSession> {
         ..nullable()
         ..checkGt(0),
       Column.text(Columns.comments)..nullable(),
+      Column.int(Columns.strengthBlockId)..nullable(),
+      Column.int(Columns.blockPosition)
+        ..nullable()
+        ..checkGe(0),
+      Column.int(Columns.plannedWorkoutId)..nullable(),
     ],
     uniqueColumns: [],
   );
@@ -90,6 +95,19 @@ class StrengthSetTable extends TableAccessor<StrengthSet> {
       Column.real(Columns.weight)
         ..nullable()
         ..checkGt(0),
+      Column.real(Columns.rpe)
+        ..nullable()
+        ..checkBetween(1, 10),
+      Column.int(Columns.rir)
+        ..nullable()
+        ..checkGe(0),
+      Column.text(Columns.tempo)
+        ..nullable()
+        ..checkLengthBetween(4, 4),
+      Column.int(Columns.rest)
+        ..nullable()
+        ..checkGe(0),
+      Column.bool(Columns.isWarmup)..withDefault('0'),
     ],
     uniqueColumns: [
       [Columns.strengthSessionId, Columns.setNumber],
@@ -26,6 +26,28 @@ class DbDurationListConverter {
   }
 }
 
+class DbIntListConverter {
+  const DbIntListConverter._() : super();
+
+  static List<int>? mapToDart(Uint8List? fromDb) {
+    assert(fromDb == null || fromDb.length % 4 == 0);
+    if (fromDb == null) {
+      return null;
+    }
+    final list = <int>[];
+    for (var index = 0; index < fromDb.length; index += 4) {
+      list.add(ByteData.sublistView(fromDb).getInt32(index, Endian.host));
+    }
+    return list;
+  }
+
+  static Uint8List? mapToSql(List<int>? value) {
+    return value == null
+        ? null
+        : Int32List.fromList(value).buffer.asUint8List();
+  }
+}
+
 class DbPositionListConverter {
   const DbPositionListConverter._() : super();
 
@@ -56,3 +78,32 @@ class DbPositionListConverter {
     return bytes;
   }
 }
+
+class DbSampleListConverter {
+  const DbSampleListConverter._() : super();
+
+  static List<Sample>? mapToDart(Uint8List? fromDb) {
+    if (fromDb == null) {
+      return null;
+    }
+    assert(fromDb.length % Sample.byteSize == 0);
+    final samples = <Sample>[];
+    for (var i = 0; i < fromDb.length; i += Sample.byteSize) {
+      samples.add(Sample.fromBytesList(fromDb.sublist(i, i + Sample.byteSize)));
+    }
+    return samples;
+  }
+
+  static Uint8List? mapToSql(List<Sample>? value) {
+    if (value == null) {
+      return null;
+    }
+    final bytes = Uint8List(value.length * Sample.byteSize);
+    var pos = 0;
+    for (final sample in value) {
+      bytes.setAll(pos, sample.asBytesList());
+      pos += Sample.byteSize;
+    }
+    return bytes;
+  }
+}
@@ -44,6 +44,20 @@ class DateConverter extends JsonConverter<DateTime, String> {
   }
 }
 
+class OptionalDateConverter extends JsonConverter<DateTime?, String?> {
+  const OptionalDateConverter() : super();
+
+  @override
+  DateTime? fromJson(String? json) {
+    return json == null ? null : DateTime.parse(json).toLocal();
+  }
+
+  @override
+  String? toJson(DateTime? object) {
+    return object == null ? null : DateFormat('yyyy-MM-dd').format(object);
+  }
+}
+
 class DateTimeConverter extends JsonConverter<DateTime, String> {
   const DateTimeConverter() : super();
 
@@ -2,3 +2,4 @@ export 'cardio_session.dart';
 export 'cardio_session_description.dart';
 export 'position.dart';
 export 'route.dart';
+export 'sample.dart';
@@ -11,6 +11,7 @@ import 'package:sport_log/helpers/search.dart';
 import 'package:sport_log/helpers/serialization/db_serialization.dart';
 import 'package:sport_log/helpers/serialization/json_serialization.dart';
 import 'package:sport_log/models/cardio/position.dart';
+import 'package:sport_log/models/cardio/sample.dart';
 import 'package:sport_log/models/clone_extensions.dart';
 import 'package:sport_log/models/entity_interfaces.dart';
 import 'package:sport_log/settings.dart';
@@ -40,6 +41,8 @@ class CardioSession extends AtomicEntity {
     required this.distance,
     required this.ascent,
     required this.descent,
+    required this.uncorrectedAscent,
+    required this.uncorrectedDescent,
     required this.time,
     required this.calories,
     required this.track,
@@ -47,7 +50,15 @@ class CardioSession extends AtomicEntity {
     required this.cadence,
     required this.avgHeartRate,
     required this.heartRate,
+    required this.avgPower,
+    required this.maxPower,
+    required this.normalizedPower,
+    required this.powerSamples,
+    required this.speedSamples,
+    required this.temperatureSamples,
     required this.routeId,
+    required this.gearId,
+    required this.plannedWorkoutId,
     required this.comments,
     required this.deleted,
   });
@@ -75,6 +86,12 @@ class CardioSession extends AtomicEntity {
   int? distance;
   int? ascent;
   int? descent;
+
+  /// The ascent before the elevation of the track was corrected by the server.
+  int? uncorrectedAscent;
+
+  /// The descent before the elevation of the track was corrected by the server.
+  int? uncorrectedDescent;
   @OptionalDurationConverter()
   Duration? time;
   int? calories;
@@ -85,8 +102,37 @@ class CardioSession extends AtomicEntity {
   int? avgHeartRate;
   @OptionalDurationListConverter()
   List<Duration>? heartRate;
+
+  /// watt
+  int? avgPower;
+
+  /// watt
+  int? maxPower;
+
+  /// watt
+  int? normalizedPower;
+
+  /// watt
+  @JsonKey(name: "power")
+  List<Sample>? powerSamples;
+
+  /// m/s
+  @JsonKey(name: "speed")
+  List<Sample>? speedSamples;
+
+  /// °C
+  @JsonKey(name: "temperature")
+  List<Sample>? temperatureSamples;
   @OptionalIdConverter()
   Int64? routeId;
+
+  /// The gear is managed by the server and only kept by the client.
+  @OptionalIdConverter()
+  Int64? gearId;
+
+  /// The planned workout is managed by the server and only kept by the client.
+  @OptionalIdConverter()
+  Int64? plannedWorkoutId;
   String? comments;
   @override
   bool deleted;
@@ -181,6 +227,15 @@ class CardioSession extends AtomicEntity {
     if (heartRate != null && heartRate!.isEmpty) {
       heartRate = null;
     }
+    if (powerSamples != null && powerSamples!.isEmpty) {
+      powerSamples = null;
+    }
+    if (speedSamples != null && speedSamples!.isEmpty) {
+      speedSamples = null;
+    }
+    if (temperatureSamples != null && temperatureSamples!.isEmpty) {
+      temperatureSamples = null;
+    }
   }
 
   void setDistance() => distance = track?.lastOrNull?.distance.round();
@@ -217,6 +272,36 @@ class CardioSession extends AtomicEntity {
         : (heartRate!.length / (time!.inMinuteFractions)).round();
   }
 
+  /// The normalized power can not be derived from the samples alone and is
+  /// reset.
+  void setPower() {
+    final power = powerSamples;
+    if (power == null || power.isEmpty) {
+      avgPower = null;
+      maxPower = null;
+    } else {
+      final values = power.map((sample) => sample.value);
+      avgPower = (values.sum / power.length).round();
+      maxPower = values.max.round();
+    }
+    normalizedPower = null;
+  }
+
+  static List<Sample>? _cutSamples(
+    List<Sample>? samples,
+    Duration start,
+    Duration end,
+  ) {
+    if (samples == null || samples.isEmpty) {
+      return samples;
+    }
+    final newSamples = samples
+        .where((sample) => sample.time >= start && sample.time <= end)
+        .map((sample) => sample.clone()..time -= start)
+        .toList();
+    return newSamples.isEmpty ? null : newSamples;
+  }
+
   void cut(Duration start, Duration end) {
     assert(start < end);
     time = end - start;
@@ -238,6 +323,9 @@ class CardioSession extends AtomicEntity {
       }
       setDistance();
       setAscentDescent();
+      // the server discards the elevation correction of the old track
+      uncorrectedAscent = null;
+      uncorrectedDescent = null;
     }
     if (cadence != null && cadence!.isNotEmpty) {
       final newCadence = cadence!.where((time) => time >= start && time <= end);
@@ -260,6 +348,12 @@ class CardioSession extends AtomicEntity {
       }
       setAvgHeartRate();
     }
+    if (powerSamples != null && powerSamples!.isNotEmpty) {
+      powerSamples = _cutSamples(powerSamples, start, end);
+      setPower();
+    }
+    speedSamples = _cutSamples(speedSamples, start, end);
+    temperatureSamples = _cutSamples(temperatureSamples, start, end);
   }
 
   // ignore: long-method
@@ -307,6 +401,12 @@ class CardioSession extends AtomicEntity {
         (session2.cadence?.map((time) => time + shiftTime).toList() ?? []);
     final heartRate = (session1.heartRate ?? []) +
         (session2.heartRate?.map((time) => time + shiftTime).toList() ?? []);
+    List<Sample> combineSamples(
+      List<Sample>? samples1,
+      List<Sample>? samples2,
+    ) =>
+        (samples1 ?? []) +
+        (samples2?.map((sample) => sample..time += shiftTime).toList() ?? []);
     return CardioSession(
       id: randomId(),
       movementId: session1.movementId,
@@ -315,6 +415,8 @@ class CardioSession extends AtomicEntity {
       distance: null, // set later
       ascent: null, // set later
       descent: null, // set later
+      uncorrectedAscent: null,
+      uncorrectedDescent: null,
       time: time,
       calories: null,
       track: track,
@@ -322,14 +424,29 @@ class CardioSession extends AtomicEntity {
       cadence: cadence,
       avgHeartRate: null, // set later
       heartRate: heartRate,
+      avgPower: null, // set later
+      maxPower: null, // set later
+      normalizedPower: null, // set later
+      powerSamples:
+          combineSamples(session1.powerSamples, session2.powerSamples),
+      speedSamples:
+          combineSamples(session1.speedSamples, session2.speedSamples),
+      temperatureSamples: combineSamples(
+        session1.temperatureSamples,
+        session2.temperatureSamples,
+      ),
       routeId: session1.routeId,
+      gearId: session1.gearId,
+      plannedWorkoutId: session1.plannedWorkoutId,
       comments: session1.comments,
       deleted: false,
     )
       ..setDistance()
       ..setAscentDescent()
       ..setAvgCadence()
-      ..setAvgHeartRate();
+      ..setAvgHeartRate()
+      ..setPower()
+      ..setEmptyListsToNull();
   }
 
   bool similarTo(CardioSession other) =>
@@ -350,6 +467,8 @@ class CardioSession extends AtomicEntity {
         distance: distance,
         ascent: ascent,
         descent: descent,
+        uncorrectedAscent: uncorrectedAscent,
+        uncorrectedDescent: uncorrectedDescent,
         time: time?.clone(),
         calories: calories,
         track: track?.clone(),
@@ -357,7 +476,15 @@ class CardioSession extends AtomicEntity {
         cadence: cadence?.clone(),
         avgHeartRate: avgHeartRate,
         heartRate: heartRate?.clone(),
+        avgPower: avgPower,
+        maxPower: maxPower,
+        normalizedPower: normalizedPower,
+        powerSamples: powerSamples?.clone(),
+        speedSamples: speedSamples?.clone(),
+        temperatureSamples: temperatureSamples?.clone(),
         routeId: routeId?.clone(),
+        gearId: gearId?.clone(),
+        plannedWorkoutId: plannedWorkoutId?.clone(),
         comments: comments,
         deleted: deleted,
       );
@@ -392,6 +519,26 @@ class CardioSession extends AtomicEntity {
         validate(
           avgHeartRate == null || avgHeartRate! >= 0,
           'CardioSession: avgHeartRate < 0',
+        ) &&
+        validate(
+          uncorrectedAscent == null || uncorrectedAscent! >= 0,
+          'CardioSession: uncorrectedAscent < 0',
+        ) &&
+        validate(
+          uncorrectedDescent == null || uncorrectedDescent! >= 0,
+          'CardioSession: uncorrectedDescent < 0',
+        ) &&
+        validate(
+          avgPower == null || avgPower! >= 0,
+          'CardioSession: avgPower < 0',
+        ) &&
+        validate(
+          maxPower == null || maxPower! >= 0,
+          'CardioSession: maxPower < 0',
+        ) &&
+        validate(
+          normalizedPower == null || normalizedPower! >= 0,
+          'CardioSession: normalizedPower < 0',
         );
   }
 
@@ -484,6 +631,8 @@ class DbCardioSessionSerializer extends DbSerializer<CardioSession> {
       distance: r[prefix + Columns.distance] as int?,
       ascent: r[prefix + Columns.ascent] as int?,
       descent: r[prefix + Columns.descent] as int?,
+      uncorrectedAscent: r[prefix + Columns.uncorrectedAscent] as int?,
+      uncorrectedDescent: r[prefix + Columns.uncorrectedDescent] as int?,
       time: r[prefix + Columns.time] == null
           ? null
           : Duration(milliseconds: r[prefix + Columns.time]! as int),
@@ -499,9 +648,27 @@ class DbCardioSessionSerializer extends DbSerializer<CardioSession> {
       heartRate: DbDurationListConverter.mapToDart(
         r[prefix + Columns.heartRate] as Uint8List?,
       ),
+      avgPower: r[prefix + Columns.avgPower] as int?,
+      maxPower: r[prefix + Columns.maxPower] as int?,
+      normalizedPower: r[prefix + Columns.normalizedPower] as int?,
+      powerSamples: DbSampleListConverter.mapToDart(
+        r[prefix + Columns.power] as Uint8List?,
+      ),
+      speedSamples: DbSampleListConverter.mapToDart(
+        r[prefix + Columns.speed] as Uint8List?,
+      ),
+      temperatureSamples: DbSampleListConverter.mapToDart(
+        r[prefix + Columns.temperature] as Uint8List?,
+      ),
       routeId: r[prefix + Columns.routeId] == null
           ? null
           : Int64(r[prefix + Columns.routeId]! as int),
+      gearId: r[prefix + Columns.gearId] == null
+          ? null
+          : Int64(r[prefix + Columns.gearId]! as int),
+      plannedWorkoutId: r[prefix + Columns.plannedWorkoutId] == null
+          ? null
+          : Int64(r[prefix + Columns.plannedWorkoutId]! as int),
       comments: r[prefix + Columns.comments] as String?,
       deleted: r[prefix + Columns.deleted]! as int == 1,
     );
@@ -517,6 +684,8 @@ class DbCardioSessionSerializer extends DbSerializer<CardioSession> {
       Columns.distance: o.distance,
       Columns.ascent: o.ascent,
       Columns.descent: o.descent,
+      Columns.uncorrectedAscent: o.uncorrectedAscent,
+      Columns.uncorrectedDescent: o.uncorrectedDescent,
       Columns.time: o.time?.inMilliseconds,
       Columns.calories: o.calories,
       Columns.track: DbPositionListConverter.mapToSql(o.track),
@@ -524,7 +693,16 @@ class DbCardioSessionSerializer extends DbSerializer<CardioSession> {
       Columns.cadence: DbDurationListConverter.mapToSql(o.cadence),
       Columns.avgHeartRate: o.avgHeartRate,
       Columns.heartRate: DbDurationListConverter.mapToSql(o.heartRate),
+      Columns.avgPower: o.avgPower,
+      Columns.maxPower: o.maxPower,
+      Columns.normalizedPower: o.normalizedPower,
+      Columns.power: DbSampleListConverter.mapToSql(o.powerSamples),
+      Columns.speed: DbSampleListConverter.mapToSql(o.speedSamples),
+      Columns.temperature:
+          DbSampleListConverter.mapToSql(o.temperatureSamples),
       Columns.routeId: o.routeId?.toInt(),
+      Columns.gearId: o.gearId?.toInt(),
+      Columns.plannedWorkoutId: o.plannedWorkoutId?.toInt(),
       Columns.comments: o.comments,
       Columns.deleted: o.deleted ? 1 : 0,
     };
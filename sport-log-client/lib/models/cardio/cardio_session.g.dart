@@ -15,6 +15,8 @@ CardioSession _$CardioSessionFromJson(Map<String, dynamic> json) =>
       distance: (json['distance'] as num?)?.toInt(),
       ascent: (json['ascent'] as num?)?.toInt(),
       descent: (json['descent'] as num?)?.toInt(),
+      uncorrectedAscent: (json['uncorrected_ascent'] as num?)?.toInt(),
+      uncorrectedDescent: (json['uncorrected_descent'] as num?)?.toInt(),
       time: const OptionalDurationConverter()
           .fromJson((json['time'] as num?)?.toInt()),
       calories: (json['calories'] as num?)?.toInt(),
@@ -27,8 +29,23 @@ CardioSession _$CardioSessionFromJson(Map<String, dynamic> json) =>
       avgHeartRate: (json['avg_heart_rate'] as num?)?.toInt(),
       heartRate: const OptionalDurationListConverter()
           .fromJson(json['heart_rate'] as List?),
+      avgPower: (json['avg_power'] as num?)?.toInt(),
+      maxPower: (json['max_power'] as num?)?.toInt(),
+      normalizedPower: (json['normalized_power'] as num?)?.toInt(),
+      powerSamples: (json['power'] as List<dynamic>?)
+          ?.map((e) => Sample.fromJson(e as Map<String, dynamic>))
+          .toList(),
+      speedSamples: (json['speed'] as List<dynamic>?)
+          ?.map((e) => Sample.fromJson(e as Map<String, dynamic>))
+          .toList(),
+      temperatureSamples: (json['temperature'] as List<dynamic>?)
+          ?.map((e) => Sample.fromJson(e as Map<String, dynamic>))
+          .toList(),
       routeId:
           const OptionalIdConverter().fromJson(json['route_id'] as String?),
+      gearId: const OptionalIdConverter().fromJson(json['gear_id'] as String?),
+      plannedWorkoutId: const OptionalIdConverter()
+          .fromJson(json['planned_workout_id'] as String?),
       comments: json['comments'] as String?,
       deleted: json['deleted'] as bool,
     );
@@ -43,6 +60,8 @@ Map<String, dynamic> _$CardioSessionToJson(CardioSession instance) =>
       'distance': instance.distance,
       'ascent': instance.ascent,
       'descent': instance.descent,
+      'uncorrected_ascent': instance.uncorrectedAscent,
+      'uncorrected_descent': instance.uncorrectedDescent,
       'time': const OptionalDurationConverter().toJson(instance.time),
       'calories': instance.calories,
       'track': instance.track,
@@ -51,7 +70,16 @@ Map<String, dynamic> _$CardioSessionToJson(CardioSession instance) =>
       'avg_heart_rate': instance.avgHeartRate,
       'heart_rate':
           const OptionalDurationListConverter().toJson(instance.heartRate),
+      'avg_power': instance.avgPower,
+      'max_power': instance.maxPower,
+      'normalized_power': instance.normalizedPower,
+      'power': instance.powerSamples,
+      'speed': instance.speedSamples,
+      'temperature': instance.temperatureSamples,
       'route_id': const OptionalIdConverter().toJson(instance.routeId),
+      'gear_id': const OptionalIdConverter().toJson(instance.gearId),
+      'planned_workout_id':
+          const OptionalIdConverter().toJson(instance.plannedWorkoutId),
       'comments': instance.comments,
       'deleted': instance.deleted,
     };
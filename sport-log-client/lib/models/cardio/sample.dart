@@ -0,0 +1,59 @@
+import 'dart:typed_data';
+
+import 'package:json_annotation/json_annotation.dart';
+import 'package:sport_log/helpers/serialization/json_serialization.dart';
+import 'package:sport_log/models/clone_extensions.dart';
+
+part 'sample.g.dart';
+
+/// A sample of a measured value like power, speed or temperature.
+@JsonSerializable()
+class Sample {
+  Sample({
+    required this.value,
+    required this.time,
+  });
+
+  factory Sample.fromJson(Map<String, dynamic> json) => _$SampleFromJson(json);
+
+  factory Sample.fromBytesList(Uint8List list) {
+    assert(list.length == byteSize);
+    final bytes = list.buffer.asByteData();
+    return Sample(
+      value: bytes.getFloat64(0),
+      time: Duration(milliseconds: bytes.getInt64(8)),
+    );
+  }
+
+  @JsonKey(name: "v")
+  double value;
+  @JsonKey(name: "t")
+  @DurationConverter()
+  Duration time;
+
+  Map<String, dynamic> toJson() => _$SampleToJson(this);
+
+  @override
+  String toString() => toJson().toString();
+
+  Sample clone() => Sample(value: value, time: time.clone());
+
+  static const int byteSize = 16;
+
+  Uint8List asBytesList() {
+    final bytes = ByteData(byteSize)
+      ..setFloat64(0, value)
+      ..setInt64(8, time.inMilliseconds);
+    final list = bytes.buffer.asUint8List();
+    assert(list.length == byteSize);
+    return list;
+  }
+
+  @override
+  bool operator ==(Object other) {
+    return other is Sample && value == other.value && time == other.time;
+  }
+
+  @override
+  int get hashCode => Object.hash(value, time);
+}
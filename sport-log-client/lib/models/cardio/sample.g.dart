@@ -0,0 +1,17 @@
+// GENERATED CODE - DO NOT MODIFY BY HAND
+
+part of 'sample.dart';
+
+// **************************************************************************
+// JsonSerializableGenerator
+// **************************************************************************
+
+Sample _$SampleFromJson(Map<String, dynamic> json) => Sample(
+      value: (json['v'] as num).toDouble(),
+      time: const DurationConverter().fromJson((json['t'] as num).toInt()),
+    );
+
+Map<String, dynamic> _$SampleToJson(Sample instance) => <String, dynamic>{
+      'v': instance.value,
+      't': const DurationConverter().toJson(instance.time),
+    };
@@ -1,5 +1,6 @@
 import 'package:fixnum/fixnum.dart';
 import 'package:sport_log/models/cardio/position.dart';
+import 'package:sport_log/models/cardio/sample.dart';
 import 'package:sport_log/models/entity_interfaces.dart';
 
 extension CloneDateTime on DateTime {
@@ -19,10 +20,18 @@ extension CloneListDuration on List<Duration> {
   List<Duration> clone() => map((d) => d.clone()).toList();
 }
 
+extension CloneListInt on List<int> {
+  List<int> clone() => toList();
+}
+
 extension CloneListPosition on List<Position> {
   List<Position> clone() => map((d) => d.clone()).toList();
 }
 
+extension CloneListSample on List<Sample> {
+  List<Sample> clone() => map((d) => d.clone()).toList();
+}
+
 extension CloneListEntity<E extends Entity> on List<E> {
   List<E> clone() => map((d) => d.clone()).toList().cast();
 }
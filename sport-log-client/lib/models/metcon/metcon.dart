@@ -16,7 +16,15 @@ enum MetconType {
   @JsonValue("Emom")
   emom("Emom"),
   @JsonValue("ForTime")
-  forTime("For Time");
+  forTime("For Time"),
+  @JsonValue("Tabata")
+  tabata("Tabata"),
+  @JsonValue("DeathBy")
+  deathBy("Death By"),
+  @JsonValue("Ladder")
+  ladder("Ladder"),
+  @JsonValue("Chipper")
+  chipper("Chipper");
 
   const MetconType(this.name);
 
@@ -105,9 +113,29 @@ class Metcon extends AtomicEntity {
             validate(timecap != null, 'Metcon: emom: timecap == null'),
       MetconType.forTime =>
         validate(rounds != null, 'Metcon: forTime: rounds == null'),
+      MetconType.tabata =>
+        validate(rounds != null, 'Metcon: tabata: rounds == null') &&
+            validate(timecap != null, 'Metcon: tabata: timecap == null'),
+      MetconType.deathBy =>
+        validate(rounds == null, 'Metcon: deathBy: rounds != null'),
+      MetconType.ladder =>
+        validate(timecap != null, 'Metcon: ladder: timecap == null'),
+      MetconType.chipper =>
+        validate(rounds == null, 'Metcon: chipper: rounds != null'),
     };
   }
 
+  /// Whether a session of this metcon can be finished within the timecap.
+  bool get canBeFinished => switch (metconType) {
+        MetconType.forTime || MetconType.chipper => true,
+        MetconType.ladder => rounds != null,
+        MetconType.amrap ||
+        MetconType.emom ||
+        MetconType.tabata ||
+        MetconType.deathBy =>
+          false,
+      };
+
   @override
   bool isValidBeforeSanitation() {
     return validate(!deleted, 'Metcon: deleted == true') &&
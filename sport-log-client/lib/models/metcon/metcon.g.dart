@@ -33,4 +33,8 @@ const _$MetconTypeEnumMap = {
   MetconType.amrap: 'Amrap',
   MetconType.emom: 'Emom',
   MetconType.forTime: 'ForTime',
+  MetconType.tabata: 'Tabata',
+  MetconType.deathBy: 'DeathBy',
+  MetconType.ladder: 'Ladder',
+  MetconType.chipper: 'Chipper',
 };
@@ -99,6 +99,18 @@ class MetconDescription extends CompoundEntity {
             : " (Timecap ${metcon.timecap?.formatTimeShort})";
         final rounds = metcon.rounds! > 1 ? "${metcon.rounds!} Rounds " : "";
         return "$rounds${metcon.metconType.name}$timecap";
+      case MetconType.tabata:
+        return "${metcon.metconType.name} ${metcon.rounds} Rounds";
+      case MetconType.deathBy:
+      case MetconType.chipper:
+        final timecap = metcon.timecap == null
+            ? ""
+            : " (Timecap ${metcon.timecap?.formatTimeShort})";
+        return "${metcon.metconType.name}$timecap";
+      case MetconType.ladder:
+        final rounds =
+            metcon.rounds == null ? "" : " (${metcon.rounds!} Rounds)";
+        return "${metcon.metconType.name} ${metcon.timecap?.formatTimeShort}$rounds";
     }
   }
 }
@@ -19,33 +19,33 @@ extension MetconRecordsExtension on MetconRecords {
     if (metconRecord == null) {
       return false;
     }
+    final metconSession = metconSessionDescription.metconSession;
+    final roundsAndReps =
+        _roundsAndReps(metconSession.rounds, metconSession.reps);
+    final recordRoundsAndReps =
+        _roundsAndReps(metconRecord.rounds, metconRecord.reps);
     return switch (
         metconSessionDescription.metconDescription.metcon.metconType) {
-      MetconType.amrap => isRecord(
-          metconSessionDescription.metconSession.rounds
-              ?.mulNullable(MetconRecord.multiplier)
-              ?.addNullable(metconSessionDescription.metconSession.reps),
-          metconRecord.rounds
-              ?.mulNullable(MetconRecord.multiplier)
-              ?.addNullable(metconRecord.reps),
-        ),
-      MetconType.forTime => metconRecord.time != null
-          ? isRecord(
-              metconSessionDescription.metconSession.time?.inMilliseconds,
-              metconRecord.time?.inMilliseconds,
-              minRecord: true,
-            )
-          : isRecord(
-              metconSessionDescription.metconSession.rounds
-                  ?.mulNullable(MetconRecord.multiplier)
-                  ?.addNullable(metconSessionDescription.metconSession.reps),
-              metconRecord.rounds
-                  ?.mulNullable(MetconRecord.multiplier)
-                  ?.addNullable(metconRecord.reps),
-            ),
+      MetconType.amrap ||
+      MetconType.tabata ||
+      MetconType.deathBy =>
+        isRecord(roundsAndReps, recordRoundsAndReps),
+      MetconType.forTime ||
+      MetconType.ladder ||
+      MetconType.chipper =>
+        metconRecord.time != null
+            ? isRecord(
+                metconSession.time?.inMilliseconds,
+                metconRecord.time?.inMilliseconds,
+                minRecord: true,
+              )
+            : isRecord(roundsAndReps, recordRoundsAndReps),
       MetconType.emom => false,
     };
   }
+
+  static int _roundsAndReps(int? rounds, int? reps) =>
+      (rounds ?? 0) * MetconRecord.multiplier + (reps ?? 0);
 }
 
 class MetconRecord {
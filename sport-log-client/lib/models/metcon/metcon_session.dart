@@ -1,8 +1,11 @@
+import 'dart:typed_data';
+
 import 'package:fixnum/fixnum.dart';
 import 'package:json_annotation/json_annotation.dart';
 import 'package:sport_log/database/db_interfaces.dart';
 import 'package:sport_log/database/table.dart';
 import 'package:sport_log/helpers/id_generation.dart';
+import 'package:sport_log/helpers/serialization/db_serialization.dart';
 import 'package:sport_log/helpers/serialization/json_serialization.dart';
 import 'package:sport_log/models/clone_extensions.dart';
 import 'package:sport_log/models/entity_interfaces.dart';
@@ -20,8 +23,10 @@ class MetconSession extends AtomicEntity {
     required this.time,
     required this.rounds,
     required this.reps,
+    required this.roundReps,
     required this.rx,
     required this.comments,
+    required this.plannedWorkoutId,
     required this.deleted,
   });
 
@@ -36,7 +41,20 @@ class MetconSession extends AtomicEntity {
       case MetconType.emom:
         break;
       case MetconType.forTime:
+      case MetconType.chipper:
         time = Duration.zero;
+      case MetconType.tabata:
+        reps = 0;
+      case MetconType.deathBy:
+        rounds = 0;
+        reps = 0;
+      case MetconType.ladder:
+        if (metcon.canBeFinished) {
+          time = Duration.zero;
+        } else {
+          rounds = 0;
+          reps = 0;
+        }
     }
     return MetconSession(
       id: randomId(),
@@ -45,8 +63,10 @@ class MetconSession extends AtomicEntity {
       time: time,
       rounds: rounds,
       reps: reps,
+      roundReps: null,
       rx: true,
       comments: null,
+      plannedWorkoutId: null,
       deleted: false,
     );
   }
@@ -68,8 +88,15 @@ class MetconSession extends AtomicEntity {
   Duration? time;
   int? rounds;
   int? reps;
+
+  /// The reps of every round of a [MetconType.tabata].
+  List<int>? roundReps;
   bool rx;
   String? comments;
+
+  /// The planned workout is managed by the server and only kept by the client.
+  @OptionalIdConverter()
+  Int64? plannedWorkoutId;
   @override
   bool deleted;
 
@@ -84,8 +111,10 @@ class MetconSession extends AtomicEntity {
         time: time?.clone(),
         rounds: rounds,
         reps: reps,
+        roundReps: roundReps?.clone(),
         rx: rx,
         comments: comments,
+        plannedWorkoutId: plannedWorkoutId?.clone(),
         deleted: deleted,
       );
 
@@ -97,7 +126,11 @@ class MetconSession extends AtomicEntity {
           'MetconSession: time <= 0',
         ) &&
         validate(rounds == null || rounds! >= 0, 'MetconSession: rounds < 0') &&
-        validate(reps == null || reps! >= 0, 'MetconSession: reps < 0');
+        validate(reps == null || reps! >= 0, 'MetconSession: reps < 0') &&
+        validate(
+          roundReps == null || roundReps!.every((reps) => reps >= 0),
+          'MetconSession: round reps < 0',
+        );
   }
 
   @override
@@ -129,8 +162,14 @@ class DbMetconSessionSerializer extends DbSerializer<MetconSession> {
           : Duration(milliseconds: r[prefix + Columns.time]! as int),
       rounds: r[prefix + Columns.rounds] as int?,
       reps: r[prefix + Columns.reps] as int?,
+      roundReps: DbIntListConverter.mapToDart(
+        r[prefix + Columns.roundReps] as Uint8List?,
+      ),
       rx: r[prefix + Columns.rx]! as int == 1,
       comments: r[prefix + Columns.comments] as String?,
+      plannedWorkoutId: r[prefix + Columns.plannedWorkoutId] == null
+          ? null
+          : Int64(r[prefix + Columns.plannedWorkoutId]! as int),
       deleted: r[prefix + Columns.deleted]! as int == 1,
     );
   }
@@ -144,8 +183,10 @@ class DbMetconSessionSerializer extends DbSerializer<MetconSession> {
       Columns.time: o.time?.inMilliseconds,
       Columns.rounds: o.rounds,
       Columns.reps: o.reps,
+      Columns.roundReps: DbIntListConverter.mapToSql(o.roundReps),
       Columns.rx: o.rx ? 1 : 0,
       Columns.comments: o.comments,
+      Columns.plannedWorkoutId: o.plannedWorkoutId?.toInt(),
       Columns.deleted: o.deleted ? 1 : 0,
     };
   }
@@ -15,8 +15,13 @@ MetconSession _$MetconSessionFromJson(Map<String, dynamic> json) =>
           .fromJson((json['time'] as num?)?.toInt()),
       rounds: (json['rounds'] as num?)?.toInt(),
       reps: (json['reps'] as num?)?.toInt(),
+      roundReps: (json['round_reps'] as List<dynamic>?)
+          ?.map((e) => (e as num).toInt())
+          .toList(),
       rx: json['rx'] as bool,
       comments: json['comments'] as String?,
+      plannedWorkoutId: const OptionalIdConverter()
+          .fromJson(json['planned_workout_id'] as String?),
       deleted: json['deleted'] as bool,
     );
 
@@ -29,7 +34,10 @@ Map<String, dynamic> _$MetconSessionToJson(MetconSession instance) =>
       'time': const OptionalDurationConverter().toJson(instance.time),
       'rounds': instance.rounds,
       'reps': instance.reps,
+      'round_reps': instance.roundReps,
       'rx': instance.rx,
       'comments': instance.comments,
+      'planned_workout_id':
+          const OptionalIdConverter().toJson(instance.plannedWorkoutId),
       'deleted': instance.deleted,
     };
@@ -75,6 +75,52 @@ class MetconSessionDescription extends CompoundEntity {
                   metconSession.rounds == null && metconSession.reps == null,
               'MetconSessionDescription: for "for time" either time or rounds and reps must be null',
             ),
+          MetconType.tabata => validate(
+                metconSession.time == null,
+                'MetconSessionDescription: time != null although tabata',
+              ) &&
+              validate(
+                metconSession.rounds == null,
+                'MetconSessionDescription: rounds != null although tabata',
+              ) &&
+              validate(
+                metconSession.reps != null,
+                'MetconSessionDescription: reps == null although tabata',
+              ),
+          MetconType.deathBy => validate(
+                metconSession.time == null,
+                'MetconSessionDescription: time != null although death by',
+              ) &&
+              validate(
+                metconSession.rounds != null,
+                'MetconSessionDescription: rounds == null although death by',
+              ),
+          MetconType.ladder => metconSession.time != null
+              ? validate(
+                    metconDescription.metcon.rounds != null,
+                    'MetconSessionDescription: time != null although ladder without rounds',
+                  ) &&
+                  validate(
+                    metconSession.rounds == null && metconSession.reps == null,
+                    'MetconSessionDescription: for ladder either time or rounds and reps must be null',
+                  )
+              : validate(
+                  metconSession.rounds != null && metconSession.reps != null,
+                  'MetconSessionDescription: for ladder either time or rounds and reps must be set',
+                ),
+          MetconType.chipper => metconSession.time != null
+              ? validate(
+                  metconSession.rounds == null && metconSession.reps == null,
+                  'MetconSessionDescription: for chipper either time or reps must be null',
+                )
+              : validate(
+                    metconSession.rounds == null,
+                    'MetconSessionDescription: rounds != null although chipper',
+                  ) &&
+                  validate(
+                    metconSession.reps != null,
+                    'MetconSessionDescription: for chipper either time or reps must be set',
+                  ),
         };
   }
 
@@ -110,6 +156,20 @@ class MetconSessionDescription extends CompoundEntity {
               ? "${metconSession.time?.formatTimeShort} min $timecap"
               : "${metconSession.rounds} rounds + ${metconSession.reps} reps (${metconDescription.metcon.rounds} rounds)";
         }
+      case MetconType.tabata:
+        return "${metconSession.reps} reps";
+      case MetconType.deathBy:
+        return metconSession.reps == null
+            ? "${metconSession.rounds} rounds"
+            : "${metconSession.rounds} rounds + ${metconSession.reps} reps";
+      case MetconType.ladder:
+        return metconSession.time != null
+            ? "${metconSession.time?.formatTimeShort} min"
+            : "${metconSession.rounds} rounds + ${metconSession.reps} reps";
+      case MetconType.chipper:
+        return metconSession.time != null
+            ? "${metconSession.time?.formatTimeShort} min"
+            : "${metconSession.reps} reps";
     }
   }
 
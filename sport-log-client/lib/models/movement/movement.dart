@@ -1,4 +1,5 @@
 import 'package:fixnum/fixnum.dart';
+import 'package:flutter/foundation.dart';
 import 'package:json_annotation/json_annotation.dart';
 import 'package:sport_log/database/db_interfaces.dart';
 import 'package:sport_log/database/table.dart';
@@ -25,6 +26,29 @@ enum MovementDimension {
   final String name;
 }
 
+enum MuscleGroup {
+  @JsonValue('Chest')
+  chest("Chest"),
+  @JsonValue('Back')
+  back("Back"),
+  @JsonValue('Shoulders')
+  shoulders("Shoulders"),
+  @JsonValue('Arms')
+  arms("Arms"),
+  @JsonValue('Core')
+  core("Core"),
+  @JsonValue('Quadriceps')
+  quadriceps("Quadriceps"),
+  @JsonValue('Hamstrings')
+  hamstrings("Hamstrings"),
+  @JsonValue('Glutes')
+  glutes("Glutes");
+
+  const MuscleGroup(this.name);
+
+  final String name;
+}
+
 @JsonSerializable(constructor: "_")
 class Movement extends AtomicEntity {
   Movement({
@@ -35,6 +59,7 @@ class Movement extends AtomicEntity {
     required this.cardio,
     required this.deleted,
     required this.dimension,
+    required this.muscleGroups,
   });
 
   Movement._({
@@ -57,7 +82,8 @@ class Movement extends AtomicEntity {
         description = null,
         cardio = true,
         deleted = false,
-        dimension = MovementDimension.reps;
+        dimension = MovementDimension.reps,
+        muscleGroups = [];
 
   @override
   @IdConverter()
@@ -75,6 +101,8 @@ class Movement extends AtomicEntity {
   bool deleted;
   @JsonKey(name: 'movement_dimension')
   MovementDimension dimension;
+  @JsonKey(name: 'muscle_groups')
+  List<MuscleGroup> muscleGroups;
 
   static Movement?
       _defaultMovement; // default movement that already exists in db
@@ -93,6 +121,7 @@ class Movement extends AtomicEntity {
         cardio: cardio,
         deleted: deleted,
         dimension: dimension,
+        muscleGroups: [...muscleGroups],
       );
 
   @override
@@ -129,7 +158,8 @@ class Movement extends AtomicEntity {
       other.description == description &&
       other.cardio == cardio &&
       other.deleted == deleted &&
-      other.dimension == dimension;
+      other.dimension == dimension &&
+      listEquals(other.muscleGroups, muscleGroups);
 
   @override
   int get hashCode => Object.hash(
@@ -140,6 +170,7 @@ class Movement extends AtomicEntity {
         cardio,
         deleted,
         dimension,
+        Object.hashAll(muscleGroups),
       );
 }
 
@@ -155,6 +186,14 @@ class DbMovementSerializer extends DbSerializer<Movement> {
       deleted: r[prefix + Columns.deleted]! as int == 1,
       dimension:
           MovementDimension.values[r[prefix + Columns.dimension]! as int],
+      muscleGroups: MuscleGroup.values
+          .where(
+            (muscleGroup) =>
+                (r[prefix + Columns.muscleGroups]! as int) &
+                    (1 << muscleGroup.index) !=
+                0,
+          )
+          .toList(),
     );
   }
 
@@ -168,6 +207,10 @@ class DbMovementSerializer extends DbSerializer<Movement> {
       Columns.cardio: o.cardio ? 1 : 0,
       Columns.deleted: o.deleted ? 1 : 0,
       Columns.dimension: o.dimension.index,
+      Columns.muscleGroups: o.muscleGroups.fold<int>(
+        0,
+        (muscleGroups, muscleGroup) => muscleGroups | (1 << muscleGroup.index),
+      ),
     };
   }
 }
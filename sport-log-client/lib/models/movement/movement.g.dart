@@ -15,6 +15,9 @@ Movement _$MovementFromJson(Map<String, dynamic> json) => Movement._(
       deleted: json['deleted'] as bool,
       dimension:
           $enumDecode(_$MovementDimensionEnumMap, json['movement_dimension']),
+      muscleGroups: (json['muscle_groups'] as List<dynamic>)
+          .map((e) => $enumDecode(_$MuscleGroupEnumMap, e))
+          .toList(),
     );
 
 Map<String, dynamic> _$MovementToJson(Movement instance) => <String, dynamic>{
@@ -25,6 +28,8 @@ Map<String, dynamic> _$MovementToJson(Movement instance) => <String, dynamic>{
       'cardio': instance.cardio,
       'deleted': instance.deleted,
       'movement_dimension': _$MovementDimensionEnumMap[instance.dimension]!,
+      'muscle_groups':
+          instance.muscleGroups.map((e) => _$MuscleGroupEnumMap[e]!).toList(),
     };
 
 const _$MovementDimensionEnumMap = {
@@ -33,3 +38,14 @@ const _$MovementDimensionEnumMap = {
   MovementDimension.distance: 'Distance',
   MovementDimension.energy: 'Energy',
 };
+
+const _$MuscleGroupEnumMap = {
+  MuscleGroup.chest: 'Chest',
+  MuscleGroup.back: 'Back',
+  MuscleGroup.shoulders: 'Shoulders',
+  MuscleGroup.arms: 'Arms',
+  MuscleGroup.core: 'Core',
+  MuscleGroup.quadriceps: 'Quadriceps',
+  MuscleGroup.hamstrings: 'Hamstrings',
+  MuscleGroup.glutes: 'Glutes',
+};
@@ -18,6 +18,9 @@ class StrengthSession extends AtomicEntity {
     required this.movementId,
     required this.interval,
     required this.comments,
+    required this.strengthBlockId,
+    required this.blockPosition,
+    required this.plannedWorkoutId,
     required this.deleted,
   });
 
@@ -37,6 +40,15 @@ class StrengthSession extends AtomicEntity {
   @OptionalDurationConverter()
   Duration? interval;
   String? comments;
+
+  /// The strength block is managed by the server and only kept by the client.
+  @OptionalIdConverter()
+  Int64? strengthBlockId;
+  int? blockPosition;
+
+  /// The planned workout is managed by the server and only kept by the client.
+  @OptionalIdConverter()
+  Int64? plannedWorkoutId;
   @override
   bool deleted;
 
@@ -51,6 +63,14 @@ class StrengthSession extends AtomicEntity {
         validate(
           interval == null || interval! >= Duration.zero,
           'StrengthSession: interval < 0',
+        ) &&
+        validate(
+          (strengthBlockId == null) == (blockPosition == null),
+          'StrengthSession: only one of strengthBlockId and blockPosition is set',
+        ) &&
+        validate(
+          blockPosition == null || blockPosition! >= 0,
+          'StrengthSession: blockPosition < 0',
         );
   }
 
@@ -84,6 +104,9 @@ class StrengthSession extends AtomicEntity {
         movementId: movementId.clone(),
         interval: interval?.clone(),
         comments: comments,
+        strengthBlockId: strengthBlockId?.clone(),
+        blockPosition: blockPosition,
+        plannedWorkoutId: plannedWorkoutId?.clone(),
         deleted: deleted,
       );
 }
@@ -99,6 +122,13 @@ class DbStrengthSessionSerializer extends DbSerializer<StrengthSession> {
           ? null
           : Duration(milliseconds: r[prefix + Columns.interval]! as int),
       comments: r[prefix + Columns.comments] as String?,
+      strengthBlockId: r[prefix + Columns.strengthBlockId] == null
+          ? null
+          : Int64(r[prefix + Columns.strengthBlockId]! as int),
+      blockPosition: r[prefix + Columns.blockPosition] as int?,
+      plannedWorkoutId: r[prefix + Columns.plannedWorkoutId] == null
+          ? null
+          : Int64(r[prefix + Columns.plannedWorkoutId]! as int),
       deleted: r[prefix + Columns.deleted]! as int == 1,
     );
   }
@@ -111,6 +141,9 @@ class DbStrengthSessionSerializer extends DbSerializer<StrengthSession> {
       Columns.movementId: o.movementId.toInt(),
       Columns.interval: o.interval?.inMilliseconds,
       Columns.comments: o.comments,
+      Columns.strengthBlockId: o.strengthBlockId?.toInt(),
+      Columns.blockPosition: o.blockPosition,
+      Columns.plannedWorkoutId: o.plannedWorkoutId?.toInt(),
       Columns.deleted: o.deleted ? 1 : 0,
     };
   }
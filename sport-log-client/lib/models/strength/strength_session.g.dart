@@ -14,6 +14,11 @@ StrengthSession _$StrengthSessionFromJson(Map<String, dynamic> json) =>
       interval: const OptionalDurationConverter()
           .fromJson((json['interval'] as num?)?.toInt()),
       comments: json['comments'] as String?,
+      strengthBlockId: const OptionalIdConverter()
+          .fromJson(json['strength_block_id'] as String?),
+      blockPosition: (json['block_position'] as num?)?.toInt(),
+      plannedWorkoutId: const OptionalIdConverter()
+          .fromJson(json['planned_workout_id'] as String?),
       deleted: json['deleted'] as bool,
     );
 
@@ -25,5 +30,10 @@ Map<String, dynamic> _$StrengthSessionToJson(StrengthSession instance) =>
       'movement_id': const IdConverter().toJson(instance.movementId),
       'interval': const OptionalDurationConverter().toJson(instance.interval),
       'comments': instance.comments,
+      'strength_block_id':
+          const OptionalIdConverter().toJson(instance.strengthBlockId),
+      'block_position': instance.blockPosition,
+      'planned_workout_id':
+          const OptionalIdConverter().toJson(instance.plannedWorkoutId),
       'deleted': instance.deleted,
     };
@@ -43,6 +43,9 @@ class StrengthSessionDescription extends CompoundEntity {
                 movementId: Movement.defaultMovement!.id,
                 interval: null,
                 comments: null,
+                strengthBlockId: null,
+                blockPosition: null,
+                plannedWorkoutId: null,
                 deleted: false,
               ),
               movement: Movement.defaultMovement!,
@@ -21,6 +21,11 @@ class StrengthSet extends AtomicEntity {
     required this.setNumber,
     required this.count,
     required this.weight,
+    required this.rpe,
+    required this.rir,
+    required this.tempo,
+    required this.rest,
+    required this.isWarmup,
     required this.deleted,
   });
 
@@ -38,6 +43,21 @@ class StrengthSet extends AtomicEntity {
   int setNumber;
   int count;
   double? weight;
+
+  /// rate of perceived exertion between 1 and 10 in steps of 0.5
+  double? rpe;
+
+  /// reps in reserve
+  int? rir;
+
+  /// eccentric, bottom, concentric and top phase in seconds or X, e.g. 31X0
+  String? tempo;
+
+  /// rest after the set
+  @OptionalDurationConverter()
+  Duration? rest;
+  @JsonKey(defaultValue: false)
+  bool isWarmup;
   @override
   bool deleted;
 
@@ -51,6 +71,11 @@ class StrengthSet extends AtomicEntity {
         setNumber: setNumber,
         count: count,
         weight: weight,
+        rpe: rpe,
+        rir: rir,
+        tempo: tempo,
+        rest: rest?.clone(),
+        isWarmup: isWarmup,
         deleted: deleted,
       );
 
@@ -59,7 +84,20 @@ class StrengthSet extends AtomicEntity {
     return validate(!deleted, 'StrengthSet: deleted == true') &&
         validate(setNumber >= 0, 'StrengthSet: setNumber < 0') &&
         validate(count >= 1, 'StrengthSet: count < 1') &&
-        validate(weight == null || weight! >= 0, 'StrengthSet: weight < 0');
+        validate(weight == null || weight! >= 0, 'StrengthSet: weight < 0') &&
+        validate(
+          rpe == null || rpe! >= 1 && rpe! <= 10 && (rpe! * 2) % 1 == 0,
+          'StrengthSet: rpe not between 1 and 10 in steps of 0.5',
+        ) &&
+        validate(rir == null || rir! >= 0, 'StrengthSet: rir < 0') &&
+        validate(
+          tempo == null || RegExp(r'^[0-9X]{4}$').hasMatch(tempo!),
+          'StrengthSet: tempo does not consist of four digits or X',
+        ) &&
+        validate(
+          rest == null || rest! >= Duration.zero,
+          'StrengthSet: rest < 0',
+        );
   }
 
   @override
@@ -116,6 +154,13 @@ class DbStrengthSetSerializer extends DbSerializer<StrengthSet> {
       setNumber: r[prefix + Columns.setNumber]! as int,
       count: r[prefix + Columns.count]! as int,
       weight: r[prefix + Columns.weight] as double?,
+      rpe: r[prefix + Columns.rpe] as double?,
+      rir: r[prefix + Columns.rir] as int?,
+      tempo: r[prefix + Columns.tempo] as String?,
+      rest: r[prefix + Columns.rest] == null
+          ? null
+          : Duration(milliseconds: r[prefix + Columns.rest]! as int),
+      isWarmup: r[prefix + Columns.isWarmup]! as int == 1,
       deleted: r[prefix + Columns.deleted]! as int == 1,
     );
   }
@@ -128,6 +173,11 @@ class DbStrengthSetSerializer extends DbSerializer<StrengthSet> {
       Columns.setNumber: o.setNumber,
       Columns.count: o.count,
       Columns.weight: o.weight,
+      Columns.rpe: o.rpe,
+      Columns.rir: o.rir,
+      Columns.tempo: o.tempo,
+      Columns.rest: o.rest?.inMilliseconds,
+      Columns.isWarmup: o.isWarmup ? 1 : 0,
       Columns.deleted: o.deleted ? 1 : 0,
     };
   }
@@ -13,6 +13,12 @@ StrengthSet _$StrengthSetFromJson(Map<String, dynamic> json) => StrengthSet(
       setNumber: (json['set_number'] as num).toInt(),
       count: (json['count'] as num).toInt(),
       weight: (json['weight'] as num?)?.toDouble(),
+      rpe: (json['rpe'] as num?)?.toDouble(),
+      rir: (json['rir'] as num?)?.toInt(),
+      tempo: json['tempo'] as String?,
+      rest: const OptionalDurationConverter()
+          .fromJson((json['rest'] as num?)?.toInt()),
+      isWarmup: json['is_warmup'] as bool? ?? false,
       deleted: json['deleted'] as bool,
     );
 
@@ -25,5 +31,10 @@ Map<String, dynamic> _$StrengthSetToJson(StrengthSet instance) =>
       'set_number': instance.setNumber,
       'count': instance.count,
       'weight': instance.weight,
+      'rpe': instance.rpe,
+      'rir': instance.rir,
+      'tempo': instance.tempo,
+      'rest': const OptionalDurationConverter().toJson(instance.rest),
+      'is_warmup': instance.isWarmup,
       'deleted': instance.deleted,
     };
@@ -4,9 +4,37 @@ import 'package:sport_log/database/db_interfaces.dart';
 import 'package:sport_log/helpers/serialization/json_serialization.dart';
 import 'package:sport_log/models/clone_extensions.dart';
 import 'package:sport_log/models/entity_interfaces.dart';
+import 'package:sport_log/models/metcon/metcon_movement.dart';
 
 part 'user.g.dart';
 
+enum EormFormula {
+  @JsonValue('Table')
+  table,
+  @JsonValue('Epley')
+  epley,
+  @JsonValue('Brzycki')
+  brzycki,
+  @JsonValue('Lombardi')
+  lombardi,
+  @JsonValue('Custom')
+  custom,
+}
+
+enum Sex {
+  @JsonValue('Male')
+  male,
+  @JsonValue('Female')
+  female,
+}
+
+enum WeightUnit {
+  @JsonValue('Kg')
+  kg,
+  @JsonValue('Lb')
+  lb,
+}
+
 @JsonSerializable()
 class User extends NonDeletableAtomicEntity {
   User({
@@ -14,6 +42,13 @@ class User extends NonDeletableAtomicEntity {
     required this.username,
     required this.password,
     required this.email,
+    required this.eormFormula,
+    required this.sex,
+    required this.birthdate,
+    required this.weightUnit,
+    required this.distanceUnit,
+    required this.timezone,
+    required this.maxHeartRate,
   });
 
   factory User.fromJson(Map<String, dynamic> json) => _$UserFromJson(json);
@@ -25,6 +60,17 @@ class User extends NonDeletableAtomicEntity {
   String password;
   String email;
 
+  /// The profile is managed by the server and only kept by the client, so that
+  /// updating the user does not reset it.
+  EormFormula eormFormula;
+  Sex? sex;
+  @OptionalDateConverter()
+  DateTime? birthdate;
+  WeightUnit weightUnit;
+  DistanceUnit distanceUnit;
+  String timezone;
+  int? maxHeartRate;
+
   @override
   Map<String, dynamic> toJson() => _$UserToJson(this);
 
@@ -34,6 +80,13 @@ class User extends NonDeletableAtomicEntity {
         username: username,
         password: password,
         email: email,
+        eormFormula: eormFormula,
+        sex: sex,
+        birthdate: birthdate?.clone(),
+        weightUnit: weightUnit,
+        distanceUnit: distanceUnit,
+        timezone: timezone,
+        maxHeartRate: maxHeartRate,
       );
 
   @override
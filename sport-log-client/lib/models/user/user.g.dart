@@ -11,6 +11,14 @@ User _$UserFromJson(Map<String, dynamic> json) => User(
       username: json['username'] as String,
       password: json['password'] as String,
       email: json['email'] as String,
+      eormFormula: $enumDecode(_$EormFormulaEnumMap, json['eorm_formula']),
+      sex: $enumDecodeNullable(_$SexEnumMap, json['sex']),
+      birthdate:
+          const OptionalDateConverter().fromJson(json['birthdate'] as String?),
+      weightUnit: $enumDecode(_$WeightUnitEnumMap, json['weight_unit']),
+      distanceUnit: $enumDecode(_$DistanceUnitEnumMap, json['distance_unit']),
+      timezone: json['timezone'] as String,
+      maxHeartRate: (json['max_heart_rate'] as num?)?.toInt(),
     );
 
 Map<String, dynamic> _$UserToJson(User instance) => <String, dynamic>{
@@ -18,4 +26,37 @@ Map<String, dynamic> _$UserToJson(User instance) => <String, dynamic>{
       'username': instance.username,
       'password': instance.password,
       'email': instance.email,
+      'eorm_formula': _$EormFormulaEnumMap[instance.eormFormula]!,
+      'sex': _$SexEnumMap[instance.sex],
+      'birthdate': const OptionalDateConverter().toJson(instance.birthdate),
+      'weight_unit': _$WeightUnitEnumMap[instance.weightUnit]!,
+      'distance_unit': _$DistanceUnitEnumMap[instance.distanceUnit]!,
+      'timezone': instance.timezone,
+      'max_heart_rate': instance.maxHeartRate,
     };
+
+const _$EormFormulaEnumMap = {
+  EormFormula.table: 'Table',
+  EormFormula.epley: 'Epley',
+  EormFormula.brzycki: 'Brzycki',
+  EormFormula.lombardi: 'Lombardi',
+  EormFormula.custom: 'Custom',
+};
+
+const _$SexEnumMap = {
+  Sex.male: 'Male',
+  Sex.female: 'Female',
+};
+
+const _$WeightUnitEnumMap = {
+  WeightUnit.kg: 'Kg',
+  WeightUnit.lb: 'Lb',
+};
+
+const _$DistanceUnitEnumMap = {
+  DistanceUnit.m: 'Meter',
+  DistanceUnit.km: 'Km',
+  DistanceUnit.yd: 'Yard',
+  DistanceUnit.ft: 'Foot',
+  DistanceUnit.mi: 'Mile',
+};
@@ -6,6 +6,7 @@ import 'package:sport_log/helpers/bool_toggle.dart';
 import 'package:sport_log/helpers/extensions/navigator_extension.dart';
 import 'package:sport_log/helpers/id_generation.dart';
 import 'package:sport_log/helpers/validation.dart';
+import 'package:sport_log/models/metcon/metcon_movement.dart';
 import 'package:sport_log/models/user/user.dart';
 import 'package:sport_log/routes.dart';
 import 'package:sport_log/settings.dart';
@@ -43,6 +44,13 @@ class _LoginPageState extends State<LoginPage> {
     email: "",
     username: "",
     password: "",
+    eormFormula: EormFormula.table,
+    sex: null,
+    birthdate: null,
+    weightUnit: WeightUnit.kg,
+    distanceUnit: DistanceUnit.km,
+    timezone: "UTC",
+    maxHeartRate: null,
   );
 
   bool _loginPending = false;
@@ -225,6 +225,16 @@ class _MetconEditPageState extends State<MetconEditPage> {
         case MetconType.forTime:
           _metconDescription.metcon.rounds ??= Metcon.roundsDefaultValue;
         // timecap can be either null or non null
+        case MetconType.tabata:
+          _metconDescription.metcon.rounds ??= Metcon.roundsDefaultValue;
+          _metconDescription.metcon.timecap ??= Metcon.timecapDefaultValue;
+        case MetconType.deathBy:
+        case MetconType.chipper:
+          _metconDescription.metcon.rounds = null;
+        // timecap can be either null or non null
+        case MetconType.ladder:
+          _metconDescription.metcon.timecap ??= Metcon.timecapDefaultValue;
+        // rounds can be either null or non null
       }
     });
   }
@@ -244,6 +254,16 @@ class _MetconEditPageState extends State<MetconEditPage> {
             _timecapInput(caption: "Timecap", allowCancel: true),
           ],
         ),
+      MetconType.tabata => _roundsInput(),
+      MetconType.deathBy ||
+      MetconType.chipper =>
+        _timecapInput(caption: "Timecap", allowCancel: true),
+      MetconType.ladder => Column(
+          children: [
+            _optionalRoundsInput(),
+            _timecapInput(caption: "Timecap", allowCancel: false),
+          ],
+        ),
     };
   }
 
@@ -251,15 +271,33 @@ class _MetconEditPageState extends State<MetconEditPage> {
     return EditTile(
       leading: AppIcons.timeInterval,
       caption: "Rounds",
-      child: IntInput(
-        initialValue:
-            _metconDescription.metcon.rounds ?? Metcon.roundsDefaultValue,
-        minValue: 1,
-        maxValue: 999,
-        onUpdate: (rounds) {
-          setState(() => _metconDescription.metcon.rounds = rounds);
-        },
-      ),
+      child: _roundsIntInput(),
+    );
+  }
+
+  Widget _optionalRoundsInput() {
+    return EditTile.optionalButton(
+      leading: AppIcons.timeInterval,
+      caption: "Rounds",
+      showButton: _metconDescription.metcon.rounds == null,
+      onButtonPressed: () => setState(() {
+        _metconDescription.metcon.rounds = Metcon.roundsDefaultValue;
+      }),
+      builder: _roundsIntInput,
+      onTrailingTap: () =>
+          setState(() => _metconDescription.metcon.rounds = null),
+    );
+  }
+
+  Widget _roundsIntInput() {
+    return IntInput(
+      initialValue:
+          _metconDescription.metcon.rounds ?? Metcon.roundsDefaultValue,
+      minValue: 1,
+      maxValue: 999,
+      onUpdate: (rounds) {
+        setState(() => _metconDescription.metcon.rounds = rounds);
+      },
     );
   }
 
@@ -39,6 +39,23 @@ class _MetconSessionEditPageState extends State<MetconSessionEditPage> {
       widget.metconSessionDescription.clone();
   late bool _finished = _metconSessionDescription.metconSession.time != null;
 
+  Metcon get _metcon => _metconSessionDescription.metconDescription.metcon;
+
+  bool get _hasRounds => switch (_metcon.metconType) {
+        MetconType.amrap || MetconType.deathBy => true,
+        MetconType.forTime || MetconType.ladder => !_finished,
+        MetconType.emom || MetconType.tabata || MetconType.chipper => false,
+      };
+
+  bool get _hasReps => switch (_metcon.metconType) {
+        MetconType.amrap || MetconType.tabata || MetconType.deathBy => true,
+        MetconType.forTime ||
+        MetconType.ladder ||
+        MetconType.chipper =>
+          !_finished,
+        MetconType.emom => false,
+      };
+
   Future<void> _saveMetconSession() async {
     final result = widget.isNew
         ? await _dataProvider.createSingle(_metconSessionDescription)
@@ -148,30 +165,14 @@ class _MetconSessionEditPageState extends State<MetconSessionEditPage> {
                           _metconSessionDescription.metconSession.metconId =
                               _metconSessionDescription
                                   .metconDescription.metcon.id;
-                          switch (_metconSessionDescription
-                              .metconDescription.metcon.metconType) {
-                            case MetconType.amrap:
-                              _metconSessionDescription.metconSession.time =
-                                  null;
-                              _metconSessionDescription.metconSession.rounds =
-                                  0;
-                              _metconSessionDescription.metconSession.reps = 0;
-                            case MetconType.emom:
-                              _metconSessionDescription.metconSession.time =
-                                  null;
-                              _metconSessionDescription.metconSession.rounds =
-                                  null;
-                              _metconSessionDescription.metconSession.reps =
-                                  null;
-                            case MetconType.forTime:
-                              _metconSessionDescription.metconSession.time =
-                                  Duration.zero;
-                              _metconSessionDescription.metconSession.rounds =
-                                  null;
-                              _metconSessionDescription.metconSession.reps =
-                                  null;
-                              _finished = true;
-                          }
+                          final metconSession =
+                              MetconSession.defaultValue(_metcon);
+                          _metconSessionDescription.metconSession
+                            ..time = metconSession.time
+                            ..rounds = metconSession.rounds
+                            ..reps = metconSession.reps
+                            ..roundReps = metconSession.roundReps;
+                          _finished = metconSession.time != null;
                         });
                       }
                     }
@@ -197,9 +198,7 @@ class _MetconSessionEditPageState extends State<MetconSessionEditPage> {
                     }
                   },
                 ),
-                if (_metconSessionDescription
-                        .metconDescription.metcon.metconType ==
-                    MetconType.forTime)
+                if (_metcon.canBeFinished)
                   Row(
                     mainAxisAlignment: MainAxisAlignment.center,
                     children: [
@@ -216,13 +215,13 @@ class _MetconSessionEditPageState extends State<MetconSessionEditPage> {
                                   Duration.zero;
                               _metconSessionDescription.metconSession.rounds =
                                   null;
-                              _metconSessionDescription.metconSession.rounds =
+                              _metconSessionDescription.metconSession.reps =
                                   null;
                             } else {
                               _metconSessionDescription.metconSession.time =
                                   null;
                               _metconSessionDescription.metconSession.rounds =
-                                  0;
+                                  _hasRounds ? 0 : null;
                               _metconSessionDescription.metconSession.reps = 0;
                             }
                           });
@@ -230,10 +229,7 @@ class _MetconSessionEditPageState extends State<MetconSessionEditPage> {
                       ),
                     ],
                   ),
-                if (_metconSessionDescription
-                            .metconDescription.metcon.metconType ==
-                        MetconType.forTime &&
-                    _finished)
+                if (_metcon.canBeFinished && _finished)
                   EditTile(
                     caption: 'Time',
                     leading: AppIcons.timeInterval,
@@ -247,60 +243,61 @@ class _MetconSessionEditPageState extends State<MetconSessionEditPage> {
                       minDuration: const Duration(seconds: 1),
                     ),
                   ),
-                if (_metconSessionDescription
-                                .metconDescription.metcon.metconType ==
-                            MetconType.forTime &&
-                        !_finished ||
-                    _metconSessionDescription
-                            .metconDescription.metcon.metconType ==
-                        MetconType.amrap)
+                if (_hasRounds || _hasReps)
                   Row(
                     children: [
-                      Expanded(
-                        child: EditTile(
-                          leading: AppIcons.repeat,
-                          caption: "Rounds",
-                          child: IntInput(
-                            initialValue: _metconSessionDescription
-                                    .metconSession.rounds ??
-                                0,
-                            minValue: 0,
-                            maxValue: _metconSessionDescription
-                                        .metconDescription.metcon.metconType ==
-                                    MetconType.forTime
-                                ? _metconSessionDescription
-                                        .metconDescription.metcon.rounds! -
-                                    1
-                                : 999,
-                            onUpdate: (rounds) => setState(
-                              () => _metconSessionDescription
-                                  .metconSession.rounds = rounds,
+                      if (_hasRounds)
+                        Expanded(
+                          child: EditTile(
+                            leading: AppIcons.repeat,
+                            caption: "Rounds",
+                            child: IntInput(
+                              initialValue: _metconSessionDescription
+                                      .metconSession.rounds ??
+                                  0,
+                              minValue: 0,
+                              maxValue: _metcon.canBeFinished
+                                  ? _metcon.rounds! - 1
+                                  : 999,
+                              onUpdate: (rounds) => setState(
+                                () => _metconSessionDescription
+                                    .metconSession.rounds = rounds,
+                              ),
                             ),
                           ),
                         ),
-                      ),
-                      Defaults.sizedBox.horizontal.normal,
-                      Expanded(
-                        child: EditTile(
-                          leading: null,
-                          caption: "Reps",
-                          child: IntInput(
-                            initialValue:
-                                _metconSessionDescription.metconSession.reps ??
-                                    0,
-                            minValue: 0,
-                            maxValue: _metconSessionDescription
-                                    .metconDescription.moves
-                                    .map((e) => e.metconMovement.count)
-                                    .sum -
-                                1,
-                            onUpdate: (reps) => setState(
-                              () => _metconSessionDescription
-                                  .metconSession.reps = reps,
+                      if (_hasRounds && _hasReps)
+                        Defaults.sizedBox.horizontal.normal,
+                      if (_hasReps)
+                        Expanded(
+                          child: EditTile(
+                            leading: _hasRounds ? null : AppIcons.repeat,
+                            caption: "Reps",
+                            child: IntInput(
+                              initialValue: _metconSessionDescription
+                                      .metconSession.reps ??
+                                  0,
+                              minValue: 0,
+                              maxValue: switch (_metcon.metconType) {
+                                MetconType.amrap ||
+                                MetconType.forTime ||
+                                MetconType.chipper =>
+                                  _metconSessionDescription
+                                          .metconDescription.moves
+                                          .map((e) => e.metconMovement.count)
+                                          .sum -
+                                      1,
+                                _ => 9999,
+                              },
+                              // the round reps no longer add up to the reps
+                              onUpdate: (reps) => setState(
+                                () => _metconSessionDescription.metconSession
+                                  ..reps = reps
+                                  ..roundReps = null,
+                              ),
                             ),
                           ),
                         ),
-                      ),
                     ],
                   ),
                 EditTile.Switch(
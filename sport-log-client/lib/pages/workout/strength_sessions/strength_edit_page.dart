@@ -136,6 +136,11 @@ class _StrengthEditPageState extends State<StrengthEditPage> {
       setNumber: _strengthSessionDescription.sets.length,
       count: count,
       weight: weight,
+      rpe: null,
+      rir: null,
+      tempo: null,
+      rest: null,
+      isWarmup: false,
       deleted: false,
     );
     setState(() {
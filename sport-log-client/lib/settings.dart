@@ -7,6 +7,7 @@ import 'package:sport_log/helpers/lat_lng.dart';
 import 'package:sport_log/helpers/logger.dart';
 import 'package:sport_log/models/epoch/epoch_map.dart';
 import 'package:sport_log/models/epoch/epoch_result.dart';
+import 'package:sport_log/models/metcon/metcon_movement.dart';
 import 'package:sport_log/models/user/user.dart';
 
 enum Units {
@@ -40,6 +41,13 @@ class Settings extends ChangeNotifier {
   static const String _username = "username";
   static const String _password = "password";
   static const String _email = "email";
+  static const String _eormFormula = "eormFormula";
+  static const String _sex = "sex";
+  static const String _birthdate = "birthdate";
+  static const String _weightUnit = "weightUnit";
+  static const String _distanceUnit = "distanceUnit";
+  static const String _timezone = "timezone";
+  static const String _maxHeartRate = "maxHeartRate";
   static const String _lastMapPosition = "lastMapPosition";
   static const String _lastGpsLatLng = "lastGpsLatLng";
   static const String _developer = "developer";
@@ -105,6 +113,28 @@ class Settings extends ChangeNotifier {
     if (!_contains(_email) || override) {
       await _storage!.put(_email, null);
     }
+    // upgrade: the profile of the user had not been stored in past
+    if (!_contains(_eormFormula) || override) {
+      await _storage!.put(_eormFormula, EormFormula.table.name);
+    }
+    if (!_contains(_sex) || override) {
+      await _storage!.put(_sex, null);
+    }
+    if (!_contains(_birthdate) || override) {
+      await _storage!.put(_birthdate, null);
+    }
+    if (!_contains(_weightUnit) || override) {
+      await _storage!.put(_weightUnit, WeightUnit.kg.name);
+    }
+    if (!_contains(_distanceUnit) || override) {
+      await _storage!.put(_distanceUnit, DistanceUnit.km.name);
+    }
+    if (!_contains(_timezone) || override) {
+      await _storage!.put(_timezone, "UTC");
+    }
+    if (!_contains(_maxHeartRate) || override) {
+      await _storage!.put(_maxHeartRate, null);
+    }
     if (!_contains(_lastMapPosition) || override) {
       await _storage!.put(
         _lastMapPosition,
@@ -244,6 +274,13 @@ class Settings extends ChangeNotifier {
       await setUsername(user.username);
       await setPassword(user.password);
       await setEmail(user.email);
+      await _put(_eormFormula, user.eormFormula.name);
+      await _put(_sex, user.sex?.name);
+      await _put(_birthdate, user.birthdate);
+      await _put(_weightUnit, user.weightUnit.name);
+      await _put(_distanceUnit, user.distanceUnit.name);
+      await _put(_timezone, user.timezone);
+      await _put(_maxHeartRate, user.maxHeartRate);
       _logger.d("user updated");
     }
   }
@@ -254,6 +291,16 @@ class Settings extends ChangeNotifier {
           username: username!,
           password: password!,
           email: email!,
+          eormFormula: EormFormula.values.byName(_getString(_eormFormula)),
+          sex: switch (_getStringOptional(_sex)) {
+            final sex? => Sex.values.byName(sex),
+            null => null,
+          },
+          birthdate: _storage!.get(_birthdate) as DateTime?,
+          weightUnit: WeightUnit.values.byName(_getString(_weightUnit)),
+          distanceUnit: DistanceUnit.values.byName(_getString(_distanceUnit)),
+          timezone: _getString(_timezone),
+          maxHeartRate: _storage!.get(_maxHeartRate) as int?,
         )
       : null;
 
@@ -7,6 +7,9 @@
 //! [`ActionEvents`](sport_log_types::ActionEvent) are only created from enabled
 //! [`ActionRules`](sport_log_types::ActionRule).
 //!
+//! The `weekday` and `time` of [`ActionRules`](sport_log_types::ActionRule) are interpreted in the
+//! time zone of the user.
+//!
 //! The timespan they are created before their `datetime` is determined by the `create_before` field
 //! of the corresponding [`Action`](sport_log_types::Action).
 //!
@@ -28,7 +31,8 @@
 
 use std::{env, fs, process::ExitCode};
 
-use chrono::{DateTime, Datelike, Days, Duration, Utc};
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use rand::Rng;
 use reqwest::{blocking::Client, Error as ReqwestError};
 use serde::Deserialize;
@@ -156,28 +160,49 @@ fn datetimes_for_rule(creatable_action_rule: &CreatableActionRule) -> Vec<DateTi
     datetimes_for_rule_from_start(creatable_action_rule, Utc::now())
 }
 
+/// Converts the local datetime in the time zone to UTC.
+///
+/// Ambiguous datetimes are resolved to the earlier one and datetimes that are skipped by a
+/// daylight saving time transition are moved forward by one hour.
+fn local_to_utc(datetime: NaiveDateTime, timezone: Tz) -> DateTime<Utc> {
+    timezone
+        .from_local_datetime(&datetime)
+        .earliest()
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(datetime + Duration::try_hours(1).unwrap()))
+                .earliest()
+        })
+        .map_or_else(
+            || datetime.and_utc(),
+            |datetime| datetime.with_timezone(&Utc),
+        )
+}
+
 fn datetimes_for_rule_from_start(
     creatable_action_rule: &CreatableActionRule,
-    mut start: DateTime<Utc>,
+    start: DateTime<Utc>,
 ) -> Vec<DateTime<Utc>> {
-    if start.time() > creatable_action_rule.time.time() {
-        start += Duration::try_days(1).unwrap();
+    let timezone: Tz = creatable_action_rule.timezone.parse().unwrap_or(Tz::UTC);
+    let time = creatable_action_rule.time.with_timezone(&timezone).time();
+
+    let mut local_start = start.with_timezone(&timezone).naive_local();
+    if local_start.time() > time {
+        local_start += Duration::try_days(1).unwrap();
     }
-    let first_datetime = DateTime::from_naive_utc_and_offset(
-        start
-            .date_naive()
-            .and_time(creatable_action_rule.time.time())
-            + Days::new(
-                (creatable_action_rule.weekday.to_u32() as i64
-                    - start.weekday().num_days_from_monday() as i64)
-                    .rem_euclid(7) as u64,
-            ),
-        Utc,
-    );
+    let first_datetime = local_start.date().and_time(time)
+        + Days::new(
+            (creatable_action_rule.weekday.to_u32() as i64
+                - local_start.weekday().num_days_from_monday() as i64)
+                .rem_euclid(7) as u64,
+        );
 
     let mut datetimes = vec![];
     for weeks in 0.. {
-        let datetime = first_datetime + Duration::try_weeks(weeks).unwrap();
+        let datetime = local_to_utc(
+            first_datetime + Duration::try_weeks(weeks).unwrap(),
+            timezone,
+        );
         if datetime
             <= start
                 + Duration::try_milliseconds(creatable_action_rule.create_before as i64).unwrap()
@@ -260,6 +285,7 @@ mod tests {
             time: datetime("2000-01-01T12:00:00"),
             arguments: None,
             create_before: Duration::try_days(14).unwrap().num_milliseconds() as i32,
+            timezone: "UTC".to_owned(),
         };
 
         // next day and in 8 days
@@ -284,4 +310,31 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn datetimes_for_rule_from_start_in_timezone() {
+        use super::datetimes_for_rule_from_start;
+        let rule = CreatableActionRule {
+            action_rule_id: ActionRuleId(1),
+            user_id: UserId(1),
+            action_id: ActionId(1),
+            weekday: Weekday::Monday,
+            // 00:30 in Europe/Vienna
+            time: datetime("1999-12-31T23:30:00"),
+            arguments: None,
+            create_before: Duration::try_days(14).unwrap().num_milliseconds() as i32,
+            timezone: "Europe/Vienna".to_owned(),
+        };
+
+        // the weekday is the one in Europe/Vienna and the offset changes on 2023-03-26
+        let datetimes = datetimes_for_rule_from_start(&rule, datetime("2023-03-19T00:00:00"));
+        // 2023-03-19 is Sunday
+        assert_eq!(
+            datetimes,
+            [
+                datetime("2023-03-19T23:30:00"),
+                datetime("2023-03-26T22:30:00"),
+            ]
+        );
+    }
 }
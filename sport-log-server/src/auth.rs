@@ -52,8 +52,9 @@ where
         let username = auth.username();
         let password = auth.password();
 
-        let State(AppState { config, db_pool }) =
-            State::<AppState>::from_request_parts(parts, state).await?;
+        let State(AppState {
+            config, db_pool, ..
+        }) = State::<AppState>::from_request_parts(parts, state).await?;
 
         let mut db = db_pool.get().await?;
 
@@ -90,13 +91,24 @@ where
 /// configured in `sport-log-server.toml` and a `id` header must be preset that is set to the id of
 /// the user the admin wants to authenticate as.
 #[derive(Debug, Clone, Copy)]
-pub struct AuthUserOrAP(UserId);
+pub struct AuthUserOrAP {
+    user_id: UserId,
+    ap_id: Option<ActionProviderId>,
+}
+
+impl AuthUserOrAP {
+    /// Returns the id of the [`ActionProvider`](sport_log_types::ActionProvider) if the request
+    /// was authenticated by an action provider and not by the user or the admin.
+    pub fn ap_id(self) -> Option<ActionProviderId> {
+        self.ap_id
+    }
+}
 
 impl Deref for AuthUserOrAP {
     type Target = UserId;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.user_id
     }
 }
 
@@ -114,34 +126,45 @@ where
         let username = auth.username();
         let password = auth.password();
 
-        let State(AppState { config, db_pool }) =
-            State::<AppState>::from_request_parts(parts, state).await?;
+        let State(AppState {
+            config, db_pool, ..
+        }) = State::<AppState>::from_request_parts(parts, state).await?;
 
         let mut db = db_pool.get().await?;
 
-        if let Ok(id) = UserDb::auth(username, password, &mut db).await {
-            return Ok(Self(id));
+        if let Ok(user_id) = UserDb::auth(username, password, &mut db).await {
+            return Ok(Self {
+                user_id,
+                ap_id: None,
+            });
         }
 
         let user_id = parse_id_header(parts, UserId)?;
         if let Ok(auth) = ActionProviderDb::auth_as_user(username, password, user_id, &mut db).await
         {
             match auth {
-                AuthApForUser::Allowed(_) => return Ok(Self(user_id)),
+                AuthApForUser::Allowed(ap_id) => {
+                    return Ok(Self {
+                        user_id,
+                        ap_id: Some(ap_id),
+                    })
+                }
                 AuthApForUser::Forbidden => return Err(StatusCode::FORBIDDEN.into()),
             }
         }
 
         let admin_password = &config.admin_password;
         if AdminDb::auth(username, password, admin_password).is_ok() {
-            return Ok(Self(user_id));
+            return Ok(Self {
+                user_id,
+                ap_id: None,
+            });
         }
         Err(StatusCode::UNAUTHORIZED.into())
     }
 }
 
 pub enum AuthApForUser {
-    #[allow(dead_code)]
     Allowed(ActionProviderId),
     Forbidden,
 }
@@ -181,8 +204,9 @@ where
         let username = auth.username();
         let password = auth.password();
 
-        let State(AppState { config, db_pool }) =
-            State::<AppState>::from_request_parts(parts, state).await?;
+        let State(AppState {
+            config, db_pool, ..
+        }) = State::<AppState>::from_request_parts(parts, state).await?;
 
         let mut db = db_pool.get().await?;
 
@@ -11,6 +11,21 @@ use serde::{Deserialize, Serialize};
 ///
 /// `ap_self_registration` determines if action providers can register themselves or if only the
 /// admin can create new action provider.
+///
+/// `route_matching_tolerance` is the maximal Hausdorff distance in meter between the track of a
+/// new [`CardioSession`](sport_log_types::CardioSession) and the track of a
+/// [`Route`](sport_log_types::Route) for the route to be assigned to the session automatically.
+/// The route is matched again when the track of a session is updated without changing its route.
+/// If it is not set, routes are never assigned automatically.
+///
+/// `segment_tolerance` is the maximal distance in meter between the track of a
+/// [`CardioSession`](sport_log_types::CardioSession) and a
+/// [`RouteSegment`](sport_log_types::RouteSegment) for the segment to be considered covered by the
+/// session. It defaults to 25 meter.
+///
+/// `dem_dir` is the directory containing the SRTM tiles in HGT format or the GeoTIFF tiles which are
+/// used for the elevation correction of cardio sessions (see [`elevation`](crate::elevation)). If it
+/// is not set, elevation correction is disabled.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     pub admin_password: String,
@@ -20,4 +35,12 @@ pub struct Config {
     pub release_address: SocketAddr,
     pub debug_address: SocketAddr,
     pub app_dir: Option<PathBuf>,
+    pub route_matching_tolerance: Option<f64>,
+    #[serde(default = "default_segment_tolerance")]
+    pub segment_tolerance: f64,
+    pub dem_dir: Option<PathBuf>,
+}
+
+fn default_segment_tolerance() -> f64 {
+    25.
 }
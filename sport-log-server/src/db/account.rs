@@ -16,13 +16,29 @@ impl AccountDataDb {
             diaries: DiaryDb::get_by_user(user_id, db).await?,
             wods: WodDb::get_by_user(user_id, db).await?,
             movements: MovementDb::get_by_user(user_id, db).await?,
+            planned_workouts: PlannedWorkoutDb::get_by_user(user_id, db).await?,
+            strength_blocks: StrengthBlockDb::get_by_user(user_id, db).await?,
             strength_sessions: StrengthSessionDb::get_by_user(user_id, db).await?,
             strength_sets: StrengthSetDb::get_by_user(user_id, db).await?,
+            user_eorms: UserEormDb::get_by_user(user_id, db).await?,
+            strength_programs: StrengthProgramDb::get_by_user(user_id, db).await?,
+            strength_program_sets: StrengthProgramSetDb::get_by_user(user_id, db).await?,
+            strength_templates: StrengthTemplateDb::get_by_user(user_id, db).await?,
+            strength_template_movements: StrengthTemplateMovementDb::get_by_user(user_id, db)
+                .await?,
             metcons: MetconDb::get_by_user(user_id, db).await?,
             metcon_sessions: MetconSessionDb::get_by_user(user_id, db).await?,
             metcon_movements: MetconMovementDb::get_by_user(user_id, db).await?,
+            metcon_movement_scalings: MetconMovementScalingDb::get_by_user(user_id, db).await?,
+            metcon_session_scalings: MetconSessionScalingDb::get_by_user(user_id, db).await?,
             cardio_sessions: CardioSessionDb::get_by_user(user_id, db).await?,
+            cardio_laps: CardioLapDb::get_by_user(user_id, db).await?,
+            multisport_sessions: MultisportSessionDb::get_by_user(user_id, db).await?,
+            multisport_legs: MultisportLegDb::get_by_user(user_id, db).await?,
+            gears: GearDb::get_by_user(user_id, db).await?,
             routes: RouteDb::get_by_user(user_id, db).await?,
+            route_segments: RouteSegmentDb::get_by_user(user_id, db).await?,
+            privacy_zones: PrivacyZoneDb::get_by_user(user_id, db).await?,
             platforms: PlatformDb::get_all(db).await?,
             platform_credentials: PlatformCredentialDb::get_by_user(user_id, db).await?,
             action_providers: ActionProviderDb::get_all(db).await?,
@@ -43,6 +59,18 @@ impl AccountDataDb {
             diaries: DiaryDb::get_by_user_and_epoch(user_id, epoch_map.diary, db).await?,
             wods: WodDb::get_by_user_and_epoch(user_id, epoch_map.wod, db).await?,
             movements: MovementDb::get_by_user_and_epoch(user_id, epoch_map.movement, db).await?,
+            planned_workouts: PlannedWorkoutDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.planned_workout,
+                db,
+            )
+            .await?,
+            strength_blocks: StrengthBlockDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.strength_block,
+                db,
+            )
+            .await?,
             strength_sessions: StrengthSessionDb::get_by_user_and_epoch(
                 user_id,
                 epoch_map.strength_session,
@@ -55,6 +83,31 @@ impl AccountDataDb {
                 db,
             )
             .await?,
+            user_eorms: UserEormDb::get_by_user_and_epoch(user_id, epoch_map.user_eorm, db).await?,
+            strength_programs: StrengthProgramDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.strength_program,
+                db,
+            )
+            .await?,
+            strength_program_sets: StrengthProgramSetDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.strength_program_set,
+                db,
+            )
+            .await?,
+            strength_templates: StrengthTemplateDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.strength_template,
+                db,
+            )
+            .await?,
+            strength_template_movements: StrengthTemplateMovementDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.strength_template_movement,
+                db,
+            )
+            .await?,
             metcons: MetconDb::get_by_user_and_epoch(user_id, epoch_map.metcon, db).await?,
             metcon_sessions: MetconSessionDb::get_by_user_and_epoch(
                 user_id,
@@ -68,13 +121,52 @@ impl AccountDataDb {
                 db,
             )
             .await?,
+            metcon_movement_scalings: MetconMovementScalingDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.metcon_movement_scaling,
+                db,
+            )
+            .await?,
+            metcon_session_scalings: MetconSessionScalingDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.metcon_session_scaling,
+                db,
+            )
+            .await?,
             cardio_sessions: CardioSessionDb::get_by_user_and_epoch(
                 user_id,
                 epoch_map.cardio_session,
                 db,
             )
             .await?,
+            cardio_laps: CardioLapDb::get_by_user_and_epoch(user_id, epoch_map.cardio_lap, db)
+                .await?,
+            multisport_sessions: MultisportSessionDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.multisport_session,
+                db,
+            )
+            .await?,
+            multisport_legs: MultisportLegDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.multisport_leg,
+                db,
+            )
+            .await?,
+            gears: GearDb::get_by_user_and_epoch(user_id, epoch_map.gear, db).await?,
             routes: RouteDb::get_by_user_and_epoch(user_id, epoch_map.route, db).await?,
+            route_segments: RouteSegmentDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.route_segment,
+                db,
+            )
+            .await?,
+            privacy_zones: PrivacyZoneDb::get_by_user_and_epoch(
+                user_id,
+                epoch_map.privacy_zone,
+                db,
+            )
+            .await?,
             platforms: PlatformDb::get_by_epoch(epoch_map.platform, db).await?,
             platform_credentials: PlatformCredentialDb::get_by_user_and_epoch(
                 user_id,
@@ -105,13 +197,35 @@ impl AccountDataDb {
             diary: DiaryDb::get_epoch_by_user(user_id, db).await?,
             wod: WodDb::get_epoch_by_user(user_id, db).await?,
             movement: MovementDb::get_epoch_by_user_optional(user_id, db).await?,
+            planned_workout: PlannedWorkoutDb::get_epoch_by_user(user_id, db).await?,
+            strength_block: StrengthBlockDb::get_epoch_by_user(user_id, db).await?,
             strength_session: StrengthSessionDb::get_epoch_by_user(user_id, db).await?,
             strength_set: StrengthSetDb::get_epoch_by_user(user_id, db).await?,
+            user_eorm: UserEormDb::get_epoch_by_user(user_id, db).await?,
+            strength_program: StrengthProgramDb::get_epoch_by_user_optional(user_id, db).await?,
+            strength_program_set: StrengthProgramSetDb::get_epoch_by_user_optional(user_id, db)
+                .await?,
+            strength_template: StrengthTemplateDb::get_epoch_by_user_optional(user_id, db).await?,
+            strength_template_movement: StrengthTemplateMovementDb::get_epoch_by_user_optional(
+                user_id, db,
+            )
+            .await?,
             metcon: MetconDb::get_epoch_by_user_optional(user_id, db).await?,
             metcon_session: MetconSessionDb::get_epoch_by_user(user_id, db).await?,
             metcon_movement: MetconMovementDb::get_epoch_by_user_optional(user_id, db).await?,
+            metcon_movement_scaling: MetconMovementScalingDb::get_epoch_by_user_optional(
+                user_id, db,
+            )
+            .await?,
+            metcon_session_scaling: MetconSessionScalingDb::get_epoch_by_user(user_id, db).await?,
             cardio_session: CardioSessionDb::get_epoch_by_user(user_id, db).await?,
+            cardio_lap: CardioLapDb::get_epoch_by_user(user_id, db).await?,
+            multisport_session: MultisportSessionDb::get_epoch_by_user(user_id, db).await?,
+            multisport_leg: MultisportLegDb::get_epoch_by_user(user_id, db).await?,
+            gear: GearDb::get_epoch_by_user(user_id, db).await?,
             route: RouteDb::get_epoch_by_user(user_id, db).await?,
+            route_segment: RouteSegmentDb::get_epoch_by_user(user_id, db).await?,
+            privacy_zone: PrivacyZoneDb::get_epoch_by_user(user_id, db).await?,
             platform: PlatformDb::get_epoch(db).await?,
             platform_credential: PlatformCredentialDb::get_epoch_by_user(user_id, db).await?,
             action_provider: ActionProviderDb::get_epoch(db).await?,
@@ -6,7 +6,7 @@ use diesel_async::RunQueryDsl;
 use rand_core::OsRng;
 use sport_log_derive::*;
 use sport_log_types::{
-    schema::{action, action_event, action_provider, action_rule, platform_credential},
+    schema::{action, action_event, action_provider, action_rule, platform_credential, user},
     Action, ActionEvent, ActionEventId, ActionProviderId, ActionRuleId, CreatableActionRule,
     DeletableActionEvent, ExecutableActionEvent, UserId,
 };
@@ -289,6 +289,7 @@ impl GetAll for CreatableActionRuleDb {
     async fn get_all(db: &mut AsyncPgConnection) -> QueryResult<Vec<<Self as Db>::Type>> {
         action_rule::table
             .inner_join(action::table)
+            .inner_join(user::table)
             .filter(action_rule::columns::enabled.eq(true))
             .filter(action_rule::columns::deleted.eq(false))
             .select((
@@ -299,6 +300,7 @@ impl GetAll for CreatableActionRuleDb {
                 action_rule::columns::time,
                 action_rule::columns::arguments,
                 action::columns::create_before,
+                user::columns::timezone,
             ))
             .get_results(db)
             .await
@@ -1,5 +1,21 @@
+use chrono::{DateTime, Utc};
 use derive_deftly::Deftly;
+use diesel::{dsl::count, prelude::*, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use rand_core::{OsRng, RngCore};
 use sport_log_derive::*;
+use sport_log_types::{
+    geo::{bounding_box, hausdorff_distance, segment_time},
+    schema::{cardio_session, gear, multisport_leg, route, route_segment, segment_effort},
+    CardioSession, CardioSessionId, GearId, GearStats, MovementId, MultisportSessionId,
+    MultisportSessionTotals, Position, Route, RouteId, RouteSegment, RouteSegmentId, RouteSession,
+    SegmentEffort, SegmentEffortId, SegmentEfforts, UserId,
+};
+
+use crate::db::*;
+
+/// The id, number of sessions, distance, time and retirement distance of a gear.
+type GearUsage = (GearId, i64, Option<i64>, Option<i64>, Option<i32>);
 
 #[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
 #[derive_deftly(
@@ -16,8 +32,62 @@ use sport_log_derive::*;
 )]
 pub struct RouteDb;
 
+impl RouteDb {
+    /// Returns the id of the [`Route`](sport_log_types::Route) of the user whose track is the most
+    /// similar to `track`.
+    ///
+    /// Only routes with a Hausdorff distance of at most `tolerance` meter are considered.
+    /// Routes that do not lie within the bounding box of `track` extended by `tolerance` can not
+    /// match and are filtered out before the tracks are compared.
+    pub async fn get_matching_route(
+        user_id: UserId,
+        track: &[Position],
+        tolerance: f64,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<RouteId>> {
+        let Some(bounds) = bounding_box(track, tolerance) else {
+            return Ok(None);
+        };
+
+        let routes: Vec<(RouteId, Option<Vec<Position>>)> = route::table
+            .filter(route::columns::user_id.eq(user_id))
+            .filter(route::columns::track.is_not_null())
+            .filter(route::columns::deleted.eq(false))
+            .filter(route::columns::min_latitude.ge(bounds.min_latitude))
+            .filter(route::columns::max_latitude.le(bounds.max_latitude))
+            .filter(route::columns::min_longitude.ge(bounds.min_longitude))
+            .filter(route::columns::max_longitude.le(bounds.max_longitude))
+            .select((route::columns::id, route::columns::track))
+            .get_results(db)
+            .await?;
+
+        Ok(routes
+            .into_iter()
+            .filter_map(|(route_id, route_track)| {
+                hausdorff_distance(track, &route_track?, tolerance)
+                    .map(|distance| (route_id, distance))
+            })
+            .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2))
+            .map(|(route_id, _)| route_id))
+    }
+
+    pub async fn get_tracks_by_user(
+        user_id: UserId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<Vec<Position>>> {
+        route::table
+            .filter(route::columns::user_id.eq(user_id))
+            .filter(route::columns::track.is_not_null())
+            .filter(route::columns::deleted.eq(false))
+            .select(route::columns::track.assume_not_null())
+            .get_results(db)
+            .await
+    }
+}
+
 #[derive(Db, DbWithUserId, DbWithDateTime, ModifiableDb, Deftly)]
 #[derive_deftly(
+    VerifyForUserGet,
     VerifyForUserOrAPGet,
     Create,
     GetById,
@@ -31,3 +101,500 @@ pub struct RouteDb;
     VerifyForUserOrAPCreate
 )]
 pub struct CardioSessionDb;
+
+impl CardioSessionDb {
+    /// Returns the elevations of the track before the elevation of the cardio session has been
+    /// corrected.
+    pub async fn get_uncorrected_elevation(
+        cardio_session_id: CardioSessionId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<Vec<f64>>> {
+        cardio_session::table
+            .find(cardio_session_id)
+            .select(cardio_session::columns::uncorrected_elevation)
+            .first(db)
+            .await
+    }
+
+    pub async fn get_share_token(
+        cardio_session_id: CardioSessionId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<String>> {
+        cardio_session::table
+            .find(cardio_session_id)
+            .select(cardio_session::columns::share_token)
+            .first(db)
+            .await
+    }
+
+    pub async fn set_share_token(
+        cardio_session_id: CardioSessionId,
+        share_token: Option<&str>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        diesel::update(cardio_session::table.find(cardio_session_id))
+            .set(cardio_session::columns::share_token.eq(share_token))
+            .execute(db)
+            .await
+    }
+
+    pub async fn get_by_share_token(
+        share_token: &str,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<CardioSession> {
+        cardio_session::table
+            .filter(cardio_session::columns::share_token.eq(share_token))
+            .filter(cardio_session::columns::deleted.eq(false))
+            .select(CardioSession::as_select())
+            .first(db)
+            .await
+    }
+
+    pub async fn set_uncorrected_elevation(
+        cardio_session_id: CardioSessionId,
+        uncorrected_elevation: Option<&[f64]>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        diesel::update(cardio_session::table.find(cardio_session_id))
+            .set(cardio_session::columns::uncorrected_elevation.eq(uncorrected_elevation))
+            .execute(db)
+            .await
+    }
+
+    pub async fn get_by_user_and_route(
+        user_id: UserId,
+        route_id: RouteId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<RouteSession>> {
+        cardio_session::table
+            .filter(cardio_session::columns::user_id.eq(user_id))
+            .filter(cardio_session::columns::route_id.eq(route_id))
+            .filter(cardio_session::columns::deleted.eq(false))
+            .select((
+                cardio_session::columns::id,
+                cardio_session::columns::movement_id,
+                cardio_session::columns::datetime,
+                cardio_session::columns::distance,
+                cardio_session::columns::time,
+                cardio_session::columns::avg_heart_rate,
+            ))
+            .order_by(cardio_session::columns::datetime)
+            .get_results(db)
+            .await
+    }
+
+    pub async fn get_tracks_by_user(
+        user_id: UserId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<Vec<Position>>> {
+        cardio_session::table
+            .filter(cardio_session::columns::user_id.eq(user_id))
+            .filter(cardio_session::columns::track.is_not_null())
+            .filter(cardio_session::columns::deleted.eq(false))
+            .select(cardio_session::columns::track.assume_not_null())
+            .get_results(db)
+            .await
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct CardioLapDb;
+
+#[derive(Db, DbWithUserId, DbWithDateTime, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserTimespan,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct MultisportSessionDb;
+
+impl MultisportSessionDb {
+    /// Returns the totals of all legs of the multisport session.
+    ///
+    /// If the multisport session has no legs, [`None`] is returned.
+    pub async fn get_totals(
+        multisport_session_id: MultisportSessionId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<MultisportSessionTotals>> {
+        multisport_leg::table
+            .inner_join(
+                cardio_session::table.on(cardio_session::columns::id
+                    .eq(multisport_leg::columns::cardio_session_id)
+                    .and(cardio_session::columns::user_id.eq(multisport_leg::columns::user_id))),
+            )
+            .filter(multisport_leg::columns::multisport_session_id.eq(multisport_session_id))
+            .filter(multisport_leg::columns::deleted.eq(false))
+            .filter(cardio_session::columns::deleted.eq(false))
+            .group_by(multisport_leg::columns::multisport_session_id)
+            .select((
+                multisport_leg::columns::multisport_session_id,
+                count(multisport_leg::columns::id),
+                diesel::dsl::sum(cardio_session::columns::distance),
+                diesel::dsl::sum(cardio_session::columns::ascent),
+                diesel::dsl::sum(cardio_session::columns::descent),
+                diesel::dsl::sum(cardio_session::columns::time),
+                diesel::dsl::sum(multisport_leg::columns::transition_time),
+                diesel::dsl::sum(cardio_session::columns::calories),
+            ))
+            .get_result(db)
+            .await
+            .optional()
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct MultisportLegDb;
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct GearDb;
+
+impl GearDb {
+    /// Returns the id of the [`Gear`](sport_log_types::Gear) of the user that is used by default
+    /// for cardio sessions of the movement.
+    ///
+    /// Retired gear is never returned.
+    pub async fn get_default_by_movement(
+        user_id: UserId,
+        movement_id: MovementId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<GearId>> {
+        gear::table
+            .filter(gear::columns::user_id.eq(user_id))
+            .filter(gear::columns::default_movement_id.eq(movement_id))
+            .filter(gear::columns::retired.eq(false))
+            .filter(gear::columns::deleted.eq(false))
+            .select(gear::columns::id)
+            .first(db)
+            .await
+            .optional()
+    }
+
+    pub async fn get_stats_by_user(
+        user_id: UserId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<GearStats>> {
+        let stats: Vec<GearUsage> = gear::table
+            .left_join(
+                cardio_session::table.on(cardio_session::columns::gear_id
+                    .eq(gear::columns::id.nullable())
+                    .and(cardio_session::columns::user_id.eq(gear::columns::user_id))
+                    .and(cardio_session::columns::deleted.eq(false))),
+            )
+            .filter(gear::columns::user_id.eq(user_id))
+            .filter(gear::columns::deleted.eq(false))
+            .group_by(gear::columns::id)
+            .select((
+                gear::columns::id,
+                count(cardio_session::columns::id.nullable()),
+                diesel::dsl::sum(cardio_session::columns::distance.nullable()),
+                diesel::dsl::sum(cardio_session::columns::time.nullable()),
+                gear::columns::retirement_distance,
+            ))
+            .get_results(db)
+            .await?;
+
+        Ok(stats
+            .into_iter()
+            .map(|(gear_id, sessions, distance, time, retirement_distance)| {
+                let distance = distance.unwrap_or(0);
+                GearStats {
+                    gear_id,
+                    sessions,
+                    distance,
+                    time: time.unwrap_or(0),
+                    retirement_distance,
+                    remaining_distance: retirement_distance.map(|retirement_distance| {
+                        (i64::from(retirement_distance) - distance).max(0)
+                    }),
+                    retirement_distance_reached: retirement_distance.is_some_and(
+                        |retirement_distance| distance >= i64::from(retirement_distance),
+                    ),
+                }
+            })
+            .collect())
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct RouteSegmentDb;
+
+impl RouteSegmentDb {
+    /// Soft deletes all segments of the deleted routes.
+    ///
+    /// This has to be called before the routes are updated. The segments would otherwise only be
+    /// archived by the database without a new epoch, so that clients would never learn about
+    /// their deletion.
+    pub async fn delete_by_deleted_routes(
+        routes: &[Route],
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        let route_ids: Vec<_> = routes
+            .iter()
+            .filter(|route| route.deleted)
+            .map(|route| route.id)
+            .collect();
+        if route_ids.is_empty() {
+            return Ok(0);
+        }
+
+        diesel::update(
+            route_segment::table
+                .filter(route_segment::columns::route_id.eq_any(route_ids))
+                .filter(route_segment::columns::deleted.eq(false)),
+        )
+        .set(route_segment::columns::deleted.eq(true))
+        .execute(db)
+        .await
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(Create)]
+pub struct SegmentEffortDb;
+
+impl SegmentEffortDb {
+    fn new_effort(
+        route_segment: &RouteSegment,
+        cardio_session_id: CardioSessionId,
+        datetime: DateTime<Utc>,
+        track: &[Position],
+        tolerance: f64,
+    ) -> Option<SegmentEffort> {
+        segment_time(track, &route_segment.track, tolerance).map(|time| SegmentEffort {
+            id: SegmentEffortId(OsRng.next_u64() as i64),
+            user_id: route_segment.user_id,
+            route_segment_id: route_segment.id,
+            cardio_session_id,
+            datetime,
+            time,
+            deleted: false,
+        })
+    }
+
+    /// Recomputes the efforts of the cardio session on all segments of its route.
+    ///
+    /// `tolerance` is the maximum distance in meter between the track and a segment for the
+    /// segment to be considered covered.
+    ///
+    /// This has to be called whenever a [`CardioSession`] is created or updated.
+    pub async fn update_by_cardio_session(
+        cardio_session: &CardioSession,
+        tolerance: f64,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        diesel::update(
+            segment_effort::table
+                .filter(segment_effort::columns::cardio_session_id.eq(cardio_session.id))
+                .filter(segment_effort::columns::deleted.eq(false)),
+        )
+        .set(segment_effort::columns::deleted.eq(true))
+        .execute(db)
+        .await?;
+
+        let (Some(route_id), Some(track), false) = (
+            cardio_session.route_id,
+            &cardio_session.track,
+            cardio_session.deleted,
+        ) else {
+            return Ok(0);
+        };
+
+        let route_segments: Vec<RouteSegment> = route_segment::table
+            .filter(route_segment::columns::route_id.eq(route_id))
+            .filter(route_segment::columns::user_id.eq(cardio_session.user_id))
+            .filter(route_segment::columns::deleted.eq(false))
+            .select(RouteSegment::as_select())
+            .get_results(db)
+            .await?;
+
+        let efforts: Vec<_> = route_segments
+            .iter()
+            .filter_map(|route_segment| {
+                Self::new_effort(
+                    route_segment,
+                    cardio_session.id,
+                    cardio_session.datetime,
+                    track,
+                    tolerance,
+                )
+            })
+            .collect();
+
+        Self::create_multiple(&efforts, db).await
+    }
+
+    /// Recomputes the efforts of all cardio sessions of the route on the segment.
+    ///
+    /// `tolerance` is the maximum distance in meter between a track and the segment for the
+    /// segment to be considered covered.
+    ///
+    /// This has to be called whenever a [`RouteSegment`] is created or updated.
+    pub async fn update_by_route_segment(
+        route_segment: &RouteSegment,
+        tolerance: f64,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<usize> {
+        diesel::update(
+            segment_effort::table
+                .filter(segment_effort::columns::route_segment_id.eq(route_segment.id))
+                .filter(segment_effort::columns::deleted.eq(false)),
+        )
+        .set(segment_effort::columns::deleted.eq(true))
+        .execute(db)
+        .await?;
+
+        if route_segment.deleted {
+            return Ok(0);
+        }
+
+        let cardio_sessions: Vec<(CardioSessionId, DateTime<Utc>, Option<Vec<Position>>)> =
+            cardio_session::table
+                .filter(cardio_session::columns::route_id.eq(route_segment.route_id))
+                .filter(cardio_session::columns::user_id.eq(route_segment.user_id))
+                .filter(cardio_session::columns::track.is_not_null())
+                .filter(cardio_session::columns::deleted.eq(false))
+                .select((
+                    cardio_session::columns::id,
+                    cardio_session::columns::datetime,
+                    cardio_session::columns::track,
+                ))
+                .get_results(db)
+                .await?;
+
+        let efforts: Vec<_> = cardio_sessions
+            .into_iter()
+            .filter_map(|(cardio_session_id, datetime, track)| {
+                Self::new_effort(
+                    route_segment,
+                    cardio_session_id,
+                    datetime,
+                    &track?,
+                    tolerance,
+                )
+            })
+            .collect();
+
+        Self::create_multiple(&efforts, db).await
+    }
+
+    /// Returns the best and the most recent effort for every segment of the user.
+    ///
+    /// If `route_segment_id` is set only the efforts on this segment are returned.
+    pub async fn get_best_and_latest_by_user(
+        user_id: UserId,
+        route_segment_id: Option<RouteSegmentId>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<SegmentEfforts>> {
+        let mut best_query = segment_effort::table
+            .filter(segment_effort::columns::user_id.eq(user_id))
+            .filter(segment_effort::columns::deleted.eq(false))
+            .distinct_on(segment_effort::columns::route_segment_id)
+            .order_by((
+                segment_effort::columns::route_segment_id,
+                segment_effort::columns::time,
+            ))
+            .select(SegmentEffort::as_select())
+            .into_boxed();
+        let mut latest_query = segment_effort::table
+            .filter(segment_effort::columns::user_id.eq(user_id))
+            .filter(segment_effort::columns::deleted.eq(false))
+            .distinct_on(segment_effort::columns::route_segment_id)
+            .order_by((
+                segment_effort::columns::route_segment_id,
+                segment_effort::columns::datetime.desc(),
+            ))
+            .select(SegmentEffort::as_select())
+            .into_boxed();
+        if let Some(route_segment_id) = route_segment_id {
+            best_query =
+                best_query.filter(segment_effort::columns::route_segment_id.eq(route_segment_id));
+            latest_query =
+                latest_query.filter(segment_effort::columns::route_segment_id.eq(route_segment_id));
+        }
+
+        let best_efforts: Vec<SegmentEffort> = best_query.get_results(db).await?;
+        let latest_efforts: Vec<SegmentEffort> = latest_query.get_results(db).await?;
+
+        // both queries yield exactly one effort per segment ordered by the segment id
+        Ok(best_efforts
+            .into_iter()
+            .zip(latest_efforts)
+            .map(|(best, latest)| SegmentEfforts {
+                route_segment_id: best.route_segment_id,
+                best,
+                latest,
+            })
+            .collect())
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserUpdate,
+    VerifyForUserCreate
+)]
+pub struct PrivacyZoneDb;
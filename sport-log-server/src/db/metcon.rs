@@ -1,5 +1,14 @@
+use std::collections::HashMap;
+
 use derive_deftly::Deftly;
+use diesel::{prelude::*, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use sport_log_derive::*;
+use sport_log_types::{
+    schema::{metcon, metcon_movement, metcon_session, metcon_session_scaling},
+    Metcon, MetconId, MetconMovement, MetconMovementId, MetconSession, MetconSessionId,
+    MetconSessionScaling,
+};
 
 use crate::db::*;
 
@@ -18,6 +27,26 @@ use crate::db::*;
 )]
 pub struct MetconDb;
 
+impl MetconDb {
+    /// Returns every existing metcon in `metcon_ids` by its id.
+    pub async fn get_by_ids(
+        metcon_ids: &[MetconId],
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<HashMap<MetconId, Metcon>> {
+        metcon::table
+            .filter(metcon::columns::id.eq_any(metcon_ids))
+            .select(Metcon::as_select())
+            .get_results(db)
+            .await
+            .map(|metcons: Vec<Metcon>| {
+                metcons
+                    .into_iter()
+                    .map(|metcon| (metcon.id, metcon))
+                    .collect()
+            })
+    }
+}
+
 #[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
 #[derive_deftly(
     Create,
@@ -33,6 +62,26 @@ pub struct MetconDb;
 )]
 pub struct MetconMovementDb;
 
+impl MetconMovementDb {
+    /// Returns every existing metcon movement in `metcon_movement_ids` by its id.
+    pub async fn get_by_ids(
+        metcon_movement_ids: &[MetconMovementId],
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<HashMap<MetconMovementId, MetconMovement>> {
+        metcon_movement::table
+            .filter(metcon_movement::columns::id.eq_any(metcon_movement_ids))
+            .select(MetconMovement::as_select())
+            .get_results(db)
+            .await
+            .map(|metcon_movements: Vec<MetconMovement>| {
+                metcon_movements
+                    .into_iter()
+                    .map(|metcon_movement| (metcon_movement.id, metcon_movement))
+                    .collect()
+            })
+    }
+}
+
 #[derive(Db, DbWithUserId, DbWithDateTime, ModifiableDb, Deftly)]
 #[derive_deftly(
     VerifyForUserOrAPGet,
@@ -48,3 +97,81 @@ pub struct MetconMovementDb;
     VerifyForUserOrAPCreate
 )]
 pub struct MetconSessionDb;
+
+impl MetconSessionDb {
+    /// Returns every existing metcon session in `metcon_session_ids` by its id.
+    pub async fn get_by_ids(
+        metcon_session_ids: &[MetconSessionId],
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<HashMap<MetconSessionId, MetconSession>> {
+        metcon_session::table
+            .filter(metcon_session::columns::id.eq_any(metcon_session_ids))
+            .select(MetconSession::as_select())
+            .get_results(db)
+            .await
+            .map(|metcon_sessions: Vec<MetconSession>| {
+                metcon_sessions
+                    .into_iter()
+                    .map(|metcon_session| (metcon_session.id, metcon_session))
+                    .collect()
+            })
+    }
+
+    /// Returns the metcon sessions of the metcons in `metcon_ids` that are not deleted.
+    pub async fn get_by_metcons(
+        metcon_ids: &[MetconId],
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<MetconSession>> {
+        metcon_session::table
+            .filter(metcon_session::columns::metcon_id.eq_any(metcon_ids))
+            .filter(metcon_session::columns::deleted.eq(false))
+            .select(MetconSession::as_select())
+            .get_results(db)
+            .await
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    Create,
+    GetById,
+    GetByUserOptional,
+    GetByUserAndEpochOptional,
+    GetEpochByUserOptional,
+    Update,
+    CheckOptionalUserId,
+    VerifyForUserOrAPGetOptional,
+    VerifyForUserOrAPUpdateOptional,
+    VerifyForUserOrAPCreateOptional
+)]
+pub struct MetconMovementScalingDb;
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct MetconSessionScalingDb;
+
+impl MetconSessionScalingDb {
+    /// Returns the scalings of the metcon sessions in `metcon_session_ids` that are not deleted.
+    pub async fn get_by_metcon_sessions(
+        metcon_session_ids: &[MetconSessionId],
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<MetconSessionScaling>> {
+        metcon_session_scaling::table
+            .filter(metcon_session_scaling::columns::metcon_session_id.eq_any(metcon_session_ids))
+            .filter(metcon_session_scaling::columns::deleted.eq(false))
+            .select(MetconSessionScaling::as_select())
+            .get_results(db)
+            .await
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::{BTreeMap, HashMap};
+
+use diesel::{prelude::*, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use sport_log_types::{
+    schema::{metcon, metcon_session, metcon_session_scaling},
+    MetconId, MetconRecord, MetconSession, MetconSessionId, MetconType, ScalingLevel, UserId,
+};
+
+pub struct MetconRecordDb;
+
+impl MetconRecordDb {
+    /// Returns the best session of the user for every metcon and every scaling level.
+    ///
+    /// If several sessions have the same score the earliest one is the record.
+    ///
+    /// If `metcon_id` is set only the record of this metcon is returned.
+    pub async fn get_by_user(
+        user_id: UserId,
+        metcon_id: Option<MetconId>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<MetconRecord>> {
+        let mut query = metcon_session::table
+            .inner_join(metcon::table)
+            .filter(metcon_session::columns::user_id.eq(user_id))
+            .filter(metcon_session::columns::deleted.eq(false))
+            .filter(metcon::columns::metcon_type.ne(MetconType::Emom))
+            .select((MetconSession::as_select(), metcon::columns::metcon_type))
+            .order_by((
+                metcon_session::columns::datetime,
+                metcon_session::columns::id,
+            ))
+            .into_boxed();
+        if let Some(metcon_id) = metcon_id {
+            query = query.filter(metcon_session::columns::metcon_id.eq(metcon_id));
+        }
+        let metcon_sessions: Vec<(MetconSession, MetconType)> = query.get_results(db).await?;
+
+        let metcon_session_ids: Vec<_> = metcon_sessions
+            .iter()
+            .map(|(metcon_session, _)| metcon_session.id)
+            .collect();
+        let metcon_session_scalings: Vec<(MetconSessionId, ScalingLevel)> =
+            metcon_session_scaling::table
+                .filter(
+                    metcon_session_scaling::columns::metcon_session_id.eq_any(metcon_session_ids),
+                )
+                .filter(metcon_session_scaling::columns::deleted.eq(false))
+                .select((
+                    metcon_session_scaling::columns::metcon_session_id,
+                    metcon_session_scaling::columns::scaling_level,
+                ))
+                .get_results(db)
+                .await?;
+        let mut scaling_levels: HashMap<MetconSessionId, Vec<ScalingLevel>> = HashMap::new();
+        for (metcon_session_id, scaling_level) in metcon_session_scalings {
+            scaling_levels
+                .entry(metcon_session_id)
+                .or_default()
+                .push(scaling_level);
+        }
+
+        let mut records: BTreeMap<(MetconId, ScalingLevel), MetconSession> = BTreeMap::new();
+        for (metcon_session, metcon_type) in metcon_sessions {
+            let scaling_level = metcon_session.scaling_level(
+                scaling_levels
+                    .get(&metcon_session.id)
+                    .into_iter()
+                    .flatten()
+                    .copied(),
+            );
+            match records.get_mut(&(metcon_session.metcon_id, scaling_level)) {
+                Some(record) => {
+                    if metcon_session.cmp_score(record, metcon_type).is_gt() {
+                        *record = metcon_session;
+                    }
+                }
+                None => {
+                    records.insert((metcon_session.metcon_id, scaling_level), metcon_session);
+                }
+            }
+        }
+
+        Ok(records
+            .into_iter()
+            .map(
+                |((metcon_id, scaling_level), metcon_session)| MetconRecord {
+                    metcon_id,
+                    scaling_level,
+                    metcon_session,
+                },
+            )
+            .collect())
+    }
+}
@@ -15,9 +15,13 @@ mod admin;
 mod cardio;
 mod diary_wod;
 mod metcon;
+mod metcon_record;
 mod movement;
+mod planned_workout;
 mod platform;
 mod strength;
+mod strength_record;
+mod training_volume;
 mod user;
 
 pub use account::*;
@@ -26,9 +30,13 @@ pub use admin::*;
 pub use cardio::*;
 pub use diary_wod::*;
 pub use metcon::*;
+pub use metcon_record::*;
 pub use movement::*;
+pub use planned_workout::*;
 pub use platform::*;
 pub use strength::*;
+pub use strength_record::*;
+pub use training_volume::*;
 pub use user::*;
 
 use crate::auth::*;
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use derive_deftly::Deftly;
+use diesel::{prelude::*, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use sport_log_derive::*;
+use sport_log_types::{
+    schema::{cardio_session, metcon_session, strength_session},
+    PlanCompliance, PlannedWorkout, PlannedWorkoutId, PlannedWorkoutStatus, PlannedWorkoutType,
+    UserId,
+};
+
+use crate::db::*;
+
+#[derive(Db, DbWithUserId, DbWithDateTime, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserTimespan,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct PlannedWorkoutDb;
+
+impl PlannedWorkoutDb {
+    /// Returns whether a cardio session with `distance` and `time` reached the distance and time
+    /// target of the planned workout.
+    fn target_met(
+        planned_workout: &PlannedWorkout,
+        distance: Option<i32>,
+        time: Option<i32>,
+    ) -> bool {
+        match (planned_workout.distance, planned_workout.time) {
+            (Some(target_distance), Some(target_time)) => match (distance, time) {
+                (Some(distance), Some(time)) => {
+                    distance >= target_distance
+                        && i64::from(time) * i64::from(target_distance)
+                            <= i64::from(target_time) * i64::from(distance)
+                }
+                _ => false,
+            },
+            (Some(target_distance), None) => distance.is_some_and(|d| d >= target_distance),
+            (None, Some(target_time)) => time.is_some_and(|t| t >= target_time),
+            (None, None) => true,
+        }
+    }
+
+    /// Returns the compliance of the user with the planned workouts in the timespan.
+    ///
+    /// A planned workout is completed if a strength, metcon or cardio session of the user
+    /// references it. The distance and time targets of cardio workouts are compared with the
+    /// cardio sessions that reference them.
+    pub async fn get_compliance(
+        user_id: UserId,
+        timespan: Timespan,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<PlanCompliance> {
+        let planned_workouts: Vec<_> = Self::get_by_user_and_timespan(user_id, timespan, db)
+            .await?
+            .into_iter()
+            .filter(|planned_workout| !planned_workout.deleted)
+            .collect();
+        let planned_workout_ids: Vec<_> = planned_workouts
+            .iter()
+            .map(|planned_workout| planned_workout.id)
+            .collect();
+
+        let mut completed_ids: HashSet<PlannedWorkoutId> = HashSet::new();
+        completed_ids.extend(
+            strength_session::table
+                .filter(strength_session::columns::user_id.eq(user_id))
+                .filter(strength_session::columns::deleted.eq(false))
+                .filter(strength_session::columns::planned_workout_id.eq_any(&planned_workout_ids))
+                .select(strength_session::columns::planned_workout_id.assume_not_null())
+                .get_results::<PlannedWorkoutId>(db)
+                .await?,
+        );
+        completed_ids.extend(
+            metcon_session::table
+                .filter(metcon_session::columns::user_id.eq(user_id))
+                .filter(metcon_session::columns::deleted.eq(false))
+                .filter(metcon_session::columns::planned_workout_id.eq_any(&planned_workout_ids))
+                .select(metcon_session::columns::planned_workout_id.assume_not_null())
+                .get_results::<PlannedWorkoutId>(db)
+                .await?,
+        );
+        let cardio_results: Vec<(PlannedWorkoutId, Option<i32>, Option<i32>)> =
+            cardio_session::table
+                .filter(cardio_session::columns::user_id.eq(user_id))
+                .filter(cardio_session::columns::deleted.eq(false))
+                .filter(cardio_session::columns::planned_workout_id.eq_any(&planned_workout_ids))
+                .select((
+                    cardio_session::columns::planned_workout_id.assume_not_null(),
+                    cardio_session::columns::distance,
+                    cardio_session::columns::time,
+                ))
+                .get_results(db)
+                .await?;
+        completed_ids.extend(cardio_results.iter().map(|(id, _, _)| *id));
+
+        let now = Utc::now();
+        let mut planned_workouts: Vec<_> = planned_workouts
+            .into_iter()
+            .map(|planned_workout| {
+                let completed = completed_ids.contains(&planned_workout.id);
+                let target_met = (completed
+                    && planned_workout.planned_workout_type == PlannedWorkoutType::Cardio
+                    && (planned_workout.distance.is_some() || planned_workout.time.is_some()))
+                .then(|| {
+                    cardio_results.iter().any(|(id, distance, time)| {
+                        *id == planned_workout.id
+                            && Self::target_met(&planned_workout, *distance, *time)
+                    })
+                });
+                PlannedWorkoutStatus {
+                    planned_workout_id: planned_workout.id,
+                    datetime: planned_workout.datetime,
+                    completed,
+                    missed: !completed && planned_workout.datetime < now,
+                    target_met,
+                }
+            })
+            .collect();
+        planned_workouts.sort_by_key(|status| status.datetime);
+
+        let planned = planned_workouts.len() as i64;
+        let completed = planned_workouts.iter().filter(|s| s.completed).count() as i64;
+        let missed = planned_workouts.iter().filter(|s| s.missed).count() as i64;
+        let compliance =
+            (completed + missed > 0).then(|| completed as f32 / (completed + missed) as f32);
+
+        Ok(PlanCompliance {
+            planned,
+            completed,
+            missed,
+            compliance,
+            planned_workouts,
+        })
+    }
+}
@@ -1,5 +1,17 @@
 use derive_deftly::Deftly;
+use diesel::{prelude::*, QueryResult};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use sport_log_derive::*;
+use sport_log_types::{
+    schema::{
+        strength_program, strength_program_set, strength_session, strength_set, strength_template,
+        strength_template_movement, user, user_eorm,
+    },
+    Eorm, EormFormula, EormPercentage, MovementId, StrengthProgramId, StrengthProgramSet,
+    StrengthSessionId, StrengthSet, StrengthTemplateId, StrengthTemplateMovement, UserId,
+};
+
+use crate::db::*;
 
 #[derive(Db, DbWithUserId, DbWithDateTime, ModifiableDb, Deftly)]
 #[derive_deftly(
@@ -17,6 +29,22 @@ use sport_log_derive::*;
 )]
 pub struct StrengthSessionDb;
 
+#[derive(Db, DbWithUserId, DbWithDateTime, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserTimespan,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct StrengthBlockDb;
+
 #[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
 #[derive_deftly(
     VerifyForUserOrAPGet,
@@ -32,6 +60,238 @@ pub struct StrengthSessionDb;
 )]
 pub struct StrengthSetDb;
 
+impl StrengthSetDb {
+    /// Returns the sets of the most recent strength session of the movement ordered by set number.
+    ///
+    /// Warm-up sets are ignored and strength sessions with only warm-up sets are skipped.
+    pub async fn get_latest_by_user_and_movement(
+        user_id: UserId,
+        movement_id: MovementId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<StrengthSet>> {
+        let strength_session_id: Option<StrengthSessionId> = strength_set::table
+            .inner_join(strength_session::table)
+            .filter(strength_session::columns::user_id.eq(user_id))
+            .filter(strength_session::columns::movement_id.eq(movement_id))
+            .filter(strength_session::columns::deleted.eq(false))
+            .filter(strength_set::columns::deleted.eq(false))
+            .filter(strength_set::columns::is_warmup.eq(false))
+            .order_by(strength_session::columns::datetime.desc())
+            .select(strength_session::columns::id)
+            .first(db)
+            .await
+            .optional()?;
+        let Some(strength_session_id) = strength_session_id else {
+            return Ok(vec![]);
+        };
+
+        strength_set::table
+            .filter(strength_set::columns::strength_session_id.eq(strength_session_id))
+            .filter(strength_set::columns::deleted.eq(false))
+            .filter(strength_set::columns::is_warmup.eq(false))
+            .order_by(strength_set::columns::set_number)
+            .select(StrengthSet::as_select())
+            .get_results(db)
+            .await
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    Create,
+    GetById,
+    GetByUserOptional,
+    GetByUserAndEpochOptional,
+    GetEpochByUserOptional,
+    Update,
+    CheckOptionalUserId,
+    VerifyForUserOrAPGetOptional,
+    VerifyForUserOrAPUpdateOptional,
+    VerifyForUserOrAPCreateOptional
+)]
+pub struct StrengthProgramDb;
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    Create,
+    GetById,
+    GetByUserOptional,
+    GetByUserAndEpochOptional,
+    GetEpochByUserOptional,
+    Update,
+    CheckOptionalUserId,
+    VerifyForUserOrAPGetOptional,
+    VerifyForUserOrAPUpdateOptional,
+    VerifyForUserOrAPCreateOptional
+)]
+pub struct StrengthProgramSetDb;
+
+impl StrengthProgramSetDb {
+    /// Returns the predefined sets and the sets of the user of the strength program ordered by
+    /// week, day and set number.
+    ///
+    /// If the strength program is neither predefined nor belongs to the user, no sets are returned.
+    pub async fn get_by_strength_program(
+        strength_program_id: StrengthProgramId,
+        user_id: UserId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<StrengthProgramSet>> {
+        strength_program_set::table
+            .inner_join(strength_program::table)
+            .filter(strength_program::columns::id.eq(strength_program_id))
+            .filter(
+                strength_program::columns::user_id
+                    .eq(user_id)
+                    .or(strength_program::columns::user_id.is_null()),
+            )
+            .filter(strength_program::columns::deleted.eq(false))
+            .filter(
+                strength_program_set::columns::user_id
+                    .eq(user_id)
+                    .or(strength_program_set::columns::user_id.is_null()),
+            )
+            .filter(strength_program_set::columns::deleted.eq(false))
+            .order_by((
+                strength_program_set::columns::week,
+                strength_program_set::columns::day,
+                strength_program_set::columns::set_number,
+            ))
+            .select(StrengthProgramSet::as_select())
+            .get_results(db)
+            .await
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    Create,
+    GetById,
+    GetByUserOptional,
+    GetByUserAndEpochOptional,
+    GetEpochByUserOptional,
+    Update,
+    CheckOptionalUserId,
+    VerifyForUserOrAPGetOptional,
+    VerifyForUserOrAPUpdateOptional,
+    VerifyForUserOrAPCreateOptional
+)]
+pub struct StrengthTemplateDb;
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    Create,
+    GetById,
+    GetByUserOptional,
+    GetByUserAndEpochOptional,
+    GetEpochByUserOptional,
+    Update,
+    CheckOptionalUserId,
+    VerifyForUserOrAPGetOptional,
+    VerifyForUserOrAPUpdateOptional,
+    VerifyForUserOrAPCreateOptional
+)]
+pub struct StrengthTemplateMovementDb;
+
+impl StrengthTemplateMovementDb {
+    /// Returns the predefined movements and the movements of the user of the strength template
+    /// ordered by movement number.
+    ///
+    /// If the strength template is neither predefined nor belongs to the user, no movements are
+    /// returned.
+    pub async fn get_by_strength_template(
+        strength_template_id: StrengthTemplateId,
+        user_id: UserId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<StrengthTemplateMovement>> {
+        strength_template_movement::table
+            .inner_join(strength_template::table)
+            .filter(strength_template::columns::id.eq(strength_template_id))
+            .filter(
+                strength_template::columns::user_id
+                    .eq(user_id)
+                    .or(strength_template::columns::user_id.is_null()),
+            )
+            .filter(strength_template::columns::deleted.eq(false))
+            .filter(
+                strength_template_movement::columns::user_id
+                    .eq(user_id)
+                    .or(strength_template_movement::columns::user_id.is_null()),
+            )
+            .filter(strength_template_movement::columns::deleted.eq(false))
+            .order_by(strength_template_movement::columns::movement_number)
+            .select(StrengthTemplateMovement::as_select())
+            .get_results(db)
+            .await
+    }
+}
+
 #[derive(Db, Deftly)]
 #[derive_deftly(VerifyForAdminGet, GetById, GetAll)]
 pub struct EormDb;
+
+impl EormDb {
+    /// Returns the eorm table according to the [`EormFormula`] of the user ordered by reps.
+    pub async fn get_by_user(
+        user_id: UserId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<EormPercentage>> {
+        let eorm_formula: EormFormula = user::table
+            .find(user_id)
+            .select(user::columns::eorm_formula)
+            .get_result(db)
+            .await?;
+        let mut eorms: Vec<EormPercentage> = Self::get_all(db)
+            .await?
+            .into_iter()
+            .map(|eorm: Eorm| EormPercentage {
+                reps: eorm.reps,
+                percentage: eorm.percentage,
+            })
+            .collect();
+
+        match eorm_formula {
+            EormFormula::Table => {}
+            EormFormula::Custom => {
+                let user_eorms: Vec<(i32, f32)> = user_eorm::table
+                    .filter(user_eorm::columns::user_id.eq(user_id))
+                    .filter(user_eorm::columns::deleted.eq(false))
+                    .select((user_eorm::columns::reps, user_eorm::columns::percentage))
+                    .get_results(db)
+                    .await?;
+                for (reps, percentage) in user_eorms {
+                    match eorms.iter_mut().find(|eorm| eorm.reps == reps) {
+                        Some(eorm) => eorm.percentage = percentage,
+                        None => eorms.push(EormPercentage { reps, percentage }),
+                    }
+                }
+            }
+            eorm_formula => {
+                eorms.retain_mut(|eorm| match eorm_formula.percentage(eorm.reps) {
+                    Some(percentage) => {
+                        eorm.percentage = percentage;
+                        true
+                    }
+                    None => false,
+                });
+            }
+        }
+
+        eorms.sort_by_key(|eorm| eorm.reps);
+        Ok(eorms)
+    }
+}
+
+#[derive(Db, DbWithUserId, ModifiableDb, Deftly)]
+#[derive_deftly(
+    VerifyForUserOrAPGet,
+    Create,
+    GetById,
+    GetByUser,
+    GetByUserAndEpoch,
+    Update,
+    GetEpochByUser,
+    CheckUserId,
+    VerifyForUserOrAPUpdate,
+    VerifyForUserOrAPCreate
+)]
+pub struct UserEormDb;
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use diesel::{
+    prelude::*,
+    sql_types::{Array, BigInt, Float, Integer, Nullable, Timestamptz},
+    QueryResult,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use sport_log_types::{
+    schema::{strength_session, strength_set},
+    E1rm, MovementId, RepMax, StrengthRecords, StrengthSessionId, UserId,
+};
+
+use crate::db::EormDb;
+
+/// Selects the best estimated one rep max of every strength session ordered by datetime.
+///
+/// `$1` is the user, `$2` an optional movement and `$3` and `$4` the reps and percentages of the
+/// eorm formula of the user.
+const E1RM_QUERY: &str = "
+    select movement_id, strength_session_id, datetime, e1rm
+    from (
+        select distinct on (strength_session.id)
+            strength_session.movement_id,
+            strength_session.id as strength_session_id,
+            strength_session.datetime,
+            strength_set.weight / eorm.percentage as e1rm
+        from strength_set
+            join strength_session on strength_session.id = strength_set.strength_session_id
+            join unnest($3, $4) as eorm(reps, percentage) on eorm.reps = strength_set.count
+        where strength_session.user_id = $1
+            and ($2::bigint is null or strength_session.movement_id = $2)
+            and strength_session.deleted = false
+            and strength_set.deleted = false
+            and strength_set.is_warmup = false
+            and strength_set.weight is not null
+        order by strength_session.id, e1rm desc
+    ) as session_e1rm
+    order by datetime, strength_session_id";
+
+#[derive(QueryableByName)]
+struct SessionE1rm {
+    #[diesel(sql_type = BigInt)]
+    movement_id: MovementId,
+    #[diesel(sql_type = BigInt)]
+    strength_session_id: StrengthSessionId,
+    #[diesel(sql_type = Timestamptz)]
+    datetime: DateTime<Utc>,
+    #[diesel(sql_type = Float)]
+    e1rm: f32,
+}
+
+pub struct StrengthRecordDb;
+
+impl StrengthRecordDb {
+    fn empty_records(movement_id: MovementId) -> StrengthRecords {
+        StrengthRecords {
+            movement_id,
+            e1rms: vec![],
+            rep_maxes: vec![],
+        }
+    }
+
+    /// Returns the estimated one rep maxes and the rep maxes of the user for every movement.
+    ///
+    /// The one rep maxes are estimated with the [`EormFormula`](sport_log_types::EormFormula) of
+    /// the user.
+    ///
+    /// If `movement_id` is set only the records of this movement are returned.
+    pub async fn get_by_user(
+        user_id: UserId,
+        movement_id: Option<MovementId>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<StrengthRecords>> {
+        let mut rep_max_query = strength_set::table
+            .inner_join(strength_session::table)
+            .filter(strength_session::columns::user_id.eq(user_id))
+            .filter(strength_session::columns::deleted.eq(false))
+            .filter(strength_set::columns::deleted.eq(false))
+            .filter(strength_set::columns::is_warmup.eq(false))
+            .filter(strength_set::columns::weight.is_not_null())
+            .distinct_on((
+                strength_session::columns::movement_id,
+                strength_set::columns::count,
+            ))
+            .order_by((
+                strength_session::columns::movement_id,
+                strength_set::columns::count,
+                strength_set::columns::weight.desc(),
+                strength_session::columns::datetime,
+            ))
+            .select((
+                strength_session::columns::movement_id,
+                (
+                    strength_set::columns::count,
+                    strength_set::columns::weight.assume_not_null(),
+                    strength_session::columns::id,
+                    strength_session::columns::datetime,
+                ),
+            ))
+            .into_boxed();
+        if let Some(movement_id) = movement_id {
+            rep_max_query =
+                rep_max_query.filter(strength_session::columns::movement_id.eq(movement_id));
+        }
+
+        let (reps, percentages): (Vec<i32>, Vec<f32>) = EormDb::get_by_user(user_id, db)
+            .await?
+            .into_iter()
+            .map(|eorm| (eorm.reps, eorm.percentage))
+            .unzip();
+        // the percentages depend on the eorm formula of the user so they are passed as arrays
+        let e1rms: Vec<SessionE1rm> = diesel::sql_query(E1RM_QUERY)
+            .bind::<BigInt, _>(user_id)
+            .bind::<Nullable<BigInt>, _>(movement_id)
+            .bind::<Array<Integer>, _>(reps)
+            .bind::<Array<Float>, _>(percentages)
+            .get_results(db)
+            .await?;
+        let rep_maxes: Vec<(MovementId, RepMax)> = rep_max_query.get_results(db).await?;
+
+        let mut records = BTreeMap::new();
+        for e1rm in e1rms {
+            records
+                .entry(e1rm.movement_id)
+                .or_insert_with(|| Self::empty_records(e1rm.movement_id))
+                .e1rms
+                .push(E1rm {
+                    strength_session_id: e1rm.strength_session_id,
+                    datetime: e1rm.datetime,
+                    e1rm: e1rm.e1rm,
+                });
+        }
+        for (movement_id, rep_max) in rep_maxes {
+            records
+                .entry(movement_id)
+                .or_insert_with(|| Self::empty_records(movement_id))
+                .rep_maxes
+                .push(rep_max);
+        }
+
+        Ok(records.into_values().collect())
+    }
+
+    /// Returns the best estimated one rep max of the user for the movement.
+    pub async fn get_best_e1rm(
+        user_id: UserId,
+        movement_id: MovementId,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Option<f32>> {
+        Ok(Self::get_by_user(user_id, Some(movement_id), db)
+            .await?
+            .into_iter()
+            .flat_map(|records| records.e1rms)
+            .map(|e1rm| e1rm.e1rm)
+            .reduce(f32::max))
+    }
+}
@@ -0,0 +1,498 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::NaiveDateTime;
+use diesel::{
+    define_sql_function,
+    dsl::count,
+    prelude::*,
+    sql_types::{Integer, Text, Timestamptz},
+    QueryResult,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use sport_log_types::{
+    schema::{
+        metcon, metcon_movement, metcon_session, movement, strength_session, strength_set, user,
+    },
+    MetconSessionId, MetconType, MovementDimension, MovementId, MuscleGroup, MuscleGroupVolume,
+    Sex, StrengthSessionId, TrainingVolume, UserId, VolumePeriod,
+};
+
+use crate::db::Timespan;
+
+define_sql_function! {
+    /// Converts the timestamp to the local time in the time zone.
+    #[sql_name = "timezone"]
+    fn local_time(zone: Text, timestamp: Timestamptz) -> Timestamp;
+}
+
+define_sql_function! {
+    /// Converts the integer to a real.
+    fn float4(x: Integer) -> Float;
+}
+
+/// The movement, strength session, local datetime, number of sets, reps and tonnage of a strength
+/// session.
+type StrengthSessionVolume = (
+    MovementId,
+    StrengthSessionId,
+    NaiveDateTime,
+    i64,
+    Option<i64>,
+    Option<f32>,
+);
+
+/// The metcon session, local datetime, metcon type, rounds of the metcon and time, rounds, reps and
+/// round reps of a metcon session.
+type MetconSessionScore = (
+    MetconSessionId,
+    NaiveDateTime,
+    MetconType,
+    Option<i32>,
+    Option<i32>,
+    Option<i32>,
+    Option<i32>,
+    Option<Vec<i32>>,
+);
+
+/// The movement, count, male weight and female weight of a metcon movement.
+type MetconMovementLoad = (MovementId, i32, Option<f32>, Option<f32>);
+
+/// A strength or metcon session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Session {
+    Strength(StrengthSessionId),
+    Metcon(MetconSessionId),
+}
+
+/// The volume of a movement in a session.
+struct SessionVolume {
+    movement_id: MovementId,
+    session: Session,
+    datetime: NaiveDateTime,
+    sets: i64,
+    reps: i64,
+    tonnage: f32,
+}
+
+/// The volume of a movement or muscle group in a period.
+#[derive(Default)]
+struct Volume {
+    sessions: HashSet<Session>,
+    sets: i64,
+    reps: i64,
+    tonnage: f32,
+}
+
+impl Volume {
+    fn add(&mut self, session_volume: &SessionVolume) {
+        self.sessions.insert(session_volume.session);
+        self.sets += session_volume.sets;
+        self.reps += session_volume.reps;
+        self.tonnage += session_volume.tonnage;
+    }
+}
+
+/// Adds a round in which the count of every movement is multiplied by `multiplier` to the sets and
+/// reps of the movements.
+///
+/// If `max_reps` is set, the round was not finished and the reps are assigned to the movements in
+/// order until `max_reps` is reached.
+fn add_round(volumes: &mut [(i64, i64)], counts: &[i32], multiplier: i32, max_reps: Option<i32>) {
+    let mut remaining = max_reps.map_or(i64::MAX, i64::from);
+    for ((sets, reps), count) in volumes.iter_mut().zip(counts) {
+        let round_reps = (i64::from(*count) * i64::from(multiplier)).min(remaining);
+        if round_reps > 0 {
+            *sets += 1;
+            *reps += round_reps;
+            remaining -= round_reps;
+        }
+    }
+}
+
+/// Returns the sets and reps of every movement of a metcon session.
+///
+/// `counts` contains the count of every movement of the metcon ordered by movement number.
+///
+/// Returns `None` if the reps of the movements can not be determined from the score.
+fn metcon_sets_and_reps(score: &MetconSessionScore, counts: &[i32]) -> Option<Vec<(i64, i64)>> {
+    let (_, _, metcon_type, metcon_rounds, time, rounds, reps, round_reps) = score;
+    let finished = time.is_some();
+    let completed_rounds = rounds.unwrap_or(0);
+    let failed_round_reps = Some(reps.unwrap_or(0));
+
+    let mut volumes = vec![(0, 0); counts.len()];
+    match metcon_type {
+        MetconType::Emom => {
+            for _ in 0..metcon_rounds.unwrap_or(1) {
+                add_round(&mut volumes, counts, 1, None);
+            }
+        }
+        MetconType::ForTime if finished => {
+            for _ in 0..metcon_rounds.unwrap_or(1) {
+                add_round(&mut volumes, counts, 1, None);
+            }
+        }
+        MetconType::Amrap | MetconType::ForTime => {
+            for _ in 0..completed_rounds {
+                add_round(&mut volumes, counts, 1, None);
+            }
+            add_round(&mut volumes, counts, 1, failed_round_reps);
+        }
+        MetconType::Ladder if finished => {
+            for round in 1..=metcon_rounds.unwrap_or(0) {
+                add_round(&mut volumes, counts, round, None);
+            }
+        }
+        MetconType::DeathBy | MetconType::Ladder => {
+            for round in 1..=completed_rounds {
+                add_round(&mut volumes, counts, round, None);
+            }
+            add_round(
+                &mut volumes,
+                counts,
+                completed_rounds + 1,
+                failed_round_reps,
+            );
+        }
+        MetconType::Chipper if finished => add_round(&mut volumes, counts, 1, None),
+        MetconType::Chipper => add_round(&mut volumes, counts, 1, failed_round_reps),
+        MetconType::Tabata => match round_reps {
+            Some(round_reps) if !volumes.is_empty() => {
+                // the intervals alternate between the movements
+                for (interval, interval_reps) in round_reps.iter().enumerate() {
+                    let (sets, reps) = &mut volumes[interval % counts.len()];
+                    *sets += 1;
+                    *reps += i64::from(*interval_reps);
+                }
+            }
+            None if volumes.len() == 1 => {
+                volumes[0] = (
+                    i64::from(metcon_rounds.unwrap_or(1)),
+                    i64::from(reps.unwrap_or(0)),
+                );
+            }
+            _ => return None,
+        },
+    }
+    Some(volumes)
+}
+
+pub struct TrainingVolumeDb;
+
+impl TrainingVolumeDb {
+    /// Returns the volume of every movement in every strength and metcon session of the user in
+    /// the timespan.
+    ///
+    /// The datetimes of the sessions are converted to the local time in `timezone` or the time
+    /// zone of the user.
+    async fn get_session_volumes(
+        user_id: UserId,
+        timespan: Timespan,
+        timezone: Option<&str>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<SessionVolume>> {
+        let (user_timezone, sex): (String, Option<Sex>) = user::table
+            .filter(user::columns::id.eq(user_id))
+            .select((user::columns::timezone, user::columns::sex))
+            .get_result(db)
+            .await?;
+        let timezone = timezone.unwrap_or(&user_timezone);
+
+        let (start, end) = match timespan {
+            Timespan::StartEnd(start, end) => (Some(start), Some(end)),
+            Timespan::Start(start) => (Some(start), None),
+            Timespan::End(end) => (None, Some(end)),
+            Timespan::All => (None, None),
+        };
+
+        let mut strength_query = strength_set::table
+            .inner_join(strength_session::table)
+            .filter(strength_session::columns::user_id.eq(user_id))
+            .filter(strength_session::columns::deleted.eq(false))
+            .filter(strength_set::columns::deleted.eq(false))
+            .filter(strength_set::columns::is_warmup.eq(false))
+            .group_by(strength_session::columns::id)
+            .select((
+                strength_session::columns::movement_id,
+                strength_session::columns::id,
+                local_time(timezone, strength_session::columns::datetime),
+                count(strength_set::columns::id),
+                diesel::dsl::sum(strength_set::columns::count),
+                diesel::dsl::sum(
+                    float4(strength_set::columns::count)
+                        * strength_set::columns::weight.assume_not_null(),
+                ),
+            ))
+            .into_boxed();
+        let mut metcon_query = metcon_session::table
+            .inner_join(metcon::table)
+            .inner_join(
+                metcon_movement::table
+                    .on(metcon_movement::columns::metcon_id.eq(metcon_session::columns::metcon_id)),
+            )
+            .inner_join(
+                movement::table.on(movement::columns::id.eq(metcon_movement::columns::movement_id)),
+            )
+            .filter(metcon_session::columns::user_id.eq(user_id))
+            .filter(metcon_session::columns::deleted.eq(false))
+            .filter(metcon_movement::columns::deleted.eq(false))
+            .filter(movement::columns::movement_dimension.eq(MovementDimension::Reps))
+            .order_by((
+                metcon_session::columns::id,
+                metcon_movement::columns::movement_number,
+            ))
+            .select((
+                (
+                    metcon_session::columns::id,
+                    local_time(timezone, metcon_session::columns::datetime),
+                    metcon::columns::metcon_type,
+                    metcon::columns::rounds,
+                    metcon_session::columns::time,
+                    metcon_session::columns::rounds,
+                    metcon_session::columns::reps,
+                    metcon_session::columns::round_reps,
+                ),
+                (
+                    metcon_movement::columns::movement_id,
+                    metcon_movement::columns::count,
+                    metcon_movement::columns::male_weight,
+                    metcon_movement::columns::female_weight,
+                ),
+            ))
+            .into_boxed();
+        if let Some(start) = start {
+            strength_query = strength_query.filter(strength_session::columns::datetime.ge(start));
+            metcon_query = metcon_query.filter(metcon_session::columns::datetime.ge(start));
+        }
+        if let Some(end) = end {
+            strength_query = strength_query.filter(strength_session::columns::datetime.le(end));
+            metcon_query = metcon_query.filter(metcon_session::columns::datetime.le(end));
+        }
+
+        let strength_sessions: Vec<StrengthSessionVolume> = strength_query.get_results(db).await?;
+        let metcon_movements: Vec<(MetconSessionScore, MetconMovementLoad)> =
+            metcon_query.get_results(db).await?;
+
+        let mut session_volumes: Vec<_> = strength_sessions
+            .into_iter()
+            .map(
+                |(movement_id, strength_session_id, datetime, sets, reps, tonnage)| SessionVolume {
+                    movement_id,
+                    session: Session::Strength(strength_session_id),
+                    datetime,
+                    sets,
+                    reps: reps.unwrap_or(0),
+                    tonnage: tonnage.unwrap_or(0.),
+                },
+            )
+            .collect();
+
+        // the metcon movements are ordered by metcon session so the movements of a session are
+        // adjacent
+        for metcon_movements in metcon_movements.chunk_by(|(a, _), (b, _)| a.0 == b.0) {
+            let score = &metcon_movements[0].0;
+            let counts: Vec<_> = metcon_movements
+                .iter()
+                .map(|(_, (_, count, _, _))| *count)
+                .collect();
+            let Some(sets_and_reps) = metcon_sets_and_reps(score, &counts) else {
+                continue;
+            };
+            for ((_, (movement_id, _, male_weight, female_weight)), (sets, reps)) in
+                metcon_movements.iter().zip(sets_and_reps)
+            {
+                let weight = match sex {
+                    Some(Sex::Female) => female_weight.or(*male_weight),
+                    _ => *male_weight,
+                };
+                session_volumes.push(SessionVolume {
+                    movement_id: *movement_id,
+                    session: Session::Metcon(score.0),
+                    datetime: score.1,
+                    sets,
+                    reps,
+                    tonnage: reps as f32 * weight.unwrap_or(0.),
+                });
+            }
+        }
+
+        Ok(session_volumes)
+    }
+
+    /// Returns the training volume of the user for every movement and period.
+    ///
+    /// The periods are calendar periods in `timezone`, which has to be a time zone name known to
+    /// Postgres. If it is `None`, the time zone of the user is used.
+    ///
+    /// For metcon movements the female weight is used if the sex of the user is
+    /// [`Female`](Sex::Female) and the movement has a female weight. Otherwise the male weight is
+    /// used.
+    pub async fn get_by_user(
+        user_id: UserId,
+        timespan: Timespan,
+        period: VolumePeriod,
+        timezone: Option<&str>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<TrainingVolume>> {
+        let session_volumes = Self::get_session_volumes(user_id, timespan, timezone, db).await?;
+
+        let mut volumes: BTreeMap<_, Volume> = BTreeMap::new();
+        for session_volume in &session_volumes {
+            volumes
+                .entry((
+                    session_volume.movement_id,
+                    period.start(session_volume.datetime.date()),
+                ))
+                .or_default()
+                .add(session_volume);
+        }
+
+        Ok(volumes
+            .into_iter()
+            .map(|((movement_id, period_start), volume)| TrainingVolume {
+                movement_id,
+                period_start,
+                sessions: volume.sessions.len() as i64,
+                sets: volume.sets,
+                reps: volume.reps,
+                tonnage: volume.tonnage,
+            })
+            .collect())
+    }
+
+    /// Returns the training volume of the user for every muscle group and period.
+    ///
+    /// The volume of a movement is added to all of its muscle groups. See
+    /// [`get_by_user`](TrainingVolumeDb::get_by_user) for the other parameters.
+    pub async fn get_by_muscle_group(
+        user_id: UserId,
+        timespan: Timespan,
+        period: VolumePeriod,
+        timezone: Option<&str>,
+        db: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<MuscleGroupVolume>> {
+        let session_volumes = Self::get_session_volumes(user_id, timespan, timezone, db).await?;
+
+        let movement_ids: HashSet<_> = session_volumes
+            .iter()
+            .map(|session_volume| session_volume.movement_id)
+            .collect();
+        let muscle_groups: HashMap<MovementId, Vec<MuscleGroup>> = movement::table
+            .filter(movement::columns::id.eq_any(movement_ids))
+            .select((movement::columns::id, movement::columns::muscle_groups))
+            .get_results(db)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut volumes: BTreeMap<_, Volume> = BTreeMap::new();
+        for session_volume in &session_volumes {
+            let period_start = period.start(session_volume.datetime.date());
+            for muscle_group in muscle_groups
+                .get(&session_volume.movement_id)
+                .into_iter()
+                .flatten()
+            {
+                volumes
+                    .entry((*muscle_group, period_start))
+                    .or_default()
+                    .add(session_volume);
+            }
+        }
+
+        Ok(volumes
+            .into_iter()
+            .map(|((muscle_group, period_start), volume)| MuscleGroupVolume {
+                muscle_group,
+                period_start,
+                sessions: volume.sessions.len() as i64,
+                sets: volume.sets,
+                reps: volume.reps,
+                tonnage: volume.tonnage,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use sport_log_types::{MetconSessionId, MetconType};
+
+    use super::metcon_sets_and_reps;
+
+    fn score(
+        metcon_type: MetconType,
+        metcon_rounds: Option<i32>,
+        time: Option<i32>,
+        rounds: Option<i32>,
+        reps: Option<i32>,
+        round_reps: Option<Vec<i32>>,
+    ) -> super::MetconSessionScore {
+        (
+            MetconSessionId(1),
+            NaiveDateTime::default(),
+            metcon_type,
+            metcon_rounds,
+            time,
+            rounds,
+            reps,
+            round_reps,
+        )
+    }
+
+    #[test]
+    fn metcon_sets_and_reps_of_rounds() {
+        // 2 rounds and 7 reps of 5 pull ups and 10 push ups
+        let amrap = score(MetconType::Amrap, None, None, Some(2), Some(7), None);
+        assert_eq!(
+            metcon_sets_and_reps(&amrap, &[5, 10]),
+            Some(vec![(3, 15), (3, 22)])
+        );
+
+        let for_time = score(MetconType::ForTime, Some(3), Some(600), None, None, None);
+        assert_eq!(
+            metcon_sets_and_reps(&for_time, &[5, 10]),
+            Some(vec![(3, 15), (3, 30)])
+        );
+
+        let chipper = score(MetconType::Chipper, None, None, None, Some(60), None);
+        assert_eq!(
+            metcon_sets_and_reps(&chipper, &[50, 50, 50]),
+            Some(vec![(1, 50), (1, 10), (0, 0)])
+        );
+    }
+
+    #[test]
+    fn metcon_sets_and_reps_of_increasing_rounds() {
+        // 4 minutes and 3 reps of the fifth minute
+        let death_by = score(MetconType::DeathBy, None, None, Some(4), Some(3), None);
+        assert_eq!(metcon_sets_and_reps(&death_by, &[1]), Some(vec![(5, 13)]));
+
+        let ladder = score(MetconType::Ladder, Some(3), Some(300), None, None, None);
+        assert_eq!(
+            metcon_sets_and_reps(&ladder, &[1, 2]),
+            Some(vec![(3, 6), (3, 12)])
+        );
+    }
+
+    #[test]
+    fn metcon_sets_and_reps_of_tabata() {
+        let tabata = score(
+            MetconType::Tabata,
+            Some(4),
+            None,
+            None,
+            Some(40),
+            Some(vec![12, 8, 11, 9]),
+        );
+        assert_eq!(
+            metcon_sets_and_reps(&tabata, &[1, 1]),
+            Some(vec![(2, 23), (2, 17)])
+        );
+
+        let tabata = score(MetconType::Tabata, Some(4), None, None, Some(40), None);
+        assert_eq!(metcon_sets_and_reps(&tabata, &[1]), Some(vec![(4, 40)]));
+        assert_eq!(metcon_sets_and_reps(&tabata, &[1, 1]), None);
+    }
+}
@@ -0,0 +1,532 @@
+//! Elevation correction of tracks using local DEM tiles.
+//!
+//! The tiles are read from the directory configured in [`Config::dem_dir`](crate::config::Config).
+//! Two formats are supported:
+//! - Uncompressed SRTM tiles in HGT format. The files must use the usual SRTM naming scheme like
+//!   `N47E011.hgt` for the tile whose south west corner is at 47°N 11°E. Both SRTM1 (3601 x 3601
+//!   samples) and SRTM3 (1201 x 1201 samples) tiles are supported.
+//! - Single band GeoTIFF files with the extension `.tif` or `.tiff` in geographic coordinates.
+//!   The area they cover is taken from their georeferencing, so they can be named arbitrarily.
+//!
+//! The directory is scanned when the first elevation correction is requested, so the server has to
+//! be restarted after tiles have been added. Loaded tiles are kept in the [`DemCache`].
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    ffi::OsStr,
+    fs::File,
+    io::{self, BufReader, Cursor, Read, Seek},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use axum::extract::FromRef;
+use sport_log_types::Position;
+use tiff::{
+    decoder::{ifd::Value, Decoder, DecodingResult},
+    tags::Tag,
+    ColorType, TiffError,
+};
+use tokio::{fs, task};
+
+use crate::state::AppState;
+
+/// The value of HGT samples for which no elevation data is available.
+const VOID: i16 = -32768;
+
+/// The maximal number of tiles that are cached.
+///
+/// SRTM1 tiles take up about 50MB each.
+const MAX_CACHE_TILES: usize = 16;
+
+/// The GeoKey for the type of the model coordinate system.
+const GT_MODEL_TYPE_GEO_KEY: u16 = 1024;
+/// The value of [`GT_MODEL_TYPE_GEO_KEY`] for geographic coordinates.
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+/// The GeoKey for the raster type.
+const GT_RASTER_TYPE_GEO_KEY: u16 = 1025;
+/// The value of [`GT_RASTER_TYPE_GEO_KEY`] if samples are located at the corner of a pixel.
+const RASTER_PIXEL_IS_POINT: u16 = 2;
+
+/// The location of the samples of a tile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Georeference {
+    /// The latitude of the first row of samples.
+    north: f64,
+    /// The longitude of the first column of samples.
+    west: f64,
+    /// The distance between two rows of samples in degrees.
+    lat_step: f64,
+    /// The distance between two columns of samples in degrees.
+    lon_step: f64,
+    width: usize,
+    height: usize,
+}
+
+impl Georeference {
+    /// Returns the fractional row and column of the sample at `lat` `lon` if it lies inside the
+    /// tile.
+    fn row_col(&self, lat: f64, lon: f64) -> Option<(f64, f64)> {
+        let row = (self.north - lat) / self.lat_step;
+        let col = (lon - self.west) / self.lon_step;
+        (row >= 0.
+            && col >= 0.
+            && row <= (self.height - 1) as f64
+            && col <= (self.width - 1) as f64)
+            .then_some((row, col))
+    }
+
+    /// Returns the georeference of the HGT tile with the given filename and size in bytes.
+    ///
+    /// If the filename does not follow the SRTM naming scheme, [`None`] is returned.
+    fn from_hgt(filename: &str, len: u64) -> io::Result<Option<Self>> {
+        let Some(name) = filename
+            .strip_suffix(".hgt")
+            .filter(|name| name.len() == 7 && name.is_ascii())
+        else {
+            return Ok(None);
+        };
+        let lat = match (&name[0..1], name[1..3].parse::<i32>()) {
+            ("N", Ok(lat)) => lat,
+            ("S", Ok(lat)) => -lat,
+            _ => return Ok(None),
+        };
+        let lon = match (&name[3..4], name[4..7].parse::<i32>()) {
+            ("E", Ok(lon)) => lon,
+            ("W", Ok(lon)) => -lon,
+            _ => return Ok(None),
+        };
+
+        let size = match len {
+            25_934_402 => 3601, // 3601 * 3601 * 2
+            2_884_802 => 1201,  // 1201 * 1201 * 2
+            _ => return Err(invalid_data("hgt file has an invalid size")),
+        };
+
+        Ok(Some(Georeference {
+            north: f64::from(lat + 1),
+            west: f64::from(lon),
+            lat_step: 1. / (size - 1) as f64,
+            lon_step: 1. / (size - 1) as f64,
+            width: size,
+            height: size,
+        }))
+    }
+
+    /// Returns the georeference of the GeoTIFF image of the decoder.
+    fn from_geotiff<R: Read + Seek>(decoder: &mut Decoder<R>) -> io::Result<Self> {
+        let (width, height) = decoder.dimensions().map_err(tiff_error)?;
+        let scale = decoder
+            .get_tag_f64_vec(Tag::ModelPixelScaleTag)
+            .map_err(tiff_error)?;
+        let tiepoint = decoder
+            .get_tag_f64_vec(Tag::ModelTiepointTag)
+            .map_err(tiff_error)?;
+        let (&[lon_step, lat_step, ..], &[col, row, _, lon, lat, ..]) =
+            (scale.as_slice(), tiepoint.as_slice())
+        else {
+            return Err(invalid_data("the georeferencing of the GeoTIFF is invalid"));
+        };
+
+        // the geo keys are stored as a header of four values followed by four values per key
+        let geo_keys = decoder
+            .find_tag_unsigned_vec::<u16>(Tag::GeoKeyDirectoryTag)
+            .map_err(tiff_error)?
+            .unwrap_or_default();
+        let geo_key = |key: u16| {
+            geo_keys
+                .chunks_exact(4)
+                .skip(1)
+                .find(|entry| entry[0] == key && entry[1] == 0)
+                .map(|entry| entry[3])
+        };
+        if geo_key(GT_MODEL_TYPE_GEO_KEY).is_some_and(|model| model != MODEL_TYPE_GEOGRAPHIC) {
+            return Err(invalid_data(
+                "the GeoTIFF does not use geographic coordinates",
+            ));
+        }
+        // by default a sample represents the area of a pixel and is located at its center
+        let offset = if geo_key(GT_RASTER_TYPE_GEO_KEY) == Some(RASTER_PIXEL_IS_POINT) {
+            0.
+        } else {
+            0.5
+        };
+
+        Ok(Georeference {
+            north: lat - (offset - row) * lat_step,
+            west: lon + (offset - col) * lon_step,
+            lat_step,
+            lon_step,
+            width: width as usize,
+            height: height as usize,
+        })
+    }
+}
+
+/// A grid of elevation samples in meter.
+///
+/// Samples for which no elevation data is available are NaN.
+struct Tile {
+    georeference: Georeference,
+    samples: Vec<f32>,
+}
+
+impl Tile {
+    fn from_hgt(georeference: Georeference, bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() != georeference.width * georeference.height * 2 {
+            return Err(invalid_data("hgt file has an invalid size"));
+        }
+        let samples = bytes
+            .chunks_exact(2)
+            .map(|sample| match i16::from_be_bytes([sample[0], sample[1]]) {
+                VOID => f32::NAN,
+                sample => f32::from(sample),
+            })
+            .collect();
+
+        Ok(Tile {
+            georeference,
+            samples,
+        })
+    }
+
+    fn from_geotiff<R: Read + Seek>(reader: R) -> io::Result<Self> {
+        let mut decoder = Decoder::new(reader).map_err(tiff_error)?;
+        if !matches!(decoder.colortype().map_err(tiff_error)?, ColorType::Gray(_)) {
+            return Err(invalid_data("the GeoTIFF has more than one band"));
+        }
+        let georeference = Georeference::from_geotiff(&mut decoder)?;
+        let no_data = match decoder.find_tag(Tag::GdalNodata).map_err(tiff_error)? {
+            Some(Value::Ascii(no_data)) => no_data.trim_matches(char::from(0)).trim().parse().ok(),
+            _ => None,
+        };
+
+        let samples: Vec<f64> = match decoder.read_image().map_err(tiff_error)? {
+            DecodingResult::U8(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::U16(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::U32(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::U64(samples) => samples.into_iter().map(|s| s as f64).collect(),
+            DecodingResult::I8(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::I16(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::I32(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::I64(samples) => samples.into_iter().map(|s| s as f64).collect(),
+            DecodingResult::F32(samples) => samples.into_iter().map(f64::from).collect(),
+            DecodingResult::F64(samples) => samples,
+        };
+        if samples.len() != georeference.width * georeference.height {
+            return Err(invalid_data("the GeoTIFF has an invalid number of samples"));
+        }
+
+        let samples = samples
+            .into_iter()
+            .map(|sample| {
+                if Some(sample) == no_data {
+                    f32::NAN
+                } else {
+                    sample as f32
+                }
+            })
+            .collect();
+
+        Ok(Tile {
+            georeference,
+            samples,
+        })
+    }
+
+    /// Returns the bilinear interpolated elevation at `lat` `lon` in meter.
+    fn elevation(&self, lat: f64, lon: f64) -> Option<f64> {
+        let Georeference { width, height, .. } = self.georeference;
+        let (row, col) = self.georeference.row_col(lat, lon)?;
+
+        let (row0, col0) = (row.floor() as usize, col.floor() as usize);
+        let (row1, col1) = ((row0 + 1).min(height - 1), (col0 + 1).min(width - 1));
+        let (row_fract, col_fract) = (row - row.floor(), col - col.floor());
+
+        let mut elevation = 0.;
+        for (row, row_weight) in [(row0, 1. - row_fract), (row1, row_fract)] {
+            for (col, col_weight) in [(col0, 1. - col_fract), (col1, col_fract)] {
+                // samples without weight may be void
+                let weight = row_weight * col_weight;
+                if weight > 0. {
+                    let sample = self.samples[row * width + col];
+                    if sample.is_nan() {
+                        return None;
+                    }
+                    elevation += f64::from(sample) * weight;
+                }
+            }
+        }
+        Some(elevation)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TileFormat {
+    Hgt,
+    GeoTiff,
+}
+
+/// A tile in the DEM directory.
+#[derive(Debug)]
+struct TileFile {
+    path: PathBuf,
+    format: TileFormat,
+    georeference: Georeference,
+}
+
+impl TileFile {
+    async fn load(&self) -> io::Result<Tile> {
+        let bytes = fs::read(&self.path).await?;
+        match self.format {
+            TileFormat::Hgt => Tile::from_hgt(self.georeference, &bytes),
+            TileFormat::GeoTiff => {
+                task::spawn_blocking(move || Tile::from_geotiff(Cursor::new(bytes)))
+                    .await
+                    .map_err(io::Error::other)?
+            }
+        }
+        .map_err(with_path(&self.path))
+    }
+}
+
+/// Returns all tiles in `dem_dir`.
+fn scan_dem_dir(dem_dir: &Path) -> io::Result<Vec<TileFile>> {
+    let mut tiles = vec![];
+    for entry in std::fs::read_dir(dem_dir)? {
+        let path = entry?.path();
+        let extension = path.extension().and_then(OsStr::to_str);
+        let tile = match extension {
+            Some("hgt") => {
+                let filename = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+                Georeference::from_hgt(filename, path.metadata()?.len())
+                    .map_err(with_path(&path))?
+                    .map(|georeference| (TileFormat::Hgt, georeference))
+            }
+            Some("tif" | "tiff") => {
+                let mut decoder = Decoder::new(BufReader::new(File::open(&path)?))
+                    .map_err(tiff_error)
+                    .map_err(with_path(&path))?;
+                let georeference =
+                    Georeference::from_geotiff(&mut decoder).map_err(with_path(&path))?;
+                Some((TileFormat::GeoTiff, georeference))
+            }
+            _ => None,
+        };
+        if let Some((format, georeference)) = tile {
+            tiles.push(TileFile {
+                path,
+                format,
+                georeference,
+            });
+        }
+    }
+    Ok(tiles)
+}
+
+fn invalid_data(error: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn tiff_error(error: TiffError) -> io::Error {
+    match error {
+        TiffError::IoError(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    }
+}
+
+/// Returns a function that adds `path` to the message of an error.
+fn with_path(path: &Path) -> impl Fn(io::Error) -> io::Error + '_ {
+    move |error| io::Error::new(error.kind(), format!("{}: {error}", path.display()))
+}
+
+#[derive(Default)]
+struct DemCacheEntries {
+    tile_files: Option<Arc<Vec<TileFile>>>,
+    tiles: HashMap<PathBuf, Arc<Tile>>,
+}
+
+/// A cache for the index of the DEM directory and loaded tiles.
+#[derive(Clone, Default)]
+pub struct DemCache(Arc<Mutex<DemCacheEntries>>);
+
+impl DemCache {
+    async fn tile_files(&self, dem_dir: &Path) -> io::Result<Arc<Vec<TileFile>>> {
+        if let Some(tile_files) = &self.0.lock().unwrap().tile_files {
+            return Ok(tile_files.clone());
+        }
+
+        let dem_dir = dem_dir.to_owned();
+        let tile_files = Arc::new(
+            task::spawn_blocking(move || scan_dem_dir(&dem_dir))
+                .await
+                .map_err(io::Error::other)??,
+        );
+        self.0.lock().unwrap().tile_files = Some(tile_files.clone());
+        Ok(tile_files)
+    }
+
+    async fn tile(&self, tile_file: &TileFile) -> io::Result<Arc<Tile>> {
+        if let Some(tile) = self.0.lock().unwrap().tiles.get(&tile_file.path) {
+            return Ok(tile.clone());
+        }
+
+        let tile = Arc::new(tile_file.load().await?);
+        let mut cache = self.0.lock().unwrap();
+        if cache.tiles.len() >= MAX_CACHE_TILES {
+            cache.tiles.clear();
+        }
+        cache.tiles.insert(tile_file.path.clone(), tile.clone());
+        Ok(tile)
+    }
+}
+
+impl FromRef<AppState> for DemCache {
+    fn from_ref(state: &AppState) -> Self {
+        state.dem_cache.clone()
+    }
+}
+
+/// Replaces the elevation of all positions of `track` by the elevation from the tiles in `dem_dir`.
+///
+/// Positions for which no elevation data is available keep their elevation.
+///
+/// Returns the number of corrected positions.
+pub async fn correct_elevation(
+    dem_dir: &Path,
+    dem_cache: &DemCache,
+    track: &mut [Position],
+) -> io::Result<usize> {
+    let tile_files = dem_cache.tile_files(dem_dir).await?;
+    let mut tiles: HashMap<usize, Arc<Tile>> = HashMap::new();
+    let mut corrected = 0;
+
+    for pos in track {
+        let Some(index) = tile_files.iter().position(|tile_file| {
+            tile_file
+                .georeference
+                .row_col(pos.latitude, pos.longitude)
+                .is_some()
+        }) else {
+            continue;
+        };
+        let tile = match tiles.entry(index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(dem_cache.tile(&tile_files[index]).await?),
+        };
+
+        if let Some(elevation) = tile.elevation(pos.latitude, pos.longitude) {
+            pos.elevation = elevation;
+            corrected += 1;
+        }
+    }
+
+    Ok(corrected)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tiff::{encoder::colortype::Gray32Float, encoder::TiffEncoder, tags::Tag};
+
+    use super::{Georeference, Tile};
+
+    #[test]
+    fn hgt_georeference() {
+        let georeference = Georeference::from_hgt("N47E011.hgt", 2_884_802)
+            .unwrap()
+            .unwrap();
+        assert_eq!(georeference.north, 48.);
+        assert_eq!(georeference.west, 11.);
+        assert_eq!(georeference.width, 1201);
+        assert_eq!(georeference.lat_step, 1. / 1200.);
+
+        let georeference = Georeference::from_hgt("S01W002.hgt", 25_934_402)
+            .unwrap()
+            .unwrap();
+        assert_eq!(georeference.north, 0.);
+        assert_eq!(georeference.west, -2.);
+        assert_eq!(georeference.width, 3601);
+
+        assert!(Georeference::from_hgt("N47E011.hgt", 1000).is_err());
+        assert!(Georeference::from_hgt("tile.hgt", 2_884_802)
+            .unwrap()
+            .is_none());
+        assert!(Georeference::from_hgt("X47E011.hgt", 2_884_802)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn hgt_tile() {
+        let georeference = Georeference {
+            north: 1.,
+            west: 0.,
+            lat_step: 1.,
+            lon_step: 1.,
+            width: 2,
+            height: 2,
+        };
+        // north west, north east, south west, south east
+        let bytes: Vec<u8> = [100i16, 200, 300, super::VOID]
+            .into_iter()
+            .flat_map(i16::to_be_bytes)
+            .collect();
+        let tile = Tile::from_hgt(georeference, &bytes).unwrap();
+
+        assert_eq!(tile.elevation(1., 0.), Some(100.));
+        assert_eq!(tile.elevation(1., 0.5), Some(150.));
+        assert_eq!(tile.elevation(0.5, 0.), Some(200.));
+        // the interpolation needs the void sample
+        assert_eq!(tile.elevation(0.5, 0.5), None);
+        assert_eq!(tile.elevation(1.5, 0.), None);
+
+        assert!(Tile::from_hgt(georeference, &bytes[..6]).is_err());
+    }
+
+    #[test]
+    fn geotiff_tile() {
+        let mut bytes = Cursor::new(vec![]);
+        let mut encoder = TiffEncoder::new(&mut bytes).unwrap();
+        let mut image = encoder.new_image::<Gray32Float>(3, 2).unwrap();
+        // pixels of 0.5 x 0.25 degrees whose north west corner is at 47°N 11°E
+        image
+            .encoder()
+            .write_tag(Tag::ModelPixelScaleTag, &[0.5, 0.25, 0.][..])
+            .unwrap();
+        image
+            .encoder()
+            .write_tag(Tag::ModelTiepointTag, &[0., 0., 0., 11., 47., 0.][..])
+            .unwrap();
+        image
+            .encoder()
+            .write_tag(
+                Tag::GeoKeyDirectoryTag,
+                &[1u16, 1, 0, 2, 1024, 0, 1, 2, 1025, 0, 1, 1][..],
+            )
+            .unwrap();
+        image.encoder().write_tag(Tag::GdalNodata, "-9999").unwrap();
+        image
+            .write_data(&[100., 200., 300., 400., 500., -9999.])
+            .unwrap();
+        bytes.set_position(0);
+
+        let tile = Tile::from_geotiff(bytes).unwrap();
+        assert_eq!(
+            tile.georeference,
+            Georeference {
+                north: 46.875,
+                west: 11.25,
+                lat_step: 0.25,
+                lon_step: 0.5,
+                width: 3,
+                height: 2,
+            }
+        );
+        assert_eq!(tile.elevation(46.875, 11.25), Some(100.));
+        assert_eq!(tile.elevation(46.75, 11.5), Some(300.));
+        assert_eq!(tile.elevation(46.625, 12.25), None);
+        assert_eq!(tile.elevation(47., 11.25), None);
+    }
+}
@@ -21,14 +21,33 @@ pub enum ErrorMessage {
     PrimaryKeyViolation { table: String },
     ForeignKeyViolation { table: String, column: String },
     UniqueViolation { table: String, columns: Vec<String> },
+    Validation { errors: Vec<FieldError> },
     Other { error: String },
 }
 
+/// A violated invariant of the field `field` of the entity at position `index` in the request.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FieldError {
+    pub index: usize,
+    pub field: String,
+    pub error: String,
+}
+
+impl FieldError {
+    pub fn new(index: usize, field: &str, error: &str) -> Self {
+        FieldError {
+            index,
+            field: field.to_owned(),
+            error: error.to_owned(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct HandlerError {
     status: StatusCode,
     message: Option<ErrorMessage>,
-    headers: Option<HeaderMap>,
+    headers: Option<Box<HeaderMap>>,
 }
 
 impl Serialize for HandlerError {
@@ -65,6 +84,16 @@ impl From<(StatusCode, ErrorMessage)> for HandlerError {
     }
 }
 
+impl From<Vec<FieldError>> for HandlerError {
+    fn from(errors: Vec<FieldError>) -> Self {
+        HandlerError {
+            status: StatusCode::BAD_REQUEST,
+            message: Some(ErrorMessage::Validation { errors }),
+            headers: None,
+        }
+    }
+}
+
 impl From<TypedHeaderRejection> for HandlerError {
     fn from(rejection: TypedHeaderRejection) -> Self {
         match rejection.reason() {
@@ -74,9 +103,11 @@ impl From<TypedHeaderRejection> for HandlerError {
                     error: format!("header {} missing", rejection.name()),
                 }),
                 headers: (rejection.name() == AUTHORIZATION).then(|| {
-                    [(WWW_AUTHENTICATE, HeaderValue::from_static("Basic"))]
-                        .into_iter()
-                        .collect()
+                    Box::new(
+                        [(WWW_AUTHENTICATE, HeaderValue::from_static("Basic"))]
+                            .into_iter()
+                            .collect(),
+                    )
                 }),
             },
             TypedHeaderRejectionReason::Error(error) => HandlerError {
@@ -118,7 +149,7 @@ impl From<DieselError> for HandlerError {
                     message: Some({
                         let table = db_error_info.table_name().unwrap_or("<unknown>").to_owned();
                         let constraint = db_error_info.constraint_name();
-                        if constraint.map_or(false, |constraint| constraint.ends_with("_pkey")) {
+                        if constraint.is_some_and(|constraint| constraint.ends_with("_pkey")) {
                             ErrorMessage::PrimaryKeyViolation { table }
                         } else {
                             let columns = constraint
@@ -190,7 +221,7 @@ impl IntoResponse for HandlerError {
             info!("{message:?}");
         }
         if let Some(header) = &self.headers {
-            (self.status, header.to_owned(), Json(self)).into_response()
+            (self.status, header.as_ref().to_owned(), Json(self)).into_response()
         } else {
             (self.status, Json(self)).into_response()
         }
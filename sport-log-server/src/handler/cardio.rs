@@ -1,13 +1,202 @@
-use axum::{extract::Query, Json};
-use sport_log_types::{CardioSession, CardioSessionId, EpochResponse, Route, RouteId};
+use std::slice;
+
+use axum::{
+    extract::{Multipart, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use diesel::QueryResult;
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection};
+use rand_core::{OsRng, RngCore};
+use serde::Deserialize;
+use sport_log_types::{
+    activity::Lap,
+    fit::parse_fit,
+    geo::{ascent_descent, remove_privacy_zones, split_at_privacy_zones},
+    gpx::{parse_gpx, Gpx},
+    tcx::{to_tcx, TcxSport},
+    CardioLap, CardioLapId, CardioSession, CardioSessionId, CardioSessionShare, CardioType,
+    EpochResponse, Gear, GearId, GearStats, MovementId, MultisportLeg, MultisportLegId,
+    MultisportSession, MultisportSessionId, MultisportSessionTotals, Position, PrivacyZone,
+    PrivacyZoneId, Route, RouteId, RouteSegment, RouteSegmentId, RouteSession, SegmentEfforts,
+    UserId,
+};
 
 use crate::{
-    auth::AuthUserOrAP,
+    auth::{AuthUser, AuthUserOrAP},
+    config::Config,
     db::*,
-    handler::{HandlerResult, IdOption, TimeSpanOption, UnverifiedSingleOrVec},
+    elevation::{correct_elevation, DemCache},
+    handler::{
+        check_optional_references, check_references, ErrorMessage, HandlerError, HandlerResult,
+        IdOption, TimeSpanOption, UnverifiedSingleOrVec,
+    },
+    heatmap::{render_heatmap, HeatmapCache, HeatmapKey, PixelArea, MAX_SIZE, MAX_ZOOM},
     state::DbConn,
 };
 
+/// The length limits of the name of a [`Route`] (see the `route` table).
+const MIN_ROUTE_NAME_LEN: usize = 2;
+const MAX_ROUTE_NAME_LEN: usize = 80;
+
+#[derive(Debug, Deserialize)]
+pub struct RouteIdQuery {
+    route_id: UnverifiedId<RouteId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CardioSessionIdQuery {
+    cardio_session_id: UnverifiedId<CardioSessionId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MultisportSessionIdQuery {
+    multisport_session_id: UnverifiedId<MultisportSessionId>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CardioSessionImportQuery {
+    movement_id: UnverifiedId<MovementId>,
+    cardio_type: CardioType,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RouteImportQuery {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HeatmapQuery {
+    zoom: u8,
+    min_latitude: f64,
+    min_longitude: f64,
+    max_latitude: f64,
+    max_longitude: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HeatmapTileQuery {
+    zoom: u8,
+    x: u32,
+    y: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Gpx,
+    Tcx,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportFormatOption {
+    format: Option<ExportFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RouteSegmentIdOption {
+    route_segment_id: Option<UnverifiedId<RouteSegmentId>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShareTokenQuery {
+    token: String,
+}
+
+/// Returns the privacy zones that have to be removed from the tracks returned by the request.
+///
+/// The privacy zones of the user are only applied if the request is made by an action provider.
+/// They are always applied to shared cardio sessions (see [`get_shared_cardio_session`]).
+async fn get_applicable_privacy_zones(
+    auth: AuthUserOrAP,
+    db: &mut AsyncPgConnection,
+) -> QueryResult<Vec<PrivacyZone>> {
+    match auth.ap_id() {
+        Some(_) => PrivacyZoneDb::get_by_user(*auth, db).await,
+        None => Ok(vec![]),
+    }
+}
+
+/// Restores the positions inside of privacy zones of a track that is sent back by an action
+/// provider.
+///
+/// Action providers only get tracks without the positions inside of privacy zones. If such a
+/// track is sent back unchanged, the stored track `old_track` is kept. Other changes of tracks
+/// with positions inside of privacy zones are rejected because they would destroy these positions.
+fn restore_privacy_zones(
+    track: &mut Option<Vec<Position>>,
+    old_track: Option<&[Position]>,
+    privacy_zones: &[PrivacyZone],
+) -> HandlerResult<()> {
+    let Some(old_track) = old_track else {
+        return Ok(());
+    };
+    let mut trimmed_track = old_track.to_vec();
+    remove_privacy_zones(&mut trimmed_track, privacy_zones);
+    if trimmed_track.len() == old_track.len() {
+        // the action provider got the whole track
+        return Ok(());
+    }
+
+    if track.as_ref() == Some(&trimmed_track) {
+        *track = Some(old_track.to_vec());
+        Ok(())
+    } else {
+        Err(HandlerError::from((
+            StatusCode::FORBIDDEN,
+            ErrorMessage::Other {
+                error: "action providers can not change tracks with positions inside of privacy \
+                        zones"
+                    .to_owned(),
+            },
+        )))
+    }
+}
+
+fn check_export_id(format: Option<ExportFormat>, has_id: bool) -> HandlerResult<()> {
+    if format.is_some() && !has_id {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "an id is required for exports".to_owned(),
+            },
+        )));
+    }
+    Ok(())
+}
+
+fn export_response(content_type: &str, filename: &str, file: String) -> Response {
+    let content_disposition = format!("attachment; filename=\"{filename}\"");
+    (
+        [
+            (CONTENT_TYPE, content_type),
+            (CONTENT_DISPOSITION, content_disposition.as_str()),
+        ],
+        file,
+    )
+        .into_response()
+}
+
+/// Guesses the TCX sport from the name of the movement.
+fn tcx_sport(movement_name: &str) -> TcxSport {
+    let movement_name = movement_name.to_lowercase();
+    if movement_name.contains("run") {
+        TcxSport::Running
+    } else if ["bik", "cycl", "rid"]
+        .iter()
+        .any(|name| movement_name.contains(name))
+    {
+        TcxSport::Biking
+    } else {
+        TcxSport::Other
+    }
+}
+
 pub async fn create_routes(
     auth: AuthUserOrAP,
     mut db: DbConn,
@@ -30,17 +219,67 @@ pub async fn create_routes(
 pub async fn get_routes(
     auth: AuthUserOrAP,
     Query(IdOption { id }): Query<IdOption<UnverifiedId<RouteId>>>,
+    Query(ExportFormatOption { format }): Query<ExportFormatOption>,
     mut db: DbConn,
-) -> HandlerResult<Json<Vec<Route>>> {
-    match id {
+) -> HandlerResult<Response> {
+    check_export_id(format, id.is_some())?;
+
+    let mut routes = match id {
         Some(id) => {
             let route_id = id.verify_user_ap_get(auth, &mut db).await?;
             RouteDb::get_by_id(route_id, &mut db).await.map(|r| vec![r])
         }
         None => RouteDb::get_by_user(*auth, &mut db).await,
+    }?;
+
+    let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+    if !privacy_zones.is_empty() {
+        for route in &mut routes {
+            for track in [&mut route.track, &mut route.marked_positions]
+                .into_iter()
+                .flatten()
+            {
+                remove_privacy_zones(track, &privacy_zones);
+            }
+        }
     }
-    .map(Json)
-    .map_err(Into::into)
+
+    let Some(format) = format else {
+        return Ok(Json(routes).into_response());
+    };
+    // the id is required for exports, so there is exactly one route
+    let route = routes.remove(0);
+    match format {
+        ExportFormat::Gpx => {
+            let filename = format!("route_{}.gpx", route.id.0);
+            Ok(export_response(
+                "application/gpx+xml",
+                &filename,
+                Gpx::from(route).to_xml(),
+            ))
+        }
+        ExportFormat::Tcx => Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "routes can not be exported as tcx".to_owned(),
+            },
+        ))),
+    }
+}
+
+/// Restores the positions inside of privacy zones of a route that is sent back by an action provider.
+async fn restore_route_privacy_zones(
+    route: &mut Route,
+    privacy_zones: &[PrivacyZone],
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let old_route = RouteDb::get_by_id(route.id, db).await?;
+    restore_privacy_zones(&mut route.track, old_route.track.as_deref(), privacy_zones)?;
+    restore_privacy_zones(
+        &mut route.marked_positions,
+        old_route.marked_positions.as_deref(),
+        privacy_zones,
+    )
 }
 
 pub async fn update_routes(
@@ -50,11 +289,23 @@ pub async fn update_routes(
 ) -> HandlerResult<Json<EpochResponse>> {
     match routes {
         UnverifiedSingleOrVec::Single(route) => {
-            let route = route.verify_user_ap_update(auth, &mut db).await?;
+            let mut route = route.verify_user_ap_update(auth, &mut db).await?;
+            let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+            if !privacy_zones.is_empty() {
+                restore_route_privacy_zones(&mut route, &privacy_zones, &mut db).await?;
+            }
+            RouteSegmentDb::delete_by_deleted_routes(slice::from_ref(&route), &mut db).await?;
             RouteDb::update(&route, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(routes) => {
-            let routes = routes.verify_user_ap_update(auth, &mut db).await?;
+            let mut routes = routes.verify_user_ap_update(auth, &mut db).await?;
+            let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+            if !privacy_zones.is_empty() {
+                for route in &mut routes {
+                    restore_route_privacy_zones(route, &privacy_zones, &mut db).await?;
+                }
+            }
+            RouteSegmentDb::delete_by_deleted_routes(&routes, &mut db).await?;
             RouteDb::update_multiple(&routes, &mut db).await?;
         }
     }
@@ -62,19 +313,304 @@ pub async fn update_routes(
     Ok(Json(EpochResponse { epoch }))
 }
 
+pub async fn get_route_sessions(
+    auth: AuthUserOrAP,
+    Query(RouteIdQuery { route_id }): Query<RouteIdQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<RouteSession>>> {
+    let route_id = route_id.verify_user_ap_get(auth, &mut db).await?;
+    CardioSessionDb::get_by_user_and_route(*auth, route_id, &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
+
+/// Checks that the routes of the route segments belong to the user.
+async fn check_route_segments(
+    route_segments: &[RouteSegment],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let route_ids: Vec<_> = route_segments
+        .iter()
+        .map(|route_segment| route_segment.route_id)
+        .collect();
+    check_references::<RouteDb>(&route_ids, user_id, db).await
+}
+
+pub async fn create_route_segments(
+    auth: AuthUserOrAP,
+    State(config): State<&Config>,
+    mut db: DbConn,
+    Json(route_segments): Json<UnverifiedSingleOrVec<RouteSegment>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    let tolerance = config.segment_tolerance;
+    match route_segments {
+        UnverifiedSingleOrVec::Single(route_segment) => {
+            let route_segment = route_segment.verify_user_ap_create(auth)?;
+            check_route_segments(slice::from_ref(&route_segment), *auth, &mut db).await?;
+            db.transaction(|db| {
+                async move {
+                    RouteSegmentDb::create(&route_segment, db).await?;
+                    SegmentEffortDb::update_by_route_segment(&route_segment, tolerance, db).await
+                }
+                .scope_boxed()
+            })
+            .await?;
+        }
+        UnverifiedSingleOrVec::Vec(route_segments) => {
+            let route_segments = route_segments.verify_user_ap_create(auth)?;
+            check_route_segments(&route_segments, *auth, &mut db).await?;
+            db.transaction(|db| {
+                async move {
+                    RouteSegmentDb::create_multiple(&route_segments, db).await?;
+                    for route_segment in &route_segments {
+                        SegmentEffortDb::update_by_route_segment(route_segment, tolerance, db)
+                            .await?;
+                    }
+                    QueryResult::Ok(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+        }
+    }
+    let epoch = RouteSegmentDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_route_segments(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<RouteSegmentId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<RouteSegment>>> {
+    let mut route_segments = match id {
+        Some(id) => {
+            let route_segment_id = id.verify_user_ap_get(auth, &mut db).await?;
+            RouteSegmentDb::get_by_id(route_segment_id, &mut db)
+                .await
+                .map(|r| vec![r])
+        }
+        None => RouteSegmentDb::get_by_user(*auth, &mut db).await,
+    }?;
+
+    let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+    if !privacy_zones.is_empty() {
+        for route_segment in &mut route_segments {
+            remove_privacy_zones(&mut route_segment.track, &privacy_zones);
+        }
+    }
+
+    Ok(Json(route_segments))
+}
+
+/// Restores the positions inside of privacy zones of a route segment that is sent back by an
+/// action provider.
+async fn restore_route_segment_privacy_zones(
+    route_segment: &mut RouteSegment,
+    privacy_zones: &[PrivacyZone],
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let old_route_segment = RouteSegmentDb::get_by_id(route_segment.id, db).await?;
+    let mut track = Some(std::mem::take(&mut route_segment.track));
+    restore_privacy_zones(&mut track, Some(&old_route_segment.track), privacy_zones)?;
+    route_segment.track = track.unwrap_or_default();
+    Ok(())
+}
+
+pub async fn update_route_segments(
+    auth: AuthUserOrAP,
+    State(config): State<&Config>,
+    mut db: DbConn,
+    Json(route_segments): Json<UnverifiedSingleOrVec<RouteSegment>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    let tolerance = config.segment_tolerance;
+    match route_segments {
+        UnverifiedSingleOrVec::Single(route_segment) => {
+            let mut route_segment = route_segment.verify_user_ap_update(auth, &mut db).await?;
+            check_route_segments(slice::from_ref(&route_segment), *auth, &mut db).await?;
+            let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+            if !privacy_zones.is_empty() {
+                restore_route_segment_privacy_zones(&mut route_segment, &privacy_zones, &mut db)
+                    .await?;
+            }
+            db.transaction(|db| {
+                async move {
+                    RouteSegmentDb::update(&route_segment, db).await?;
+                    SegmentEffortDb::update_by_route_segment(&route_segment, tolerance, db).await
+                }
+                .scope_boxed()
+            })
+            .await?;
+        }
+        UnverifiedSingleOrVec::Vec(route_segments) => {
+            let mut route_segments = route_segments.verify_user_ap_update(auth, &mut db).await?;
+            check_route_segments(&route_segments, *auth, &mut db).await?;
+            let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+            if !privacy_zones.is_empty() {
+                for route_segment in &mut route_segments {
+                    restore_route_segment_privacy_zones(route_segment, &privacy_zones, &mut db)
+                        .await?;
+                }
+            }
+            db.transaction(|db| {
+                async move {
+                    RouteSegmentDb::update_multiple(&route_segments, db).await?;
+                    for route_segment in &route_segments {
+                        SegmentEffortDb::update_by_route_segment(route_segment, tolerance, db)
+                            .await?;
+                    }
+                    QueryResult::Ok(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+        }
+    }
+    let epoch = RouteSegmentDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_segment_efforts(
+    auth: AuthUserOrAP,
+    Query(RouteSegmentIdOption { route_segment_id }): Query<RouteSegmentIdOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<SegmentEfforts>>> {
+    let route_segment_id = match route_segment_id {
+        Some(route_segment_id) => Some(route_segment_id.verify_user_ap_get(auth, &mut db).await?),
+        None => None,
+    };
+    SegmentEffortDb::get_best_and_latest_by_user(*auth, route_segment_id, &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
+
+/// Assigns the best matching [`Route`] to the cardio session if it has a track but no route yet.
+async fn assign_matching_route(
+    cardio_session: &mut CardioSession,
+    config: &Config,
+    db: &mut AsyncPgConnection,
+) -> QueryResult<()> {
+    if let (Some(tolerance), None, Some(track)) = (
+        config.route_matching_tolerance,
+        cardio_session.route_id,
+        &cardio_session.track,
+    ) {
+        cardio_session.route_id =
+            RouteDb::get_matching_route(cardio_session.user_id, track, tolerance, db).await?;
+    }
+    Ok(())
+}
+
+/// Assigns the best matching [`Route`] to the cardio session if its track was changed but its
+/// route was not.
+///
+/// `old_cardio_session` is the cardio session as it is currently stored.
+async fn rematch_route(
+    cardio_session: &mut CardioSession,
+    old_cardio_session: &CardioSession,
+    config: &Config,
+    db: &mut AsyncPgConnection,
+) -> QueryResult<()> {
+    if let (Some(tolerance), Some(track)) = (config.route_matching_tolerance, &cardio_session.track)
+    {
+        if cardio_session.track != old_cardio_session.track
+            && cardio_session.route_id == old_cardio_session.route_id
+        {
+            cardio_session.route_id =
+                RouteDb::get_matching_route(cardio_session.user_id, track, tolerance, db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Discards the elevation correction of the cardio session if its track was changed.
+///
+/// The uncorrected elevation, ascent and descent belong to the old track and can not be restored
+/// any more.
+///
+/// `old_cardio_session` is the cardio session as it is currently stored.
+async fn discard_elevation_correction(
+    cardio_session: &mut CardioSession,
+    old_cardio_session: &CardioSession,
+    db: &mut AsyncPgConnection,
+) -> QueryResult<()> {
+    if cardio_session.track != old_cardio_session.track {
+        cardio_session.uncorrected_ascent = None;
+        cardio_session.uncorrected_descent = None;
+        CardioSessionDb::set_uncorrected_elevation(cardio_session.id, None, db).await?;
+    }
+    Ok(())
+}
+
+/// Assigns the default [`Gear`] of the movement to the cardio session if it has no gear yet.
+async fn assign_default_gear(
+    cardio_session: &mut CardioSession,
+    db: &mut AsyncPgConnection,
+) -> QueryResult<()> {
+    if cardio_session.gear_id.is_none() {
+        cardio_session.gear_id =
+            GearDb::get_default_by_movement(cardio_session.user_id, cardio_session.movement_id, db)
+                .await?;
+    }
+    Ok(())
+}
+
+/// Checks that the gear and the planned workouts of the cardio sessions belong to the user.
+async fn check_cardio_sessions(
+    cardio_sessions: &[CardioSession],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let gear_ids: Vec<_> = cardio_sessions
+        .iter()
+        .filter_map(|cardio_session| cardio_session.gear_id)
+        .collect();
+    check_references::<GearDb>(&gear_ids, user_id, db).await?;
+    let planned_workout_ids: Vec<_> = cardio_sessions
+        .iter()
+        .filter_map(|cardio_session| cardio_session.planned_workout_id)
+        .collect();
+    check_references::<PlannedWorkoutDb>(&planned_workout_ids, user_id, db).await
+}
+
 pub async fn create_cardio_sessions(
     auth: AuthUserOrAP,
+    State(config): State<&Config>,
     mut db: DbConn,
     Json(cardio_sessions): Json<UnverifiedSingleOrVec<CardioSession>>,
 ) -> HandlerResult<Json<EpochResponse>> {
     match cardio_sessions {
         UnverifiedSingleOrVec::Single(cardio_session) => {
-            let cardio_session = cardio_session.verify_user_ap_create(auth)?;
+            let mut cardio_session = cardio_session.verify_user_ap_create(auth)?;
+            check_cardio_sessions(slice::from_ref(&cardio_session), *auth, &mut db).await?;
+            assign_matching_route(&mut cardio_session, config, &mut db).await?;
+            assign_default_gear(&mut cardio_session, &mut db).await?;
             CardioSessionDb::create(&cardio_session, &mut db).await?;
+            SegmentEffortDb::update_by_cardio_session(
+                &cardio_session,
+                config.segment_tolerance,
+                &mut db,
+            )
+            .await?;
         }
         UnverifiedSingleOrVec::Vec(cardio_sessions) => {
-            let cardio_sessions = cardio_sessions.verify_user_ap_create(auth)?;
+            let mut cardio_sessions = cardio_sessions.verify_user_ap_create(auth)?;
+            check_cardio_sessions(&cardio_sessions, *auth, &mut db).await?;
+            for cardio_session in &mut cardio_sessions {
+                assign_matching_route(cardio_session, config, &mut db).await?;
+                assign_default_gear(cardio_session, &mut db).await?;
+            }
             CardioSessionDb::create_multiple(&cardio_sessions, &mut db).await?;
+            for cardio_session in &cardio_sessions {
+                SegmentEffortDb::update_by_cardio_session(
+                    cardio_session,
+                    config.segment_tolerance,
+                    &mut db,
+                )
+                .await?;
+            }
         }
     }
     let epoch = CardioSessionDb::get_epoch_by_user(*auth, &mut db).await?;
@@ -85,9 +621,12 @@ pub async fn get_cardio_sessions(
     auth: AuthUserOrAP,
     Query(IdOption { id }): Query<IdOption<UnverifiedId<CardioSessionId>>>,
     Query(time_span_option): Query<TimeSpanOption>,
+    Query(ExportFormatOption { format }): Query<ExportFormatOption>,
     mut db: DbConn,
-) -> HandlerResult<Json<Vec<CardioSession>>> {
-    match id {
+) -> HandlerResult<Response> {
+    check_export_id(format, id.is_some())?;
+
+    let mut cardio_sessions = match id {
         Some(id) => {
             let cardio_session_id = id.verify_user_ap_get(auth, &mut db).await?;
             CardioSessionDb::get_by_id(cardio_session_id, &mut db)
@@ -97,26 +636,1020 @@ pub async fn get_cardio_sessions(
         None => {
             CardioSessionDb::get_by_user_and_timespan(*auth, time_span_option.into(), &mut db).await
         }
+    }?;
+
+    let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+    if !privacy_zones.is_empty() {
+        for track in cardio_sessions
+            .iter_mut()
+            .filter_map(|cardio_session| cardio_session.track.as_mut())
+        {
+            remove_privacy_zones(track, &privacy_zones);
+        }
+    }
+
+    let Some(format) = format else {
+        return Ok(Json(cardio_sessions).into_response());
+    };
+    // the id is required for exports, so there is exactly one cardio session
+    let cardio_session = cardio_sessions.remove(0);
+    match format {
+        ExportFormat::Gpx => {
+            let filename = format!("cardio_session_{}.gpx", cardio_session.id.0);
+            Ok(export_response(
+                "application/gpx+xml",
+                &filename,
+                Gpx::from(cardio_session).to_xml(),
+            ))
+        }
+        ExportFormat::Tcx => {
+            let movement = MovementDb::get_by_id(cardio_session.movement_id, &mut db).await?;
+            let filename = format!("cardio_session_{}.tcx", cardio_session.id.0);
+            Ok(export_response(
+                "application/vnd.garmin.tcx+xml",
+                &filename,
+                to_tcx(&cardio_session, tcx_sport(&movement.name)),
+            ))
+        }
     }
-    .map(Json)
-    .map_err(Into::into)
 }
 
 pub async fn update_cardio_sessions(
     auth: AuthUserOrAP,
+    State(config): State<&Config>,
     mut db: DbConn,
     Json(cardio_sessions): Json<UnverifiedSingleOrVec<CardioSession>>,
 ) -> HandlerResult<Json<EpochResponse>> {
     match cardio_sessions {
         UnverifiedSingleOrVec::Single(cardio_session) => {
-            let cardio_session = cardio_session.verify_user_ap_update(auth, &mut db).await?;
+            let mut cardio_session = cardio_session.verify_user_ap_update(auth, &mut db).await?;
+            check_cardio_sessions(slice::from_ref(&cardio_session), *auth, &mut db).await?;
+            let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+            let old_cardio_session = CardioSessionDb::get_by_id(cardio_session.id, &mut db).await?;
+            restore_privacy_zones(
+                &mut cardio_session.track,
+                old_cardio_session.track.as_deref(),
+                &privacy_zones,
+            )?;
+            rematch_route(&mut cardio_session, &old_cardio_session, config, &mut db).await?;
+            discard_elevation_correction(&mut cardio_session, &old_cardio_session, &mut db).await?;
             CardioSessionDb::update(&cardio_session, &mut db).await?;
+            SegmentEffortDb::update_by_cardio_session(
+                &cardio_session,
+                config.segment_tolerance,
+                &mut db,
+            )
+            .await?;
         }
         UnverifiedSingleOrVec::Vec(cardio_sessions) => {
-            let cardio_sessions = cardio_sessions.verify_user_ap_update(auth, &mut db).await?;
+            let mut cardio_sessions = cardio_sessions.verify_user_ap_update(auth, &mut db).await?;
+            check_cardio_sessions(&cardio_sessions, *auth, &mut db).await?;
+            let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+            for cardio_session in &mut cardio_sessions {
+                let old_cardio_session =
+                    CardioSessionDb::get_by_id(cardio_session.id, &mut db).await?;
+                restore_privacy_zones(
+                    &mut cardio_session.track,
+                    old_cardio_session.track.as_deref(),
+                    &privacy_zones,
+                )?;
+                rematch_route(cardio_session, &old_cardio_session, config, &mut db).await?;
+                discard_elevation_correction(cardio_session, &old_cardio_session, &mut db).await?;
+            }
             CardioSessionDb::update_multiple(&cardio_sessions, &mut db).await?;
+            for cardio_session in &cardio_sessions {
+                SegmentEffortDb::update_by_cardio_session(
+                    cardio_session,
+                    config.segment_tolerance,
+                    &mut db,
+                )
+                .await?;
+            }
         }
     }
     let epoch = CardioSessionDb::get_epoch_by_user(*auth, &mut db).await?;
     Ok(Json(EpochResponse { epoch }))
 }
+
+/// Checks that the cardio sessions of the laps belong to the user.
+async fn check_cardio_laps(
+    cardio_laps: &[CardioLap],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let cardio_session_ids: Vec<_> = cardio_laps
+        .iter()
+        .map(|cardio_lap| cardio_lap.cardio_session_id)
+        .collect();
+    check_references::<CardioSessionDb>(&cardio_session_ids, user_id, db).await
+}
+
+pub async fn create_cardio_laps(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(cardio_laps): Json<UnverifiedSingleOrVec<CardioLap>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match cardio_laps {
+        UnverifiedSingleOrVec::Single(cardio_lap) => {
+            let cardio_lap = cardio_lap.verify_user_ap_create(auth)?;
+            check_cardio_laps(slice::from_ref(&cardio_lap), *auth, &mut db).await?;
+            CardioLapDb::create(&cardio_lap, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(cardio_laps) => {
+            let cardio_laps = cardio_laps.verify_user_ap_create(auth)?;
+            check_cardio_laps(&cardio_laps, *auth, &mut db).await?;
+            CardioLapDb::create_multiple(&cardio_laps, &mut db).await?;
+        }
+    }
+    let epoch = CardioLapDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_cardio_laps(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<CardioLapId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<CardioLap>>> {
+    match id {
+        Some(id) => {
+            let cardio_lap_id = id.verify_user_ap_get(auth, &mut db).await?;
+            CardioLapDb::get_by_id(cardio_lap_id, &mut db)
+                .await
+                .map(|c| vec![c])
+        }
+        None => CardioLapDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_cardio_laps(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(cardio_laps): Json<UnverifiedSingleOrVec<CardioLap>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match cardio_laps {
+        UnverifiedSingleOrVec::Single(cardio_lap) => {
+            let cardio_lap = cardio_lap.verify_user_ap_update(auth, &mut db).await?;
+            check_cardio_laps(slice::from_ref(&cardio_lap), *auth, &mut db).await?;
+            CardioLapDb::update(&cardio_lap, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(cardio_laps) => {
+            let cardio_laps = cardio_laps.verify_user_ap_update(auth, &mut db).await?;
+            check_cardio_laps(&cardio_laps, *auth, &mut db).await?;
+            CardioLapDb::update_multiple(&cardio_laps, &mut db).await?;
+        }
+    }
+    let epoch = CardioLapDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn create_multisport_sessions(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(multisport_sessions): Json<UnverifiedSingleOrVec<MultisportSession>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match multisport_sessions {
+        UnverifiedSingleOrVec::Single(multisport_session) => {
+            let multisport_session = multisport_session.verify_user_ap_create(auth)?;
+            MultisportSessionDb::create(&multisport_session, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(multisport_sessions) => {
+            let multisport_sessions = multisport_sessions.verify_user_ap_create(auth)?;
+            MultisportSessionDb::create_multiple(&multisport_sessions, &mut db).await?;
+        }
+    }
+    let epoch = MultisportSessionDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_multisport_sessions(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<MultisportSessionId>>>,
+    Query(time_span_option): Query<TimeSpanOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<MultisportSession>>> {
+    match id {
+        Some(id) => {
+            let multisport_session_id = id.verify_user_ap_get(auth, &mut db).await?;
+            MultisportSessionDb::get_by_id(multisport_session_id, &mut db)
+                .await
+                .map(|m| vec![m])
+        }
+        None => {
+            MultisportSessionDb::get_by_user_and_timespan(*auth, time_span_option.into(), &mut db)
+                .await
+        }
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_multisport_sessions(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(multisport_sessions): Json<UnverifiedSingleOrVec<MultisportSession>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match multisport_sessions {
+        UnverifiedSingleOrVec::Single(multisport_session) => {
+            let multisport_session = multisport_session
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            MultisportSessionDb::update(&multisport_session, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(multisport_sessions) => {
+            let multisport_sessions = multisport_sessions
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            MultisportSessionDb::update_multiple(&multisport_sessions, &mut db).await?;
+        }
+    }
+    let epoch = MultisportSessionDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Returns the totals of all legs of a multisport session.
+pub async fn get_multisport_session_totals(
+    auth: AuthUserOrAP,
+    Query(MultisportSessionIdQuery {
+        multisport_session_id,
+    }): Query<MultisportSessionIdQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<MultisportSessionTotals>> {
+    let multisport_session_id = multisport_session_id
+        .verify_user_ap_get(auth, &mut db)
+        .await?;
+    let totals = MultisportSessionDb::get_totals(multisport_session_id, &mut db)
+        .await?
+        .unwrap_or(MultisportSessionTotals {
+            multisport_session_id,
+            legs: 0,
+            distance: None,
+            ascent: None,
+            descent: None,
+            time: None,
+            transition_time: None,
+            calories: None,
+        });
+    Ok(Json(totals))
+}
+
+/// Checks that the multisport sessions and cardio sessions of the legs belong to the user.
+async fn check_multisport_legs(
+    multisport_legs: &[MultisportLeg],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let multisport_session_ids: Vec<_> = multisport_legs
+        .iter()
+        .map(|multisport_leg| multisport_leg.multisport_session_id)
+        .collect();
+    check_references::<MultisportSessionDb>(&multisport_session_ids, user_id, db).await?;
+    let cardio_session_ids: Vec<_> = multisport_legs
+        .iter()
+        .map(|multisport_leg| multisport_leg.cardio_session_id)
+        .collect();
+    check_references::<CardioSessionDb>(&cardio_session_ids, user_id, db).await
+}
+
+pub async fn create_multisport_legs(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(multisport_legs): Json<UnverifiedSingleOrVec<MultisportLeg>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match multisport_legs {
+        UnverifiedSingleOrVec::Single(multisport_leg) => {
+            let multisport_leg = multisport_leg.verify_user_ap_create(auth)?;
+            check_multisport_legs(std::slice::from_ref(&multisport_leg), *auth, &mut db).await?;
+            MultisportLegDb::create(&multisport_leg, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(multisport_legs) => {
+            let multisport_legs = multisport_legs.verify_user_ap_create(auth)?;
+            check_multisport_legs(&multisport_legs, *auth, &mut db).await?;
+            MultisportLegDb::create_multiple(&multisport_legs, &mut db).await?;
+        }
+    }
+    let epoch = MultisportLegDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_multisport_legs(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<MultisportLegId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<MultisportLeg>>> {
+    match id {
+        Some(id) => {
+            let multisport_leg_id = id.verify_user_ap_get(auth, &mut db).await?;
+            MultisportLegDb::get_by_id(multisport_leg_id, &mut db)
+                .await
+                .map(|m| vec![m])
+        }
+        None => MultisportLegDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_multisport_legs(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(multisport_legs): Json<UnverifiedSingleOrVec<MultisportLeg>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match multisport_legs {
+        UnverifiedSingleOrVec::Single(multisport_leg) => {
+            let multisport_leg = multisport_leg.verify_user_ap_update(auth, &mut db).await?;
+            check_multisport_legs(std::slice::from_ref(&multisport_leg), *auth, &mut db).await?;
+            MultisportLegDb::update(&multisport_leg, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(multisport_legs) => {
+            let multisport_legs = multisport_legs.verify_user_ap_update(auth, &mut db).await?;
+            check_multisport_legs(&multisport_legs, *auth, &mut db).await?;
+            MultisportLegDb::update_multiple(&multisport_legs, &mut db).await?;
+        }
+    }
+    let epoch = MultisportLegDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Checks that the default movements of the gear belong to the user or are predefined.
+async fn check_gears(
+    gears: &[Gear],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let movement_ids: Vec<_> = gears
+        .iter()
+        .filter_map(|gear| gear.default_movement_id)
+        .collect();
+    check_optional_references::<MovementDb>(&movement_ids, user_id, db).await
+}
+
+pub async fn create_gears(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(gears): Json<UnverifiedSingleOrVec<Gear>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match gears {
+        UnverifiedSingleOrVec::Single(gear) => {
+            let gear = gear.verify_user_ap_create(auth)?;
+            check_gears(slice::from_ref(&gear), *auth, &mut db).await?;
+            GearDb::create(&gear, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(gears) => {
+            let gears = gears.verify_user_ap_create(auth)?;
+            check_gears(&gears, *auth, &mut db).await?;
+            GearDb::create_multiple(&gears, &mut db).await?;
+        }
+    }
+    let epoch = GearDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_gears(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<GearId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<Gear>>> {
+    match id {
+        Some(id) => {
+            let gear_id = id.verify_user_ap_get(auth, &mut db).await?;
+            GearDb::get_by_id(gear_id, &mut db).await.map(|g| vec![g])
+        }
+        None => GearDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_gears(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(gears): Json<UnverifiedSingleOrVec<Gear>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match gears {
+        UnverifiedSingleOrVec::Single(gear) => {
+            let gear = gear.verify_user_ap_update(auth, &mut db).await?;
+            check_gears(slice::from_ref(&gear), *auth, &mut db).await?;
+            GearDb::update(&gear, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(gears) => {
+            let gears = gears.verify_user_ap_update(auth, &mut db).await?;
+            check_gears(&gears, *auth, &mut db).await?;
+            GearDb::update_multiple(&gears, &mut db).await?;
+        }
+    }
+    let epoch = GearDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Returns the accumulated distance and time of all gear of the user.
+///
+/// Gear that reached its retirement distance is marked with `retirement_distance_reached`.
+pub async fn get_gear_stats(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<GearStats>>> {
+    GearDb::get_stats_by_user(*auth, &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
+
+/// Replaces the elevation of the track of the cardio session by the elevation from the configured
+/// DEM tiles and recomputes the ascent and descent.
+///
+/// The original ascent and descent are kept in `uncorrected_ascent` and `uncorrected_descent`.
+/// The original elevation of the track is kept by the server, so that the correction can be
+/// reverted with [`revert_cardio_session_elevation`].
+pub async fn correct_cardio_session_elevation(
+    auth: AuthUserOrAP,
+    Query(CardioSessionIdQuery { cardio_session_id }): Query<CardioSessionIdQuery>,
+    State(config): State<&Config>,
+    State(dem_cache): State<DemCache>,
+    mut db: DbConn,
+) -> HandlerResult<Json<CardioSession>> {
+    let Some(dem_dir) = &config.dem_dir else {
+        return Err(HandlerError::from((
+            StatusCode::FORBIDDEN,
+            ErrorMessage::Other {
+                error: "elevation correction is disabled".to_owned(),
+            },
+        )));
+    };
+
+    let cardio_session_id = cardio_session_id.verify_user_ap_get(auth, &mut db).await?;
+    let mut cardio_session = CardioSessionDb::get_by_id(cardio_session_id, &mut db).await?;
+
+    let Some(track) = &mut cardio_session.track else {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "the cardio session has no track".to_owned(),
+            },
+        )));
+    };
+
+    let uncorrected_elevation: Vec<f64> = track.iter().map(|pos| pos.elevation).collect();
+    let corrected = correct_elevation(dem_dir, &dem_cache, track)
+        .await
+        .map_err(|error| {
+            HandlerError::from((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorMessage::Other {
+                    error: format!("failed to read the elevation data: {error}"),
+                },
+            ))
+        })?;
+    if corrected == 0 {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "no elevation data is available for the track".to_owned(),
+            },
+        )));
+    }
+
+    let (ascent, descent) = ascent_descent(track);
+    let first_correction = CardioSessionDb::get_uncorrected_elevation(cardio_session_id, &mut db)
+        .await?
+        .is_none();
+    if first_correction {
+        cardio_session.uncorrected_ascent = cardio_session.ascent;
+        cardio_session.uncorrected_descent = cardio_session.descent;
+    }
+    cardio_session.ascent = Some(ascent.round() as i32);
+    cardio_session.descent = Some(descent.round() as i32);
+
+    CardioSessionDb::update(&cardio_session, &mut db).await?;
+    if first_correction {
+        CardioSessionDb::set_uncorrected_elevation(
+            cardio_session_id,
+            Some(&uncorrected_elevation),
+            &mut db,
+        )
+        .await?;
+    }
+
+    let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+    if let Some(track) = &mut cardio_session.track {
+        remove_privacy_zones(track, &privacy_zones);
+    }
+
+    Ok(Json(cardio_session))
+}
+
+/// Reverts the elevation correction of a [`CardioSession`].
+///
+/// The original elevation of the track as well as the original ascent and descent are restored.
+pub async fn revert_cardio_session_elevation(
+    auth: AuthUserOrAP,
+    Query(CardioSessionIdQuery { cardio_session_id }): Query<CardioSessionIdQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<CardioSession>> {
+    let cardio_session_id = cardio_session_id.verify_user_ap_get(auth, &mut db).await?;
+    let mut cardio_session = CardioSessionDb::get_by_id(cardio_session_id, &mut db).await?;
+
+    let Some(uncorrected_elevation) =
+        CardioSessionDb::get_uncorrected_elevation(cardio_session_id, &mut db).await?
+    else {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "the elevation of the cardio session has not been corrected".to_owned(),
+            },
+        )));
+    };
+    let Some(track) = cardio_session
+        .track
+        .as_mut()
+        .filter(|track| track.len() == uncorrected_elevation.len())
+    else {
+        return Err(HandlerError::from((
+            StatusCode::CONFLICT,
+            ErrorMessage::Other {
+                error: "the track has been changed since the elevation correction".to_owned(),
+            },
+        )));
+    };
+
+    for (pos, elevation) in track.iter_mut().zip(uncorrected_elevation) {
+        pos.elevation = elevation;
+    }
+    cardio_session.ascent = cardio_session.uncorrected_ascent.take();
+    cardio_session.descent = cardio_session.uncorrected_descent.take();
+
+    CardioSessionDb::update(&cardio_session, &mut db).await?;
+    CardioSessionDb::set_uncorrected_elevation(cardio_session_id, None, &mut db).await?;
+
+    let privacy_zones = get_applicable_privacy_zones(auth, &mut db).await?;
+    if let Some(track) = &mut cardio_session.track {
+        remove_privacy_zones(track, &privacy_zones);
+    }
+
+    Ok(Json(cardio_session))
+}
+
+/// Creates a share link for a [`CardioSession`] or returns the existing one.
+pub async fn share_cardio_session(
+    auth: AuthUser,
+    Query(CardioSessionIdQuery { cardio_session_id }): Query<CardioSessionIdQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<CardioSessionShare>> {
+    let cardio_session_id = cardio_session_id.verify_user_get(auth, &mut db).await?;
+
+    let token =
+        if let Some(token) = CardioSessionDb::get_share_token(cardio_session_id, &mut db).await? {
+            token
+        } else {
+            let token = format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64());
+            CardioSessionDb::set_share_token(cardio_session_id, Some(&token), &mut db).await?;
+            token
+        };
+
+    Ok(Json(CardioSessionShare {
+        cardio_session_id,
+        token,
+    }))
+}
+
+/// Deletes the share link of a [`CardioSession`].
+pub async fn unshare_cardio_session(
+    auth: AuthUser,
+    Query(CardioSessionIdQuery { cardio_session_id }): Query<CardioSessionIdQuery>,
+    mut db: DbConn,
+) -> HandlerResult<StatusCode> {
+    let cardio_session_id = cardio_session_id.verify_user_get(auth, &mut db).await?;
+    CardioSessionDb::set_share_token(cardio_session_id, None, &mut db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Returns the shared [`CardioSession`] with the given share token.
+///
+/// No authentication is required. The privacy zones of the owner are always removed from the
+/// track.
+pub async fn get_shared_cardio_session(
+    Query(ShareTokenQuery { token }): Query<ShareTokenQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<CardioSession>> {
+    let mut cardio_session = CardioSessionDb::get_by_share_token(&token, &mut db).await?;
+
+    let privacy_zones = PrivacyZoneDb::get_by_user(cardio_session.user_id, &mut db).await?;
+    if let Some(track) = &mut cardio_session.track {
+        remove_privacy_zones(track, &privacy_zones);
+    }
+
+    Ok(Json(cardio_session))
+}
+
+pub async fn create_privacy_zones(
+    auth: AuthUser,
+    mut db: DbConn,
+    Json(privacy_zones): Json<UnverifiedSingleOrVec<PrivacyZone>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match privacy_zones {
+        UnverifiedSingleOrVec::Single(privacy_zone) => {
+            let privacy_zone = privacy_zone.verify_user_create(auth)?;
+            PrivacyZoneDb::create(&privacy_zone, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(privacy_zones) => {
+            let privacy_zones = privacy_zones.verify_user_create(auth)?;
+            PrivacyZoneDb::create_multiple(&privacy_zones, &mut db).await?;
+        }
+    }
+    let epoch = PrivacyZoneDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_privacy_zones(
+    auth: AuthUser,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<PrivacyZoneId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<PrivacyZone>>> {
+    match id {
+        Some(id) => {
+            let privacy_zone_id = id.verify_user_get(auth, &mut db).await?;
+            PrivacyZoneDb::get_by_id(privacy_zone_id, &mut db)
+                .await
+                .map(|p| vec![p])
+        }
+        None => PrivacyZoneDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_privacy_zones(
+    auth: AuthUser,
+    mut db: DbConn,
+    Json(privacy_zones): Json<UnverifiedSingleOrVec<PrivacyZone>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match privacy_zones {
+        UnverifiedSingleOrVec::Single(privacy_zone) => {
+            let privacy_zone = privacy_zone.verify_user_update(auth, &mut db).await?;
+            PrivacyZoneDb::update(&privacy_zone, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(privacy_zones) => {
+            let privacy_zones = privacy_zones.verify_user_update(auth, &mut db).await?;
+            PrivacyZoneDb::update_multiple(&privacy_zones, &mut db).await?;
+        }
+    }
+    let epoch = PrivacyZoneDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Returns the heatmap of all tracks of the user in the area.
+///
+/// Rendered heatmaps are cached until the cardio sessions, routes or privacy zones of the user
+/// change.
+async fn get_cached_heatmap(
+    auth: AuthUserOrAP,
+    area: PixelArea,
+    cache: &HeatmapCache,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<impl IntoResponse> {
+    let privacy_zone_epoch = match auth.ap_id() {
+        Some(_) => Some(PrivacyZoneDb::get_epoch_by_user(*auth, db).await?),
+        None => None,
+    };
+    let key = HeatmapKey::new(
+        *auth,
+        CardioSessionDb::get_epoch_by_user(*auth, db).await?,
+        RouteDb::get_epoch_by_user(*auth, db).await?,
+        privacy_zone_epoch,
+        area,
+    );
+
+    let heatmap = if let Some(heatmap) = cache.get(&key) {
+        heatmap
+    } else {
+        let mut tracks = CardioSessionDb::get_tracks_by_user(*auth, db).await?;
+        tracks.extend(RouteDb::get_tracks_by_user(*auth, db).await?);
+        let privacy_zones = get_applicable_privacy_zones(auth, db).await?;
+
+        let heatmap = tokio::task::spawn_blocking(move || {
+            // the parts of a track are not connected across privacy zones
+            render_heatmap(
+                tracks
+                    .iter()
+                    .flat_map(|track| split_at_privacy_zones(track, &privacy_zones)),
+                area,
+            )
+        })
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        cache.insert(key, heatmap.clone());
+        heatmap
+    };
+
+    Ok(([(CONTENT_TYPE, "image/png")], heatmap))
+}
+
+pub async fn get_heatmap(
+    auth: AuthUserOrAP,
+    Query(HeatmapQuery {
+        zoom,
+        min_latitude,
+        min_longitude,
+        max_latitude,
+        max_longitude,
+    }): Query<HeatmapQuery>,
+    State(cache): State<HeatmapCache>,
+    mut db: DbConn,
+) -> HandlerResult<impl IntoResponse> {
+    if zoom > MAX_ZOOM
+        || !(-90. ..=90.).contains(&min_latitude)
+        || !(-90. ..=90.).contains(&max_latitude)
+        || !(-180. ..=180.).contains(&min_longitude)
+        || !(-180. ..=180.).contains(&max_longitude)
+        || min_latitude >= max_latitude
+        || min_longitude >= max_longitude
+    {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "the zoom level or the bounding box is invalid".to_owned(),
+            },
+        )));
+    }
+
+    let area = PixelArea::from_bounding_box(
+        zoom,
+        min_latitude,
+        min_longitude,
+        max_latitude,
+        max_longitude,
+    );
+    if area.width > MAX_SIZE || area.height > MAX_SIZE {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: format!("the heatmap must not be larger than {MAX_SIZE}x{MAX_SIZE} pixel"),
+            },
+        )));
+    }
+    // the bounding box can be smaller than a pixel if both edges project to the same pixel border
+    if area.width == 0 || area.height == 0 {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "the heatmap must be at least 1x1 pixel".to_owned(),
+            },
+        )));
+    }
+
+    get_cached_heatmap(auth, area, &cache, &mut db).await
+}
+
+pub async fn get_heatmap_tile(
+    auth: AuthUserOrAP,
+    Query(HeatmapTileQuery { zoom, x, y }): Query<HeatmapTileQuery>,
+    State(cache): State<HeatmapCache>,
+    mut db: DbConn,
+) -> HandlerResult<impl IntoResponse> {
+    if zoom > MAX_ZOOM || x >= 1 << zoom || y >= 1 << zoom {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "the tile does not exist".to_owned(),
+            },
+        )));
+    }
+
+    get_cached_heatmap(auth, PixelArea::from_tile(zoom, x, y), &cache, &mut db).await
+}
+
+fn to_cardio_laps(cardio_session: &CardioSession, laps: Vec<Lap>) -> Vec<CardioLap> {
+    laps.into_iter()
+        .enumerate()
+        .map(|(lap_number, lap)| CardioLap {
+            id: CardioLapId(OsRng.next_u64() as i64),
+            user_id: cardio_session.user_id,
+            cardio_session_id: cardio_session.id,
+            lap_number: lap_number as i32,
+            start: lap.start,
+            time: lap.time,
+            distance: lap.distance,
+            avg_heart_rate: lap.avg_heart_rate,
+            rest: lap.rest,
+            deleted: false,
+        })
+        .collect()
+}
+
+fn parse_gpx_body(body: &str) -> HandlerResult<Gpx> {
+    parse_gpx(body).map_err(|error| {
+        HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: error.to_string(),
+            },
+        ))
+    })
+}
+
+/// Creates a new cardio session from the GPX file in the request body.
+pub async fn import_cardio_session_gpx(
+    auth: AuthUserOrAP,
+    Query(CardioSessionImportQuery {
+        movement_id,
+        cardio_type,
+    }): Query<CardioSessionImportQuery>,
+    State(config): State<&Config>,
+    mut db: DbConn,
+    body: String,
+) -> HandlerResult<Json<CardioSession>> {
+    let movement_id = movement_id.verify_user_ap_get(auth, &mut db).await?;
+    let gpx = parse_gpx_body(&body)?;
+
+    let (ascent, descent) = gpx.ascent_descent().unzip();
+    let distance = gpx.distance().filter(|&distance| distance > 0);
+    let time = gpx.time().filter(|&time| time > 0);
+    let avg_cadence = gpx.avg_cadence();
+    let avg_heart_rate = gpx.avg_heart_rate();
+    let mut cardio_session = CardioSession {
+        id: CardioSessionId(OsRng.next_u64() as i64),
+        user_id: *auth,
+        movement_id,
+        cardio_type,
+        datetime: gpx.start_time.unwrap_or_else(Utc::now),
+        distance,
+        ascent,
+        descent,
+        uncorrected_ascent: None,
+        uncorrected_descent: None,
+        time,
+        calories: None,
+        track: Some(gpx.track),
+        avg_cadence,
+        cadence: gpx.cadence,
+        avg_heart_rate,
+        heart_rate: gpx.heart_rate,
+        avg_power: None,
+        max_power: None,
+        normalized_power: None,
+        power: None,
+        speed: None,
+        temperature: None,
+        route_id: None,
+        gear_id: None,
+        planned_workout_id: None,
+        comments: gpx.name,
+        deleted: false,
+    };
+
+    let cardio_laps = to_cardio_laps(&cardio_session, gpx.laps);
+
+    assign_matching_route(&mut cardio_session, config, &mut db).await?;
+    assign_default_gear(&mut cardio_session, &mut db).await?;
+    let tolerance = config.segment_tolerance;
+    db.transaction(|db| {
+        async {
+            CardioSessionDb::create(&cardio_session, db).await?;
+            if !cardio_laps.is_empty() {
+                CardioLapDb::create_multiple(&cardio_laps, db).await?;
+            }
+            SegmentEffortDb::update_by_cardio_session(&cardio_session, tolerance, db).await
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(Json(cardio_session))
+}
+
+/// Creates a new cardio session from every FIT file in the multipart request body.
+///
+/// If any of the files can not be parsed or stored, no cardio session is created.
+pub async fn import_cardio_session_fit(
+    auth: AuthUserOrAP,
+    Query(CardioSessionImportQuery {
+        movement_id,
+        cardio_type,
+    }): Query<CardioSessionImportQuery>,
+    State(config): State<&Config>,
+    mut db: DbConn,
+    mut multipart: Multipart,
+) -> HandlerResult<Json<Vec<CardioSession>>> {
+    let movement_id = movement_id.verify_user_ap_get(auth, &mut db).await?;
+    let bad_request = |error: String| {
+        HandlerError::from((StatusCode::BAD_REQUEST, ErrorMessage::Other { error }))
+    };
+
+    let mut cardio_sessions = vec![];
+    let mut cardio_laps = vec![];
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|error| bad_request(error.body_text()))?
+    {
+        let file_name = field.file_name().unwrap_or_default().to_owned();
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|error| bad_request(error.body_text()))?;
+        let fit =
+            parse_fit(&bytes).map_err(|error| bad_request(format!("{file_name}: {error}")))?;
+
+        let mut cardio_session = CardioSession {
+            id: CardioSessionId(OsRng.next_u64() as i64),
+            user_id: *auth,
+            movement_id,
+            cardio_type,
+            datetime: fit.start_time.unwrap_or_else(Utc::now),
+            distance: fit.distance,
+            ascent: fit.ascent,
+            descent: fit.descent,
+            uncorrected_ascent: None,
+            uncorrected_descent: None,
+            time: fit.time,
+            calories: fit.calories,
+            track: (!fit.track.is_empty()).then_some(fit.track),
+            avg_cadence: fit.avg_cadence,
+            cadence: fit.cadence,
+            avg_heart_rate: fit.avg_heart_rate,
+            heart_rate: fit.heart_rate,
+            avg_power: fit.avg_power,
+            max_power: fit.max_power,
+            normalized_power: fit.normalized_power,
+            power: fit.power,
+            speed: fit.speed,
+            temperature: fit.temperature,
+            route_id: None,
+            gear_id: None,
+            planned_workout_id: None,
+            comments: None,
+            deleted: false,
+        };
+        cardio_laps.extend(to_cardio_laps(&cardio_session, fit.laps));
+        assign_matching_route(&mut cardio_session, config, &mut db).await?;
+        assign_default_gear(&mut cardio_session, &mut db).await?;
+        cardio_sessions.push(cardio_session);
+    }
+
+    if cardio_sessions.is_empty() {
+        return Err(bad_request("the request contains no fit file".to_owned()));
+    }
+    let tolerance = config.segment_tolerance;
+    db.transaction(|db| {
+        async {
+            CardioSessionDb::create_multiple(&cardio_sessions, db).await?;
+            if !cardio_laps.is_empty() {
+                CardioLapDb::create_multiple(&cardio_laps, db).await?;
+            }
+            for cardio_session in &cardio_sessions {
+                SegmentEffortDb::update_by_cardio_session(cardio_session, tolerance, db).await?;
+            }
+            QueryResult::Ok(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+    Ok(Json(cardio_sessions))
+}
+
+/// Creates a new route from the GPX file in the request body.
+///
+/// If no `name` is given, the name from the GPX file is used. It is shortened to at most 80
+/// characters. A `name` that is given explicitly is not shortened but must not be longer.
+pub async fn import_route_gpx(
+    auth: AuthUserOrAP,
+    Query(RouteImportQuery { name }): Query<RouteImportQuery>,
+    mut db: DbConn,
+    body: String,
+) -> HandlerResult<Json<Route>> {
+    let gpx = parse_gpx_body(&body)?;
+
+    let name = name.or_else(|| {
+        gpx.name
+            .as_deref()
+            .map(|name| name.chars().take(MAX_ROUTE_NAME_LEN).collect())
+    });
+    let Some(name) = name.filter(|name: &String| {
+        (MIN_ROUTE_NAME_LEN..=MAX_ROUTE_NAME_LEN).contains(&name.chars().count())
+    }) else {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: format!(
+                    "the route needs a name with {MIN_ROUTE_NAME_LEN} to {MAX_ROUTE_NAME_LEN} \
+                     characters"
+                ),
+            },
+        )));
+    };
+    let (ascent, descent) = gpx.ascent_descent().unzip();
+    let route = Route {
+        id: RouteId(OsRng.next_u64() as i64),
+        user_id: *auth,
+        name,
+        distance: gpx.distance().filter(|&distance| distance > 0),
+        ascent,
+        descent,
+        track: Some(gpx.track),
+        marked_positions: Some(gpx.marked_positions),
+        deleted: false,
+    };
+
+    RouteDb::create(&route, &mut db).await?;
+    Ok(Json(route))
+}
@@ -1,16 +1,409 @@
+use std::collections::HashMap;
+
 use axum::{extract::Query, Json};
+use diesel_async::AsyncPgConnection;
+use serde::Deserialize;
 use sport_log_types::{
-    EpochResponse, Metcon, MetconId, MetconMovement, MetconMovementId, MetconSession,
-    MetconSessionId,
+    EpochResponse, Metcon, MetconId, MetconMovement, MetconMovementId, MetconMovementScaling,
+    MetconMovementScalingId, MetconRecord, MetconSession, MetconSessionId, MetconSessionScaling,
+    MetconSessionScalingId, MetconType, ScalingLevel, UserId,
 };
 
 use crate::{
     auth::AuthUserOrAP,
     db::*,
-    handler::{HandlerResult, IdOption, TimeSpanOption, UnverifiedSingleOrVec},
+    handler::{
+        check_optional_references, check_references, FieldError, HandlerResult, IdOption,
+        TimeSpanOption, UnverifiedSingleOrVec,
+    },
     state::DbConn,
 };
 
+#[derive(Debug, Deserialize)]
+pub struct MetconIdOption {
+    metcon_id: Option<UnverifiedId<MetconId>>,
+}
+
+/// Adds an error if `is_set` does not match `expected`.
+///
+/// `expected` is `Some(true)` if the field must be set, `Some(false)` if it must not be set and
+/// `None` if it is optional.
+fn check_field(
+    errors: &mut Vec<FieldError>,
+    index: usize,
+    field: &str,
+    is_set: bool,
+    expected: Option<bool>,
+) {
+    match expected {
+        Some(true) if !is_set => {
+            errors.push(FieldError::new(
+                index,
+                field,
+                "must be set for this metcon type",
+            ));
+        }
+        Some(false) if is_set => {
+            errors.push(FieldError::new(
+                index,
+                field,
+                "must not be set for this metcon type",
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// Checks that `rounds` and `timecap` of the metcons match their [`MetconType`].
+///
+/// Deleted metcons are skipped, so that metcons that were created before the check can still be
+/// deleted.
+fn check_metcons(metcons: &[Metcon]) -> HandlerResult<()> {
+    let mut errors = vec![];
+    for (index, metcon) in metcons.iter().enumerate() {
+        if metcon.deleted {
+            continue;
+        }
+        let (rounds, timecap) = match metcon.metcon_type {
+            MetconType::Amrap => (Some(false), Some(true)),
+            MetconType::Emom | MetconType::Tabata => (Some(true), Some(true)),
+            MetconType::ForTime => (Some(true), None),
+            MetconType::DeathBy | MetconType::Chipper => (Some(false), None),
+            MetconType::Ladder => (None, Some(true)),
+        };
+        check_field(
+            &mut errors,
+            index,
+            "rounds",
+            metcon.rounds.is_some(),
+            rounds,
+        );
+        check_field(
+            &mut errors,
+            index,
+            "timecap",
+            metcon.timecap.is_some(),
+            timecap,
+        );
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Checks that the existing metcon sessions of the metcons still match their [`MetconType`] and
+/// `rounds` if one of them changed.
+///
+/// Deleted metcons are skipped. Metcons whose type and rounds are unchanged are skipped, so that
+/// sessions that were created before the score check do not prevent other changes.
+async fn check_metcon_updates(metcons: &[Metcon], db: &mut AsyncPgConnection) -> HandlerResult<()> {
+    let metcon_ids: Vec<_> = metcons.iter().map(|metcon| metcon.id).collect();
+    let old_metcons = MetconDb::get_by_ids(&metcon_ids, db).await?;
+    let changed_metcons: HashMap<_, _> = metcons
+        .iter()
+        .enumerate()
+        .filter(|(_, metcon)| !metcon.deleted)
+        .filter(|(_, metcon)| {
+            old_metcons.get(&metcon.id).is_some_and(|old_metcon| {
+                old_metcon.metcon_type != metcon.metcon_type || old_metcon.rounds != metcon.rounds
+            })
+        })
+        .map(|(index, metcon)| (metcon.id, (index, metcon.clone())))
+        .collect();
+    if changed_metcons.is_empty() {
+        return Ok(());
+    }
+
+    let changed_metcon_ids: Vec<_> = changed_metcons.keys().copied().collect();
+    let metcon_sessions = MetconSessionDb::get_by_metcons(&changed_metcon_ids, db).await?;
+    let mut errors = vec![];
+    for (metcon_id, (index, metcon)) in changed_metcons {
+        let metcon_sessions: Vec<_> = metcon_sessions
+            .iter()
+            .filter(|metcon_session| metcon_session.metcon_id == metcon_id)
+            .cloned()
+            .collect();
+        if check_metcon_session_scores(&metcon_sessions, &HashMap::from([(metcon_id, metcon)]))
+            .is_err()
+        {
+            errors.push(FieldError::new(
+                index,
+                "metcon_type",
+                "does not match the score of the existing metcon sessions",
+            ));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Checks that the planned workouts of the metcon sessions belong to the user, that the score of
+/// the metcon sessions matches the [`MetconType`] of their metcon and that rx sessions have no
+/// scalings.
+///
+/// Deleted sessions are skipped, so that sessions that were created before the check can still be
+/// deleted. Sessions of metcons that do not exist are skipped, they are rejected by the database.
+async fn check_metcon_sessions(
+    metcon_sessions: &[MetconSession],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let planned_workout_ids: Vec<_> = metcon_sessions
+        .iter()
+        .filter_map(|metcon_session| metcon_session.planned_workout_id)
+        .collect();
+    check_references::<PlannedWorkoutDb>(&planned_workout_ids, user_id, db).await?;
+
+    let metcon_ids: Vec<_> = metcon_sessions
+        .iter()
+        .filter(|metcon_session| !metcon_session.deleted)
+        .map(|metcon_session| metcon_session.metcon_id)
+        .collect();
+    if metcon_ids.is_empty() {
+        return Ok(());
+    }
+    let metcons = MetconDb::get_by_ids(&metcon_ids, db).await?;
+    check_metcon_session_scores(metcon_sessions, &metcons)?;
+
+    let rx_metcon_session_ids: Vec<_> = metcon_sessions
+        .iter()
+        .filter(|metcon_session| !metcon_session.deleted && metcon_session.rx)
+        .map(|metcon_session| metcon_session.id)
+        .collect();
+    let metcon_session_scalings =
+        MetconSessionScalingDb::get_by_metcon_sessions(&rx_metcon_session_ids, db).await?;
+    let errors: Vec<_> = metcon_sessions
+        .iter()
+        .enumerate()
+        .filter(|(_, metcon_session)| rx_metcon_session_ids.contains(&metcon_session.id))
+        .filter(|(_, metcon_session)| {
+            metcon_session_scalings
+                .iter()
+                .any(|scaling| scaling.metcon_session_id == metcon_session.id)
+        })
+        .map(|(index, _)| {
+            FieldError::new(index, "rx", "must not be set for a scaled metcon session")
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Checks that the score of the metcon sessions matches the [`MetconType`] of their metcon in
+/// `metcons`.
+fn check_metcon_session_scores(
+    metcon_sessions: &[MetconSession],
+    metcons: &HashMap<MetconId, Metcon>,
+) -> HandlerResult<()> {
+    let mut errors = vec![];
+    for (index, metcon_session) in metcon_sessions.iter().enumerate() {
+        if metcon_session.deleted {
+            continue;
+        }
+        let Some(metcon) = metcons.get(&metcon_session.metcon_id) else {
+            continue;
+        };
+        let finished = metcon_session.time.is_some();
+        let (time, rounds, reps, round_reps) = match metcon.metcon_type {
+            MetconType::Amrap => (Some(false), Some(true), Some(true), Some(false)),
+            MetconType::Emom => (Some(false), Some(false), Some(false), Some(false)),
+            MetconType::ForTime | MetconType::Chipper if finished => {
+                (None, Some(false), Some(false), Some(false))
+            }
+            MetconType::ForTime => (None, None, None, Some(false)),
+            MetconType::Tabata => (Some(false), Some(false), Some(true), None),
+            MetconType::DeathBy => (Some(false), Some(true), None, Some(false)),
+            MetconType::Ladder if finished => (
+                Some(metcon.rounds.is_some()),
+                Some(false),
+                Some(false),
+                Some(false),
+            ),
+            MetconType::Ladder => (None, Some(true), Some(true), Some(false)),
+            MetconType::Chipper => (None, Some(false), Some(true), Some(false)),
+        };
+        check_field(&mut errors, index, "time", finished, time);
+        check_field(
+            &mut errors,
+            index,
+            "rounds",
+            metcon_session.rounds.is_some(),
+            rounds,
+        );
+        check_field(
+            &mut errors,
+            index,
+            "reps",
+            metcon_session.reps.is_some(),
+            reps,
+        );
+        check_field(
+            &mut errors,
+            index,
+            "round_reps",
+            metcon_session.round_reps.is_some(),
+            round_reps,
+        );
+
+        if let Some(session_round_reps) = &metcon_session.round_reps {
+            if metcon
+                .rounds
+                .is_some_and(|rounds| usize::try_from(rounds) != Ok(session_round_reps.len()))
+            {
+                errors.push(FieldError::new(
+                    index,
+                    "round_reps",
+                    "must contain the reps of every round",
+                ));
+            }
+            if metcon_session
+                .reps
+                .is_some_and(|reps| reps != session_round_reps.iter().sum::<i32>())
+            {
+                errors.push(FieldError::new(index, "round_reps", "must add up to reps"));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Checks that the metcon movements and the movements of the scalings belong to the user or are
+/// public and that the scalings are not at [`ScalingLevel::Rx`].
+///
+/// Deleted scalings are skipped, so that scalings that were created before the check can still be
+/// deleted.
+async fn check_metcon_movement_scalings(
+    metcon_movement_scalings: &[MetconMovementScaling],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let (metcon_movement_ids, movement_ids): (Vec<_>, Vec<_>) = metcon_movement_scalings
+        .iter()
+        .filter(|metcon_movement_scaling| !metcon_movement_scaling.deleted)
+        .map(|metcon_movement_scaling| {
+            (
+                metcon_movement_scaling.metcon_movement_id,
+                metcon_movement_scaling.movement_id,
+            )
+        })
+        .unzip();
+    check_optional_references::<MetconMovementDb>(&metcon_movement_ids, user_id, db).await?;
+    check_optional_references::<MovementDb>(&movement_ids, user_id, db).await?;
+
+    let errors: Vec<_> = metcon_movement_scalings
+        .iter()
+        .enumerate()
+        .filter(|(_, metcon_movement_scaling)| {
+            !metcon_movement_scaling.deleted
+                && metcon_movement_scaling.scaling_level == ScalingLevel::Rx
+        })
+        .map(|(index, _)| {
+            FieldError::new(
+                index,
+                "scaling_level",
+                "must not be rx, the metcon movement itself is performed rx",
+            )
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Checks that the metcon sessions of the scalings belong to the user and that the scaled metcon
+/// movements belong to the metcon of the session.
+///
+/// Deleted scalings are skipped, so that scalings that were created before the check can still be
+/// deleted.
+async fn check_metcon_session_scalings(
+    metcon_session_scalings: &[MetconSessionScaling],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let (metcon_session_ids, metcon_movement_ids): (Vec<_>, Vec<_>) = metcon_session_scalings
+        .iter()
+        .filter(|metcon_session_scaling| !metcon_session_scaling.deleted)
+        .map(|metcon_session_scaling| {
+            (
+                metcon_session_scaling.metcon_session_id,
+                metcon_session_scaling.metcon_movement_id,
+            )
+        })
+        .unzip();
+    if metcon_session_ids.is_empty() {
+        return Ok(());
+    }
+    check_references::<MetconSessionDb>(&metcon_session_ids, user_id, db).await?;
+    let metcon_sessions = MetconSessionDb::get_by_ids(&metcon_session_ids, db).await?;
+    let metcon_movements = MetconMovementDb::get_by_ids(&metcon_movement_ids, db).await?;
+    check_metcon_session_scaling_movements(
+        metcon_session_scalings,
+        &metcon_sessions,
+        &metcon_movements,
+    )
+}
+
+/// Checks that the scalings are not at [`ScalingLevel::Rx`], that their metcon sessions in
+/// `metcon_sessions` were not performed rx and that their metcon movements in `metcon_movements`
+/// belong to the metcon of their metcon sessions.
+fn check_metcon_session_scaling_movements(
+    metcon_session_scalings: &[MetconSessionScaling],
+    metcon_sessions: &HashMap<MetconSessionId, MetconSession>,
+    metcon_movements: &HashMap<MetconMovementId, MetconMovement>,
+) -> HandlerResult<()> {
+    let mut errors = vec![];
+    for (index, metcon_session_scaling) in metcon_session_scalings.iter().enumerate() {
+        if metcon_session_scaling.deleted {
+            continue;
+        }
+        if metcon_session_scaling.scaling_level == ScalingLevel::Rx {
+            errors.push(FieldError::new(
+                index,
+                "scaling_level",
+                "must not be rx, movements without scaling are performed rx",
+            ));
+        }
+        if let (Some(metcon_session), Some(metcon_movement)) = (
+            metcon_sessions.get(&metcon_session_scaling.metcon_session_id),
+            metcon_movements.get(&metcon_session_scaling.metcon_movement_id),
+        ) {
+            if metcon_session.rx {
+                errors.push(FieldError::new(
+                    index,
+                    "metcon_session_id",
+                    "must not be an rx metcon session",
+                ));
+            }
+            if metcon_movement.metcon_id != metcon_session.metcon_id {
+                errors.push(FieldError::new(
+                    index,
+                    "metcon_movement_id",
+                    "must belong to the metcon of the metcon session",
+                ));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
 pub async fn create_metcon_sessions(
     auth: AuthUserOrAP,
     mut db: DbConn,
@@ -19,10 +412,12 @@ pub async fn create_metcon_sessions(
     match metcon_sessions {
         UnverifiedSingleOrVec::Single(metcon_session) => {
             let metcon_session = metcon_session.verify_user_ap_create(auth)?;
+            check_metcon_sessions(std::slice::from_ref(&metcon_session), *auth, &mut db).await?;
             MetconSessionDb::create(&metcon_session, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(metcon_sessions) => {
             let metcon_sessions = metcon_sessions.verify_user_ap_create(auth)?;
+            check_metcon_sessions(&metcon_sessions, *auth, &mut db).await?;
             MetconSessionDb::create_multiple(&metcon_sessions, &mut db).await?;
         }
     }
@@ -59,10 +454,12 @@ pub async fn update_metcon_sessions(
     match metcon_sessions {
         UnverifiedSingleOrVec::Single(metcon_session) => {
             let metcon_session = metcon_session.verify_user_ap_update(auth, &mut db).await?;
+            check_metcon_sessions(std::slice::from_ref(&metcon_session), *auth, &mut db).await?;
             MetconSessionDb::update(&metcon_session, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(metcon_sessions) => {
             let metcon_sessions = metcon_sessions.verify_user_ap_update(auth, &mut db).await?;
+            check_metcon_sessions(&metcon_sessions, *auth, &mut db).await?;
             MetconSessionDb::update_multiple(&metcon_sessions, &mut db).await?;
         }
     }
@@ -70,6 +467,24 @@ pub async fn update_metcon_sessions(
     Ok(Json(EpochResponse { epoch }))
 }
 
+/// Returns the best session of the user for every metcon and [`ScalingLevel`].
+///
+/// [`MetconType::Emom`] metcons have no score and therefore no record.
+pub async fn get_metcon_records(
+    auth: AuthUserOrAP,
+    Query(MetconIdOption { metcon_id }): Query<MetconIdOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<MetconRecord>>> {
+    let metcon_id = match metcon_id {
+        Some(metcon_id) => Some(metcon_id.verify_user_ap_get(auth, &mut db).await?),
+        None => None,
+    };
+    MetconRecordDb::get_by_user(*auth, metcon_id, &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
+
 pub async fn create_metcons(
     auth: AuthUserOrAP,
     mut db: DbConn,
@@ -78,10 +493,12 @@ pub async fn create_metcons(
     match metcons {
         UnverifiedSingleOrVec::Single(metcon) => {
             let metcon = metcon.verify_user_ap_create(auth)?;
+            check_metcons(std::slice::from_ref(&metcon))?;
             MetconDb::create(&metcon, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(metcons) => {
             let metcons = metcons.verify_user_ap_create(auth)?;
+            check_metcons(&metcons)?;
             MetconDb::create_multiple(&metcons, &mut db).await?;
         }
     }
@@ -115,10 +532,14 @@ pub async fn update_metcons(
     match metcons {
         UnverifiedSingleOrVec::Single(metcon) => {
             let metcon = metcon.verify_user_ap_update(auth, &mut db).await?;
+            check_metcons(std::slice::from_ref(&metcon))?;
+            check_metcon_updates(std::slice::from_ref(&metcon), &mut db).await?;
             MetconDb::update(&metcon, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(metcons) => {
             let metcons = metcons.verify_user_ap_update(auth, &mut db).await?;
+            check_metcons(&metcons)?;
+            check_metcon_updates(&metcons, &mut db).await?;
             MetconDb::update_multiple(&metcons, &mut db).await?;
         }
     }
@@ -183,3 +604,351 @@ pub async fn update_metcon_movements(
     let epoch = MetconMovementDb::get_epoch_by_user_optional(*auth, &mut db).await?;
     Ok(Json(EpochResponse { epoch }))
 }
+
+pub async fn create_metcon_movement_scalings(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(metcon_movement_scalings): Json<UnverifiedSingleOrVec<MetconMovementScaling>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match metcon_movement_scalings {
+        UnverifiedSingleOrVec::Single(metcon_movement_scaling) => {
+            let metcon_movement_scaling = metcon_movement_scaling.verify_user_ap_create(auth)?;
+            check_metcon_movement_scalings(
+                std::slice::from_ref(&metcon_movement_scaling),
+                *auth,
+                &mut db,
+            )
+            .await?;
+            MetconMovementScalingDb::create(&metcon_movement_scaling, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(metcon_movement_scalings) => {
+            let metcon_movement_scalings = metcon_movement_scalings.verify_user_ap_create(auth)?;
+            check_metcon_movement_scalings(&metcon_movement_scalings, *auth, &mut db).await?;
+            MetconMovementScalingDb::create_multiple(&metcon_movement_scalings, &mut db).await?;
+        }
+    }
+    let epoch = MetconMovementScalingDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_metcon_movement_scalings(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<MetconMovementScalingId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<MetconMovementScaling>>> {
+    match id {
+        Some(id) => {
+            let metcon_movement_scaling_id = id.verify_user_ap_get(auth, &mut db).await?;
+            MetconMovementScalingDb::get_by_id(metcon_movement_scaling_id, &mut db)
+                .await
+                .map(|m| vec![m])
+        }
+        None => MetconMovementScalingDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_metcon_movement_scalings(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(metcon_movement_scalings): Json<UnverifiedSingleOrVec<MetconMovementScaling>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match metcon_movement_scalings {
+        UnverifiedSingleOrVec::Single(metcon_movement_scaling) => {
+            let metcon_movement_scaling = metcon_movement_scaling
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_metcon_movement_scalings(
+                std::slice::from_ref(&metcon_movement_scaling),
+                *auth,
+                &mut db,
+            )
+            .await?;
+            MetconMovementScalingDb::update(&metcon_movement_scaling, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(metcon_movement_scalings) => {
+            let metcon_movement_scalings = metcon_movement_scalings
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_metcon_movement_scalings(&metcon_movement_scalings, *auth, &mut db).await?;
+            MetconMovementScalingDb::update_multiple(&metcon_movement_scalings, &mut db).await?;
+        }
+    }
+    let epoch = MetconMovementScalingDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn create_metcon_session_scalings(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(metcon_session_scalings): Json<UnverifiedSingleOrVec<MetconSessionScaling>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match metcon_session_scalings {
+        UnverifiedSingleOrVec::Single(metcon_session_scaling) => {
+            let metcon_session_scaling = metcon_session_scaling.verify_user_ap_create(auth)?;
+            check_metcon_session_scalings(
+                std::slice::from_ref(&metcon_session_scaling),
+                *auth,
+                &mut db,
+            )
+            .await?;
+            MetconSessionScalingDb::create(&metcon_session_scaling, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(metcon_session_scalings) => {
+            let metcon_session_scalings = metcon_session_scalings.verify_user_ap_create(auth)?;
+            check_metcon_session_scalings(&metcon_session_scalings, *auth, &mut db).await?;
+            MetconSessionScalingDb::create_multiple(&metcon_session_scalings, &mut db).await?;
+        }
+    }
+    let epoch = MetconSessionScalingDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_metcon_session_scalings(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<MetconSessionScalingId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<MetconSessionScaling>>> {
+    match id {
+        Some(id) => {
+            let metcon_session_scaling_id = id.verify_user_ap_get(auth, &mut db).await?;
+            MetconSessionScalingDb::get_by_id(metcon_session_scaling_id, &mut db)
+                .await
+                .map(|m| vec![m])
+        }
+        None => MetconSessionScalingDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_metcon_session_scalings(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(metcon_session_scalings): Json<UnverifiedSingleOrVec<MetconSessionScaling>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match metcon_session_scalings {
+        UnverifiedSingleOrVec::Single(metcon_session_scaling) => {
+            let metcon_session_scaling = metcon_session_scaling
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_metcon_session_scalings(
+                std::slice::from_ref(&metcon_session_scaling),
+                *auth,
+                &mut db,
+            )
+            .await?;
+            MetconSessionScalingDb::update(&metcon_session_scaling, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(metcon_session_scalings) => {
+            let metcon_session_scalings = metcon_session_scalings
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_metcon_session_scalings(&metcon_session_scalings, *auth, &mut db).await?;
+            MetconSessionScalingDb::update_multiple(&metcon_session_scalings, &mut db).await?;
+        }
+    }
+    let epoch = MetconSessionScalingDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use sport_log_types::{
+        Metcon, MetconId, MetconMovement, MetconMovementId, MetconSession, MetconSessionId,
+        MetconSessionScaling, MetconSessionScalingId, MetconType, MovementId, ScalingLevel, UserId,
+    };
+
+    use super::{
+        check_metcon_session_scaling_movements, check_metcon_session_scores, check_metcons,
+    };
+    use crate::handler::HandlerResult;
+
+    /// Returns the index and the field of every validation error.
+    fn invalid_fields(result: HandlerResult<()>) -> Vec<(u64, String)> {
+        let Err(error) = result else {
+            return vec![];
+        };
+        let error = serde_json::to_value(error).unwrap();
+        error["message"]["validation"]["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field_error| {
+                (
+                    field_error["index"].as_u64().unwrap(),
+                    field_error["field"].as_str().unwrap().to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    fn metcon(metcon_type: MetconType, rounds: Option<i32>, timecap: Option<i32>) -> Metcon {
+        Metcon {
+            id: MetconId(1),
+            user_id: Some(UserId(1)),
+            name: "metcon".to_owned(),
+            metcon_type,
+            rounds,
+            timecap,
+            description: None,
+            deleted: false,
+        }
+    }
+
+    fn metcon_session(time: Option<i32>, rounds: Option<i32>, reps: Option<i32>) -> MetconSession {
+        MetconSession {
+            id: MetconSessionId(1),
+            user_id: UserId(1),
+            metcon_id: MetconId(1),
+            datetime: Utc::now(),
+            time,
+            rounds,
+            reps,
+            round_reps: None,
+            rx: true,
+            comments: None,
+            planned_workout_id: None,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn check_metcon_rounds_and_timecap() {
+        let metcons = [
+            metcon(MetconType::Amrap, None, Some(600_000)),
+            metcon(MetconType::Amrap, Some(5), None),
+            metcon(MetconType::Ladder, Some(10), Some(600_000)),
+            metcon(MetconType::Chipper, None, None),
+        ];
+        assert_eq!(
+            invalid_fields(check_metcons(&metcons)),
+            [(1, "rounds".to_owned()), (1, "timecap".to_owned())]
+        );
+
+        // deleted metcons are not checked
+        let deleted = Metcon {
+            deleted: true,
+            ..metcons[1].clone()
+        };
+        assert!(check_metcons(&[deleted]).is_ok());
+    }
+
+    #[test]
+    fn check_metcon_session_score() {
+        let metcons = HashMap::from([(MetconId(1), metcon(MetconType::ForTime, Some(3), None))]);
+
+        let metcon_sessions = [
+            // finished
+            metcon_session(Some(300_000), None, None),
+            // not finished within the time cap
+            metcon_session(None, Some(2), Some(10)),
+            // finished but with rounds
+            metcon_session(Some(300_000), Some(3), None),
+        ];
+        assert_eq!(
+            invalid_fields(check_metcon_session_scores(&metcon_sessions, &metcons)),
+            [(2, "rounds".to_owned())]
+        );
+
+        // the round reps of tabata have to match the rounds of the metcon and the reps
+        let tabata = HashMap::from([(
+            MetconId(1),
+            metcon(MetconType::Tabata, Some(3), Some(240_000)),
+        )]);
+        let round_reps = MetconSession {
+            round_reps: Some(vec![5, 5]),
+            ..metcon_session(None, None, Some(12))
+        };
+        assert_eq!(
+            invalid_fields(check_metcon_session_scores(&[round_reps], &tabata)),
+            [(0, "round_reps".to_owned()), (0, "round_reps".to_owned())]
+        );
+
+        // deleted sessions and sessions of unknown metcons are not checked
+        let deleted = MetconSession {
+            deleted: true,
+            ..metcon_sessions[2].clone()
+        };
+        let unknown_metcon = MetconSession {
+            metcon_id: MetconId(2),
+            ..metcon_sessions[2].clone()
+        };
+        assert!(check_metcon_session_scores(&[deleted, unknown_metcon], &metcons).is_ok());
+    }
+
+    #[test]
+    fn check_metcon_session_scaling() {
+        let metcon_sessions = HashMap::from([
+            (
+                MetconSessionId(1),
+                MetconSession {
+                    rx: false,
+                    ..metcon_session(Some(300_000), None, None)
+                },
+            ),
+            (
+                MetconSessionId(2),
+                metcon_session(Some(300_000), None, None),
+            ),
+        ]);
+        let metcon_movement = |id, metcon_id| MetconMovement {
+            id: MetconMovementId(id),
+            user_id: Some(UserId(1)),
+            metcon_id: MetconId(metcon_id),
+            movement_id: MovementId(1),
+            distance_unit: None,
+            movement_number: 0,
+            count: 10,
+            male_weight: None,
+            female_weight: None,
+            deleted: false,
+        };
+        let metcon_movements = HashMap::from([
+            (MetconMovementId(1), metcon_movement(1, 1)),
+            (MetconMovementId(2), metcon_movement(2, 2)),
+        ]);
+        let metcon_session_scaling = |metcon_movement_id, scaling_level| MetconSessionScaling {
+            id: MetconSessionScalingId(1),
+            user_id: UserId(1),
+            metcon_session_id: MetconSessionId(1),
+            metcon_movement_id: MetconMovementId(metcon_movement_id),
+            scaling_level,
+            deleted: false,
+        };
+
+        let metcon_session_scalings = [
+            metcon_session_scaling(1, ScalingLevel::Scaled),
+            // movements without scaling are performed rx
+            metcon_session_scaling(1, ScalingLevel::Rx),
+            // the metcon movement belongs to another metcon
+            metcon_session_scaling(2, ScalingLevel::Foundations),
+            // the metcon session was performed rx
+            MetconSessionScaling {
+                metcon_session_id: MetconSessionId(2),
+                ..metcon_session_scaling(1, ScalingLevel::Scaled)
+            },
+            // deleted scalings are not checked
+            MetconSessionScaling {
+                deleted: true,
+                ..metcon_session_scaling(2, ScalingLevel::Rx)
+            },
+        ];
+        assert_eq!(
+            invalid_fields(check_metcon_session_scaling_movements(
+                &metcon_session_scalings,
+                &metcon_sessions,
+                &metcon_movements
+            )),
+            [
+                (1, "scaling_level".to_owned()),
+                (2, "metcon_movement_id".to_owned()),
+                (3, "metcon_session_id".to_owned())
+            ]
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use axum::http::StatusCode;
 use chrono::{DateTime, Utc};
+use diesel_async::AsyncPgConnection;
 use serde::Deserialize;
+use sport_log_types::UserId;
 
-use crate::db::{Timespan, Unverified};
+use crate::db::{CheckOptionalUserId, CheckUserId, Timespan, Unverified};
 pub use crate::error::*;
 
 mod account;
@@ -12,6 +14,7 @@ mod cardio;
 mod diary_wod;
 mod metcon;
 mod movement;
+mod planned_workout;
 mod platform;
 mod strength;
 mod user;
@@ -23,6 +26,7 @@ pub use cardio::*;
 pub use diary_wod::*;
 pub use metcon::*;
 pub use movement::*;
+pub use planned_workout::*;
 pub use platform::*;
 pub use strength::*;
 pub use user::*;
@@ -91,3 +95,43 @@ fn check_password(password: &str) -> HandlerResult<()> {
         )))
     }
 }
+
+/// Checks that the entries with an id in `ids` belong to the user.
+///
+/// The verification of an entry only checks its own `user_id`, so the entries it references have
+/// to be checked separately.
+async fn check_references<D>(
+    ids: &[D::Id],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()>
+where
+    D: CheckUserId,
+    D::Id: Sync,
+{
+    if D::check_user_ids(ids, user_id, db).await? {
+        Ok(())
+    } else {
+        Err(HandlerError::from(StatusCode::FORBIDDEN))
+    }
+}
+
+/// Checks that the entries with an id in `ids` belong to the user or are public.
+///
+/// This is the equivalent of [`check_references`] for entries that can be predefined.
+async fn check_optional_references<D>(
+    ids: &[D::Id],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()>
+where
+    D: CheckOptionalUserId,
+    D::Id: Copy,
+{
+    for &id in ids {
+        if !D::check_optional_user_id(id, user_id, db).await? {
+            return Err(HandlerError::from(StatusCode::FORBIDDEN));
+        }
+    }
+    Ok(())
+}
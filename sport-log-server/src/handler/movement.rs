@@ -1,13 +1,25 @@
-use axum::{extract::Query, Json};
-use sport_log_types::{EpochResponse, Movement, MovementId};
+use axum::{extract::Query, http::StatusCode, Json};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use sport_log_types::{
+    EpochResponse, Movement, MovementId, MuscleGroupVolume, TrainingVolume, VolumePeriod,
+};
 
 use crate::{
     auth::*,
     db::*,
-    handler::{HandlerResult, IdOption, UnverifiedSingleOrVec},
+    handler::{
+        ErrorMessage, HandlerError, HandlerResult, IdOption, TimeSpanOption, UnverifiedSingleOrVec,
+    },
     state::DbConn,
 };
 
+#[derive(Debug, Deserialize)]
+pub struct TrainingVolumeQuery {
+    period: VolumePeriod,
+    timezone: Option<String>,
+}
+
 pub async fn create_movements(
     auth: AuthUserOrAP,
     mut db: DbConn,
@@ -63,3 +75,61 @@ pub async fn update_movements(
     let epoch = MovementDb::get_epoch_by_user_optional(*auth, &mut db).await?;
     Ok(Json(EpochResponse { epoch }))
 }
+
+fn check_timezone(timezone: Option<&str>) -> HandlerResult<()> {
+    match timezone {
+        Some(timezone) if timezone.parse::<Tz>().is_err() => Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: format!("unknown time zone {timezone}"),
+            },
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the training volume of the user for every movement and calendar week or month.
+///
+/// The calendar periods are determined in `timezone` which defaults to the time zone of the user.
+pub async fn get_training_volume(
+    auth: AuthUserOrAP,
+    Query(TrainingVolumeQuery { period, timezone }): Query<TrainingVolumeQuery>,
+    Query(time_span_option): Query<TimeSpanOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<TrainingVolume>>> {
+    check_timezone(timezone.as_deref())?;
+
+    TrainingVolumeDb::get_by_user(
+        *auth,
+        time_span_option.into(),
+        period,
+        timezone.as_deref(),
+        &mut db,
+    )
+    .await
+    .map(Json)
+    .map_err(Into::into)
+}
+
+/// Returns the training volume of the user for every muscle group and calendar week or month.
+///
+/// The calendar periods are determined in `timezone` which defaults to the time zone of the user.
+pub async fn get_muscle_group_volume(
+    auth: AuthUserOrAP,
+    Query(TrainingVolumeQuery { period, timezone }): Query<TrainingVolumeQuery>,
+    Query(time_span_option): Query<TimeSpanOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<MuscleGroupVolume>>> {
+    check_timezone(timezone.as_deref())?;
+
+    TrainingVolumeDb::get_by_muscle_group(
+        *auth,
+        time_span_option.into(),
+        period,
+        timezone.as_deref(),
+        &mut db,
+    )
+    .await
+    .map(Json)
+    .map_err(Into::into)
+}
@@ -0,0 +1,116 @@
+use std::slice;
+
+use axum::{extract::Query, Json};
+use diesel_async::AsyncPgConnection;
+use sport_log_types::{EpochResponse, PlanCompliance, PlannedWorkout, PlannedWorkoutId, UserId};
+
+use crate::{
+    auth::AuthUserOrAP,
+    db::*,
+    handler::{
+        check_optional_references, HandlerResult, IdOption, TimeSpanOption, UnverifiedSingleOrVec,
+    },
+    state::DbConn,
+};
+
+/// Checks that the metcons, movements and strength templates of the planned workouts are
+/// predefined or belong to the user.
+async fn check_planned_workouts(
+    planned_workouts: &[PlannedWorkout],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let metcon_ids: Vec<_> = planned_workouts
+        .iter()
+        .filter_map(|planned_workout| planned_workout.metcon_id)
+        .collect();
+    check_optional_references::<MetconDb>(&metcon_ids, user_id, db).await?;
+    let movement_ids: Vec<_> = planned_workouts
+        .iter()
+        .filter_map(|planned_workout| planned_workout.movement_id)
+        .collect();
+    check_optional_references::<MovementDb>(&movement_ids, user_id, db).await?;
+    let strength_template_ids: Vec<_> = planned_workouts
+        .iter()
+        .filter_map(|planned_workout| planned_workout.strength_template_id)
+        .collect();
+    check_optional_references::<StrengthTemplateDb>(&strength_template_ids, user_id, db).await
+}
+
+pub async fn create_planned_workouts(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(planned_workouts): Json<UnverifiedSingleOrVec<PlannedWorkout>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match planned_workouts {
+        UnverifiedSingleOrVec::Single(planned_workout) => {
+            let planned_workout = planned_workout.verify_user_ap_create(auth)?;
+            check_planned_workouts(slice::from_ref(&planned_workout), *auth, &mut db).await?;
+            PlannedWorkoutDb::create(&planned_workout, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(planned_workouts) => {
+            let planned_workouts = planned_workouts.verify_user_ap_create(auth)?;
+            check_planned_workouts(&planned_workouts, *auth, &mut db).await?;
+            PlannedWorkoutDb::create_multiple(&planned_workouts, &mut db).await?;
+        }
+    }
+    let epoch = PlannedWorkoutDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_planned_workouts(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<PlannedWorkoutId>>>,
+    Query(time_span_option): Query<TimeSpanOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<PlannedWorkout>>> {
+    match id {
+        Some(id) => {
+            let planned_workout_id = id.verify_user_ap_get(auth, &mut db).await?;
+            PlannedWorkoutDb::get_by_id(planned_workout_id, &mut db)
+                .await
+                .map(|p| vec![p])
+        }
+        None => {
+            PlannedWorkoutDb::get_by_user_and_timespan(*auth, time_span_option.into(), &mut db)
+                .await
+        }
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_planned_workouts(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(planned_workouts): Json<UnverifiedSingleOrVec<PlannedWorkout>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match planned_workouts {
+        UnverifiedSingleOrVec::Single(planned_workout) => {
+            let planned_workout = planned_workout.verify_user_ap_update(auth, &mut db).await?;
+            check_planned_workouts(slice::from_ref(&planned_workout), *auth, &mut db).await?;
+            PlannedWorkoutDb::update(&planned_workout, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(planned_workouts) => {
+            let planned_workouts = planned_workouts
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_planned_workouts(&planned_workouts, *auth, &mut db).await?;
+            PlannedWorkoutDb::update_multiple(&planned_workouts, &mut db).await?;
+        }
+    }
+    let epoch = PlannedWorkoutDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Returns which of the planned workouts in the time span have been completed or missed.
+pub async fn get_plan_compliance(
+    auth: AuthUserOrAP,
+    Query(time_span_option): Query<TimeSpanOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<PlanCompliance>> {
+    PlannedWorkoutDb::get_compliance(*auth, time_span_option.into(), &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
@@ -1,15 +1,139 @@
-use axum::{extract::Query, Json};
+use std::{collections::HashMap, slice};
+
+use axum::{extract::Query, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection, AsyncPgConnection};
+use rand_core::{OsRng, RngCore};
+use serde::Deserialize;
 use sport_log_types::{
-    Eorm, EpochResponse, StrengthSession, StrengthSessionId, StrengthSet, StrengthSetId,
+    round_weight, Eorm, EormPercentage, EpochResponse, MovementId, PlannedWorkoutId,
+    ProgressionParameters, ProgressionScheme, StrengthBlock, StrengthBlockId, StrengthProgram,
+    StrengthProgramId, StrengthProgramSet, StrengthProgramSetId, StrengthRecords, StrengthSession,
+    StrengthSessionId, StrengthSet, StrengthSetId, StrengthTemplate, StrengthTemplateId,
+    StrengthTemplateMovement, StrengthTemplateMovementId, SuggestedSet, UserEorm, UserEormId,
+    UserId,
 };
 
 use crate::{
     auth::AuthUserOrAP,
     db::*,
-    handler::{HandlerResult, IdOption, TimeSpanOption, UnverifiedSingleOrVec},
+    handler::{
+        check_optional_references, check_references, ErrorMessage, FieldError, HandlerError,
+        HandlerResult, IdOption, TimeSpanOption, UnverifiedSingleOrVec,
+    },
     state::DbConn,
 };
 
+#[derive(Debug, Deserialize)]
+pub struct MovementIdOption {
+    movement_id: Option<UnverifiedId<MovementId>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StrengthSuggestionQuery {
+    movement_id: UnverifiedId<MovementId>,
+    scheme: ProgressionScheme,
+    increment: Option<f32>,
+    min_reps: Option<i32>,
+    max_reps: Option<i32>,
+    rpe: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StrengthProgramGenerationQuery {
+    strength_program_id: UnverifiedId<StrengthProgramId>,
+    movement_id: UnverifiedId<MovementId>,
+    training_max: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StrengthTemplateInstantiationQuery {
+    strength_template_id: UnverifiedId<StrengthTemplateId>,
+    datetime: DateTime<Utc>,
+    planned_workout_id: Option<UnverifiedId<PlannedWorkoutId>>,
+}
+
+/// The share of the best estimated one rep max that is used as training max by default.
+const TRAINING_MAX_PERCENTAGE: f32 = 0.9;
+
+pub async fn create_strength_blocks(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_blocks): Json<UnverifiedSingleOrVec<StrengthBlock>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_blocks {
+        UnverifiedSingleOrVec::Single(strength_block) => {
+            let strength_block = strength_block.verify_user_ap_create(auth)?;
+            StrengthBlockDb::create(&strength_block, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_blocks) => {
+            let strength_blocks = strength_blocks.verify_user_ap_create(auth)?;
+            StrengthBlockDb::create_multiple(&strength_blocks, &mut db).await?;
+        }
+    }
+    let epoch = StrengthBlockDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_strength_blocks(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<StrengthBlockId>>>,
+    Query(time_span_option): Query<TimeSpanOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<StrengthBlock>>> {
+    match id {
+        Some(id) => {
+            let strength_block_id = id.verify_user_ap_get(auth, &mut db).await?;
+            StrengthBlockDb::get_by_id(strength_block_id, &mut db)
+                .await
+                .map(|s| vec![s])
+        }
+        None => {
+            StrengthBlockDb::get_by_user_and_timespan(*auth, time_span_option.into(), &mut db).await
+        }
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_strength_blocks(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_blocks): Json<UnverifiedSingleOrVec<StrengthBlock>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_blocks {
+        UnverifiedSingleOrVec::Single(strength_block) => {
+            let strength_block = strength_block.verify_user_ap_update(auth, &mut db).await?;
+            StrengthBlockDb::update(&strength_block, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_blocks) => {
+            let strength_blocks = strength_blocks.verify_user_ap_update(auth, &mut db).await?;
+            StrengthBlockDb::update_multiple(&strength_blocks, &mut db).await?;
+        }
+    }
+    let epoch = StrengthBlockDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Checks that the strength blocks and the planned workouts of the strength sessions belong to the
+/// user.
+async fn check_strength_sessions(
+    strength_sessions: &[StrengthSession],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let strength_block_ids: Vec<_> = strength_sessions
+        .iter()
+        .filter_map(|strength_session| strength_session.strength_block_id)
+        .collect();
+    check_references::<StrengthBlockDb>(&strength_block_ids, user_id, db).await?;
+    let planned_workout_ids: Vec<_> = strength_sessions
+        .iter()
+        .filter_map(|strength_session| strength_session.planned_workout_id)
+        .collect();
+    check_references::<PlannedWorkoutDb>(&planned_workout_ids, user_id, db).await
+}
+
 pub async fn create_strength_sessions(
     auth: AuthUserOrAP,
     mut db: DbConn,
@@ -18,10 +142,12 @@ pub async fn create_strength_sessions(
     match strength_sessions {
         UnverifiedSingleOrVec::Single(strength_session) => {
             let strength_session = strength_session.verify_user_ap_create(auth)?;
+            check_strength_sessions(slice::from_ref(&strength_session), *auth, &mut db).await?;
             StrengthSessionDb::create(&strength_session, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(strength_sessions) => {
             let strength_sessions = strength_sessions.verify_user_ap_create(auth)?;
+            check_strength_sessions(&strength_sessions, *auth, &mut db).await?;
             StrengthSessionDb::create_multiple(&strength_sessions, &mut db).await?;
         }
     }
@@ -61,12 +187,14 @@ pub async fn update_strength_sessions(
             let strength_session = strength_session
                 .verify_user_ap_update(auth, &mut db)
                 .await?;
+            check_strength_sessions(slice::from_ref(&strength_session), *auth, &mut db).await?;
             StrengthSessionDb::update(&strength_session, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(strength_sessions) => {
             let strength_sessions = strength_sessions
                 .verify_user_ap_update(auth, &mut db)
                 .await?;
+            check_strength_sessions(&strength_sessions, *auth, &mut db).await?;
             StrengthSessionDb::update_multiple(&strength_sessions, &mut db).await?;
         }
     }
@@ -74,6 +202,45 @@ pub async fn update_strength_sessions(
     Ok(Json(EpochResponse { epoch }))
 }
 
+/// Checks that the optional fields of the strength sets are in their valid ranges.
+///
+/// See [`StrengthSet`] for the meaning of the fields.
+fn check_strength_sets(strength_sets: &[StrengthSet]) -> HandlerResult<()> {
+    let mut errors = vec![];
+    for (index, strength_set) in strength_sets.iter().enumerate() {
+        if strength_set
+            .rpe
+            .is_some_and(|rpe| !(1.0..=10.0).contains(&rpe) || (rpe * 2.0).fract() != 0.0)
+        {
+            errors.push(FieldError::new(
+                index,
+                "rpe",
+                "must be between 1 and 10 in steps of 0.5",
+            ));
+        }
+        if strength_set.rir.is_some_and(|rir| rir < 0) {
+            errors.push(FieldError::new(index, "rir", "must not be negative"));
+        }
+        if strength_set.tempo.as_ref().is_some_and(|tempo| {
+            tempo.len() != 4 || !tempo.chars().all(|c| c.is_ascii_digit() || c == 'X')
+        }) {
+            errors.push(FieldError::new(
+                index,
+                "tempo",
+                "must consist of four digits or X",
+            ));
+        }
+        if strength_set.rest.is_some_and(|rest| rest < 0) {
+            errors.push(FieldError::new(index, "rest", "must not be negative"));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
 pub async fn create_strength_sets(
     auth: AuthUserOrAP,
     mut db: DbConn,
@@ -82,10 +249,12 @@ pub async fn create_strength_sets(
     match strength_sets {
         UnverifiedSingleOrVec::Single(strength_set) => {
             let strength_set = strength_set.verify_user_ap_create(auth)?;
+            check_strength_sets(std::slice::from_ref(&strength_set))?;
             StrengthSetDb::create(&strength_set, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(strength_sets) => {
             let strength_sets = strength_sets.verify_user_ap_create(auth)?;
+            check_strength_sets(&strength_sets)?;
             StrengthSetDb::create_multiple(&strength_sets, &mut db).await?;
         }
     }
@@ -119,10 +288,12 @@ pub async fn update_strength_sets(
     match strength_sets {
         UnverifiedSingleOrVec::Single(strength_set) => {
             let strength_set = strength_set.verify_user_ap_update(auth, &mut db).await?;
+            check_strength_sets(std::slice::from_ref(&strength_set))?;
             StrengthSetDb::update(&strength_set, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(strength_sets) => {
             let strength_sets = strength_sets.verify_user_ap_update(auth, &mut db).await?;
+            check_strength_sets(&strength_sets)?;
             StrengthSetDb::update_multiple(&strength_sets, &mut db).await?;
         }
     }
@@ -130,6 +301,720 @@ pub async fn update_strength_sets(
     Ok(Json(EpochResponse { epoch }))
 }
 
+/// Returns the estimated one rep maxes and the rep maxes of the user for every movement.
+pub async fn get_strength_records(
+    auth: AuthUserOrAP,
+    Query(MovementIdOption { movement_id }): Query<MovementIdOption>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<StrengthRecords>>> {
+    let movement_id = match movement_id {
+        Some(movement_id) => Some(movement_id.verify_user_ap_get(auth, &mut db).await?),
+        None => None,
+    };
+    StrengthRecordDb::get_by_user(*auth, movement_id, &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
+
+/// Returns suggested sets for the next strength session of the movement.
+///
+/// The suggestion is based on the last strength session of the movement and the selected
+/// progression scheme.
+///
+/// If no parameters are given, weights are increased by 2.5 kg, double progression uses a range of
+/// 8 to 12 reps and rpe based progression targets an rpe of 8.
+///
+/// `rpe` has to be between 0 and 10 and `increment` must not be negative.
+pub async fn get_strength_suggestions(
+    auth: AuthUserOrAP,
+    Query(StrengthSuggestionQuery {
+        movement_id,
+        scheme,
+        increment,
+        min_reps,
+        max_reps,
+        rpe,
+    }): Query<StrengthSuggestionQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<SuggestedSet>>> {
+    let parameters = ProgressionParameters {
+        increment: increment.unwrap_or(2.5),
+        min_reps: min_reps.unwrap_or(8),
+        max_reps: max_reps.unwrap_or(12),
+        rpe: rpe.unwrap_or(8.),
+    };
+    if parameters.min_reps < 1 || parameters.min_reps > parameters.max_reps {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "min_reps must be positive and at most max_reps".to_owned(),
+            },
+        )));
+    }
+    if !(0. ..=10.).contains(&parameters.rpe) {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "rpe must be between 0 and 10".to_owned(),
+            },
+        )));
+    }
+    if parameters.increment.is_nan() || parameters.increment < 0. {
+        return Err(HandlerError::from((
+            StatusCode::BAD_REQUEST,
+            ErrorMessage::Other {
+                error: "increment must not be negative".to_owned(),
+            },
+        )));
+    }
+
+    let movement_id = movement_id.verify_user_ap_get(auth, &mut db).await?;
+    let last_sets =
+        StrengthSetDb::get_latest_by_user_and_movement(*auth, movement_id, &mut db).await?;
+    let eorms = EormDb::get_by_user(*auth, &mut db).await?;
+    Ok(Json(scheme.suggest(&last_sets, &eorms, parameters)))
+}
+
 pub async fn get_eorms(_auth: AuthUserOrAP, mut db: DbConn) -> HandlerResult<Json<Vec<Eorm>>> {
     EormDb::get_all(&mut db).await.map(Json).map_err(Into::into)
 }
+
+/// Returns the eorm table according to the [`EormFormula`](sport_log_types::EormFormula) of the
+/// user.
+pub async fn get_eorm_percentages(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<EormPercentage>>> {
+    EormDb::get_by_user(*auth, &mut db)
+        .await
+        .map(Json)
+        .map_err(Into::into)
+}
+
+pub async fn create_user_eorms(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(user_eorms): Json<UnverifiedSingleOrVec<UserEorm>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match user_eorms {
+        UnverifiedSingleOrVec::Single(user_eorm) => {
+            let user_eorm = user_eorm.verify_user_ap_create(auth)?;
+            UserEormDb::create(&user_eorm, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(user_eorms) => {
+            let user_eorms = user_eorms.verify_user_ap_create(auth)?;
+            UserEormDb::create_multiple(&user_eorms, &mut db).await?;
+        }
+    }
+    let epoch = UserEormDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_user_eorms(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<UserEormId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<UserEorm>>> {
+    match id {
+        Some(id) => {
+            let user_eorm_id = id.verify_user_ap_get(auth, &mut db).await?;
+            UserEormDb::get_by_id(user_eorm_id, &mut db)
+                .await
+                .map(|u| vec![u])
+        }
+        None => UserEormDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_user_eorms(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(user_eorms): Json<UnverifiedSingleOrVec<UserEorm>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match user_eorms {
+        UnverifiedSingleOrVec::Single(user_eorm) => {
+            let user_eorm = user_eorm.verify_user_ap_update(auth, &mut db).await?;
+            UserEormDb::update(&user_eorm, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(user_eorms) => {
+            let user_eorms = user_eorms.verify_user_ap_update(auth, &mut db).await?;
+            UserEormDb::update_multiple(&user_eorms, &mut db).await?;
+        }
+    }
+    let epoch = UserEormDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn create_strength_programs(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_programs): Json<UnverifiedSingleOrVec<StrengthProgram>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_programs {
+        UnverifiedSingleOrVec::Single(strength_program) => {
+            let strength_program = strength_program.verify_user_ap_create(auth)?;
+            StrengthProgramDb::create(&strength_program, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_programs) => {
+            let strength_programs = strength_programs.verify_user_ap_create(auth)?;
+            StrengthProgramDb::create_multiple(&strength_programs, &mut db).await?;
+        }
+    }
+    let epoch = StrengthProgramDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_strength_programs(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<StrengthProgramId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<StrengthProgram>>> {
+    match id {
+        Some(id) => {
+            let strength_program_id = id.verify_user_ap_get(auth, &mut db).await?;
+            StrengthProgramDb::get_by_id(strength_program_id, &mut db)
+                .await
+                .map(|s| vec![s])
+        }
+        None => StrengthProgramDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_strength_programs(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_programs): Json<UnverifiedSingleOrVec<StrengthProgram>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_programs {
+        UnverifiedSingleOrVec::Single(strength_program) => {
+            let strength_program = strength_program
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            StrengthProgramDb::update(&strength_program, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_programs) => {
+            let strength_programs = strength_programs
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            StrengthProgramDb::update_multiple(&strength_programs, &mut db).await?;
+        }
+    }
+    let epoch = StrengthProgramDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Checks that the strength programs of the sets are predefined or belong to the user.
+async fn check_strength_program_sets(
+    strength_program_sets: &[StrengthProgramSet],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let strength_program_ids: Vec<_> = strength_program_sets
+        .iter()
+        .map(|strength_program_set| strength_program_set.strength_program_id)
+        .collect();
+    check_optional_references::<StrengthProgramDb>(&strength_program_ids, user_id, db).await
+}
+
+pub async fn create_strength_program_sets(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_program_sets): Json<UnverifiedSingleOrVec<StrengthProgramSet>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_program_sets {
+        UnverifiedSingleOrVec::Single(strength_program_set) => {
+            let strength_program_set = strength_program_set.verify_user_ap_create(auth)?;
+            check_strength_program_sets(slice::from_ref(&strength_program_set), *auth, &mut db)
+                .await?;
+            StrengthProgramSetDb::create(&strength_program_set, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_program_sets) => {
+            let strength_program_sets = strength_program_sets.verify_user_ap_create(auth)?;
+            check_strength_program_sets(&strength_program_sets, *auth, &mut db).await?;
+            StrengthProgramSetDb::create_multiple(&strength_program_sets, &mut db).await?;
+        }
+    }
+    let epoch = StrengthProgramSetDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_strength_program_sets(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<StrengthProgramSetId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<StrengthProgramSet>>> {
+    match id {
+        Some(id) => {
+            let strength_program_set_id = id.verify_user_ap_get(auth, &mut db).await?;
+            StrengthProgramSetDb::get_by_id(strength_program_set_id, &mut db)
+                .await
+                .map(|s| vec![s])
+        }
+        None => StrengthProgramSetDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_strength_program_sets(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_program_sets): Json<UnverifiedSingleOrVec<StrengthProgramSet>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_program_sets {
+        UnverifiedSingleOrVec::Single(strength_program_set) => {
+            let strength_program_set = strength_program_set
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_strength_program_sets(slice::from_ref(&strength_program_set), *auth, &mut db)
+                .await?;
+            StrengthProgramSetDb::update(&strength_program_set, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_program_sets) => {
+            let strength_program_sets = strength_program_sets
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_strength_program_sets(&strength_program_sets, *auth, &mut db).await?;
+            StrengthProgramSetDb::update_multiple(&strength_program_sets, &mut db).await?;
+        }
+    }
+    let epoch = StrengthProgramSetDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Creates a strength session with its sets for every day of the strength program.
+///
+/// The body contains the datetimes of the days of the program ordered by week and day.
+/// The weights are the percentages of the program applied to the training max, which defaults to
+/// 90 % of the best estimated one rep max of the movement.
+pub async fn generate_strength_program(
+    auth: AuthUserOrAP,
+    Query(StrengthProgramGenerationQuery {
+        strength_program_id,
+        movement_id,
+        training_max,
+    }): Query<StrengthProgramGenerationQuery>,
+    mut db: DbConn,
+    Json(datetimes): Json<Vec<DateTime<Utc>>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    let bad_request = |error: String| {
+        HandlerError::from((StatusCode::BAD_REQUEST, ErrorMessage::Other { error }))
+    };
+
+    let strength_program_id = strength_program_id
+        .verify_user_ap_get(auth, &mut db)
+        .await?;
+    let movement_id = movement_id.verify_user_ap_get(auth, &mut db).await?;
+    let strength_program = StrengthProgramDb::get_by_id(strength_program_id, &mut db).await?;
+    let program_sets =
+        StrengthProgramSetDb::get_by_strength_program(strength_program_id, *auth, &mut db).await?;
+
+    let training_max = match training_max {
+        Some(training_max) => training_max,
+        None => StrengthRecordDb::get_best_e1rm(*auth, movement_id, &mut db)
+            .await?
+            .map(|e1rm| e1rm * TRAINING_MAX_PERCENTAGE)
+            .ok_or_else(|| {
+                bad_request(
+                    "the movement has no estimated one rep max, a training_max is required"
+                        .to_owned(),
+                )
+            })?,
+    };
+
+    // the sets are ordered by week, day and set number
+    let days: Vec<_> = program_sets
+        .chunk_by(|a, b| (a.week, a.day) == (b.week, b.day))
+        .collect();
+    if days.len() != datetimes.len() {
+        return Err(bad_request(format!(
+            "the strength program has {} days but {} datetimes were given",
+            days.len(),
+            datetimes.len()
+        )));
+    }
+
+    let mut strength_sessions = vec![];
+    let mut strength_sets = vec![];
+    for (day_sets, datetime) in days.into_iter().zip(datetimes) {
+        let day = format!(
+            "{}: week {}, day {}",
+            strength_program.name, day_sets[0].week, day_sets[0].day
+        );
+        let amrap_sets: Vec<_> = day_sets
+            .iter()
+            .filter(|program_set| program_set.amrap)
+            .map(|program_set| (program_set.set_number + 1).to_string())
+            .collect();
+        let comments = if amrap_sets.is_empty() {
+            day
+        } else {
+            format!(
+                "{day}, as many reps as possible in set {}",
+                amrap_sets.join(", ")
+            )
+        };
+
+        let strength_session = StrengthSession {
+            id: StrengthSessionId(OsRng.next_u64() as i64),
+            user_id: *auth,
+            datetime,
+            movement_id,
+            interval: None,
+            comments: Some(comments),
+            strength_block_id: None,
+            block_position: None,
+            planned_workout_id: None,
+            deleted: false,
+        };
+        strength_sets.extend(day_sets.iter().map(|program_set| StrengthSet {
+            id: StrengthSetId(OsRng.next_u64() as i64),
+            user_id: *auth,
+            strength_session_id: strength_session.id,
+            set_number: program_set.set_number,
+            count: program_set.count,
+            weight: Some(round_weight(training_max * program_set.percentage)),
+            rpe: None,
+            rir: None,
+            tempo: None,
+            rest: None,
+            is_warmup: false,
+            deleted: false,
+        }));
+        strength_sessions.push(strength_session);
+    }
+
+    db.transaction(|db| {
+        async move {
+            StrengthSessionDb::create_multiple(&strength_sessions, db).await?;
+            StrengthSetDb::create_multiple(&strength_sets, db).await
+        }
+        .scope_boxed()
+    })
+    .await?;
+    let epoch = StrengthSessionDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn create_strength_templates(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_templates): Json<UnverifiedSingleOrVec<StrengthTemplate>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_templates {
+        UnverifiedSingleOrVec::Single(strength_template) => {
+            let strength_template = strength_template.verify_user_ap_create(auth)?;
+            StrengthTemplateDb::create(&strength_template, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_templates) => {
+            let strength_templates = strength_templates.verify_user_ap_create(auth)?;
+            StrengthTemplateDb::create_multiple(&strength_templates, &mut db).await?;
+        }
+    }
+    let epoch = StrengthTemplateDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_strength_templates(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<StrengthTemplateId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<StrengthTemplate>>> {
+    match id {
+        Some(id) => {
+            let strength_template_id = id.verify_user_ap_get(auth, &mut db).await?;
+            StrengthTemplateDb::get_by_id(strength_template_id, &mut db)
+                .await
+                .map(|s| vec![s])
+        }
+        None => StrengthTemplateDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_strength_templates(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_templates): Json<UnverifiedSingleOrVec<StrengthTemplate>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_templates {
+        UnverifiedSingleOrVec::Single(strength_template) => {
+            let strength_template = strength_template
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            StrengthTemplateDb::update(&strength_template, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_templates) => {
+            let strength_templates = strength_templates
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            StrengthTemplateDb::update_multiple(&strength_templates, &mut db).await?;
+        }
+    }
+    let epoch = StrengthTemplateDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Checks that the strength templates and movements of the template movements are predefined or
+/// belong to the user.
+async fn check_strength_template_movements(
+    strength_template_movements: &[StrengthTemplateMovement],
+    user_id: UserId,
+    db: &mut AsyncPgConnection,
+) -> HandlerResult<()> {
+    let strength_template_ids: Vec<_> = strength_template_movements
+        .iter()
+        .map(|strength_template_movement| strength_template_movement.strength_template_id)
+        .collect();
+    check_optional_references::<StrengthTemplateDb>(&strength_template_ids, user_id, db).await?;
+    let movement_ids: Vec<_> = strength_template_movements
+        .iter()
+        .map(|strength_template_movement| strength_template_movement.movement_id)
+        .collect();
+    check_optional_references::<MovementDb>(&movement_ids, user_id, db).await
+}
+
+pub async fn create_strength_template_movements(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_template_movements): Json<UnverifiedSingleOrVec<StrengthTemplateMovement>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_template_movements {
+        UnverifiedSingleOrVec::Single(strength_template_movement) => {
+            let strength_template_movement =
+                strength_template_movement.verify_user_ap_create(auth)?;
+            check_strength_template_movements(
+                slice::from_ref(&strength_template_movement),
+                *auth,
+                &mut db,
+            )
+            .await?;
+            StrengthTemplateMovementDb::create(&strength_template_movement, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_template_movements) => {
+            let strength_template_movements =
+                strength_template_movements.verify_user_ap_create(auth)?;
+            check_strength_template_movements(&strength_template_movements, *auth, &mut db).await?;
+            StrengthTemplateMovementDb::create_multiple(&strength_template_movements, &mut db)
+                .await?;
+        }
+    }
+    let epoch = StrengthTemplateMovementDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+pub async fn get_strength_template_movements(
+    auth: AuthUserOrAP,
+    Query(IdOption { id }): Query<IdOption<UnverifiedId<StrengthTemplateMovementId>>>,
+    mut db: DbConn,
+) -> HandlerResult<Json<Vec<StrengthTemplateMovement>>> {
+    match id {
+        Some(id) => {
+            let strength_template_movement_id = id.verify_user_ap_get(auth, &mut db).await?;
+            StrengthTemplateMovementDb::get_by_id(strength_template_movement_id, &mut db)
+                .await
+                .map(|s| vec![s])
+        }
+        None => StrengthTemplateMovementDb::get_by_user(*auth, &mut db).await,
+    }
+    .map(Json)
+    .map_err(Into::into)
+}
+
+pub async fn update_strength_template_movements(
+    auth: AuthUserOrAP,
+    mut db: DbConn,
+    Json(strength_template_movements): Json<UnverifiedSingleOrVec<StrengthTemplateMovement>>,
+) -> HandlerResult<Json<EpochResponse>> {
+    match strength_template_movements {
+        UnverifiedSingleOrVec::Single(strength_template_movement) => {
+            let strength_template_movement = strength_template_movement
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_strength_template_movements(
+                slice::from_ref(&strength_template_movement),
+                *auth,
+                &mut db,
+            )
+            .await?;
+            StrengthTemplateMovementDb::update(&strength_template_movement, &mut db).await?;
+        }
+        UnverifiedSingleOrVec::Vec(strength_template_movements) => {
+            let strength_template_movements = strength_template_movements
+                .verify_user_ap_update(auth, &mut db)
+                .await?;
+            check_strength_template_movements(&strength_template_movements, *auth, &mut db).await?;
+            StrengthTemplateMovementDb::update_multiple(&strength_template_movements, &mut db)
+                .await?;
+        }
+    }
+    let epoch = StrengthTemplateMovementDb::get_epoch_by_user_optional(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+/// Creates a strength session with its sets for every movement of the strength template at
+/// `datetime`.
+///
+/// Percentages of the template are applied to the best estimated one rep max of the movement.
+/// If `planned_workout_id` is set, the strength sessions are linked to the planned workout.
+pub async fn instantiate_strength_template(
+    auth: AuthUserOrAP,
+    Query(StrengthTemplateInstantiationQuery {
+        strength_template_id,
+        datetime,
+        planned_workout_id,
+    }): Query<StrengthTemplateInstantiationQuery>,
+    mut db: DbConn,
+) -> HandlerResult<Json<EpochResponse>> {
+    let strength_template_id = strength_template_id
+        .verify_user_ap_get(auth, &mut db)
+        .await?;
+    let planned_workout_id = match planned_workout_id {
+        Some(planned_workout_id) => {
+            Some(planned_workout_id.verify_user_ap_get(auth, &mut db).await?)
+        }
+        None => None,
+    };
+    let strength_template = StrengthTemplateDb::get_by_id(strength_template_id, &mut db).await?;
+    let template_movements =
+        StrengthTemplateMovementDb::get_by_strength_template(strength_template_id, *auth, &mut db)
+            .await?;
+
+    let best_e1rms: HashMap<MovementId, f32> = if template_movements
+        .iter()
+        .any(|template_movement| template_movement.percentage.is_some())
+    {
+        StrengthRecordDb::get_by_user(*auth, None, &mut db)
+            .await?
+            .into_iter()
+            .filter_map(|records| {
+                let best_e1rm = records
+                    .e1rms
+                    .iter()
+                    .map(|e1rm| e1rm.e1rm)
+                    .reduce(f32::max)?;
+                Some((records.movement_id, best_e1rm))
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let mut strength_sessions = vec![];
+    let mut strength_sets = vec![];
+    for template_movement in template_movements {
+        let weight = match (template_movement.weight, template_movement.percentage) {
+            (Some(weight), _) => Some(weight),
+            (None, Some(percentage)) => {
+                let e1rm = best_e1rms
+                    .get(&template_movement.movement_id)
+                    .ok_or_else(|| {
+                        HandlerError::from((
+                            StatusCode::BAD_REQUEST,
+                            ErrorMessage::Other {
+                                error: "a movement of the strength template has no estimated one \
+                                        rep max"
+                                    .to_owned(),
+                            },
+                        ))
+                    })?;
+                Some(round_weight(e1rm * percentage))
+            }
+            (None, None) => None,
+        };
+
+        let strength_session = StrengthSession {
+            id: StrengthSessionId(OsRng.next_u64() as i64),
+            user_id: *auth,
+            datetime,
+            movement_id: template_movement.movement_id,
+            interval: None,
+            comments: Some(strength_template.name.clone()),
+            strength_block_id: None,
+            block_position: None,
+            planned_workout_id,
+            deleted: false,
+        };
+        strength_sets.extend((0..template_movement.sets).map(|set_number| StrengthSet {
+            id: StrengthSetId(OsRng.next_u64() as i64),
+            user_id: *auth,
+            strength_session_id: strength_session.id,
+            set_number,
+            count: template_movement.count,
+            weight,
+            rpe: None,
+            rir: None,
+            tempo: None,
+            rest: None,
+            is_warmup: false,
+            deleted: false,
+        }));
+        strength_sessions.push(strength_session);
+    }
+
+    db.transaction(|db| {
+        async move {
+            StrengthSessionDb::create_multiple(&strength_sessions, db).await?;
+            StrengthSetDb::create_multiple(&strength_sets, db).await
+        }
+        .scope_boxed()
+    })
+    .await?;
+    let epoch = StrengthSessionDb::get_epoch_by_user(*auth, &mut db).await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+#[cfg(test)]
+mod tests {
+    use sport_log_types::{StrengthSessionId, StrengthSet, StrengthSetId, UserId};
+
+    use super::check_strength_sets;
+
+    fn strength_set() -> StrengthSet {
+        StrengthSet {
+            id: StrengthSetId(1),
+            user_id: UserId(1),
+            strength_session_id: StrengthSessionId(1),
+            set_number: 0,
+            count: 5,
+            weight: Some(100.),
+            rpe: Some(8.5),
+            rir: Some(1),
+            tempo: Some("31X0".to_owned()),
+            rest: Some(120_000),
+            is_warmup: false,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn check_strength_set_fields() {
+        assert!(check_strength_sets(&[strength_set()]).is_ok());
+
+        let invalid = StrengthSet {
+            rpe: Some(8.3),
+            tempo: Some("31X".to_owned()),
+            ..strength_set()
+        };
+        let error = check_strength_sets(&[strength_set(), invalid]).unwrap_err();
+        let error = serde_json::to_value(error).unwrap();
+        let fields: Vec<_> = error["message"]["validation"]["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field_error| {
+                (
+                    field_error["index"].as_u64().unwrap(),
+                    field_error["field"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(fields, [(1, "rpe"), (1, "tempo")]);
+    }
+}
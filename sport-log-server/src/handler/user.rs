@@ -1,14 +1,56 @@
 use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use chrono_tz::Tz;
 use sport_log_types::{EpochResponse, User};
 
 use crate::{
     auth::{AuthAdmin, AuthUser},
     config::Config,
     db::*,
-    handler::{check_password, ErrorMessage, HandlerError, HandlerResult, UnverifiedSingleOrVec},
+    handler::{
+        check_password, ErrorMessage, FieldError, HandlerError, HandlerResult,
+        UnverifiedSingleOrVec,
+    },
     state::DbConn,
 };
 
+/// Checks that the profile fields of the users are valid.
+fn check_profiles(users: &[User]) -> HandlerResult<()> {
+    let today = Utc::now().date_naive();
+    let mut errors = vec![];
+    for (index, user) in users.iter().enumerate() {
+        if user.birthdate.is_some_and(|birthdate| birthdate > today) {
+            errors.push(FieldError::new(
+                index,
+                "birthdate",
+                "must not be in the future",
+            ));
+        }
+        if user.timezone.parse::<Tz>().is_err() {
+            errors.push(FieldError::new(
+                index,
+                "timezone",
+                "must be an IANA time zone name",
+            ));
+        }
+        if user
+            .max_heart_rate
+            .is_some_and(|max_heart_rate| !(1..=250).contains(&max_heart_rate))
+        {
+            errors.push(FieldError::new(
+                index,
+                "max_heart_rate",
+                "must be between 1 and 250",
+            ));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
 pub async fn adm_create_users(
     auth: AuthAdmin,
     mut db: DbConn,
@@ -18,6 +60,7 @@ pub async fn adm_create_users(
         UnverifiedSingleOrVec::Single(user) => {
             let mut user = user.verify_adm(auth)?;
             check_password(&user.password)?;
+            check_profiles(std::slice::from_ref(&user))?;
             UserDb::create(&mut user, &mut db).await?;
         }
         UnverifiedSingleOrVec::Vec(users) => {
@@ -25,6 +68,7 @@ pub async fn adm_create_users(
             for user in &users {
                 check_password(&user.password)?;
             }
+            check_profiles(&users)?;
             UserDb::create_multiple(&mut users, &mut db).await?;
         }
     }
@@ -47,6 +91,7 @@ pub async fn create_user(
 
     let mut user = user.verify_unchecked_create()?;
     check_password(&user.password)?;
+    check_profiles(std::slice::from_ref(&user))?;
     UserDb::create(&mut user, &mut db).await?;
     let epoch = UserDb::get_epoch_by_user(user.id, &mut db).await?;
     Ok(Json(EpochResponse { epoch }))
@@ -66,6 +111,7 @@ pub async fn update_user(
 ) -> HandlerResult<Json<EpochResponse>> {
     let mut user = user.verify_user_update(auth, &mut db).await?;
     check_password(&user.password)?;
+    check_profiles(std::slice::from_ref(&user))?;
     UserDb::update(&mut user, &mut db).await?;
     let epoch = UserDb::get_epoch_by_user(*auth, &mut db).await?;
     Ok(Json(EpochResponse { epoch }))
@@ -0,0 +1,362 @@
+//! Rendering of heatmaps of tracks.
+//!
+//! Heatmaps are rendered in the web mercator projection that is also used by XYZ tile servers.
+//! Coordinates are given in global pixel coordinates at a certain zoom level, so that the world is
+//! `256 * 2^zoom` pixels wide and high.
+
+use std::{
+    collections::HashMap,
+    f64::consts::PI,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use axum::{body::Bytes, extract::FromRef};
+use flate2::{write::ZlibEncoder, Compression};
+use sport_log_types::{Epoch, Position, UserId};
+
+use crate::state::AppState;
+
+pub const TILE_SIZE: u32 = 256;
+pub const MAX_ZOOM: u8 = 18;
+/// The maximal width and height of a rendered heatmap in pixel.
+pub const MAX_SIZE: u32 = 4096;
+
+/// The maximal number of heatmaps that are cached.
+const MAX_CACHE_ENTRIES: usize = 1024;
+
+/// The area of a heatmap in global pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PixelArea {
+    pub zoom: u8,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PixelArea {
+    /// Returns the area that contains the bounding box at the given zoom level.
+    pub fn from_bounding_box(
+        zoom: u8,
+        min_latitude: f64,
+        min_longitude: f64,
+        max_latitude: f64,
+        max_longitude: f64,
+    ) -> Self {
+        let (x_min, y_min) = project(max_latitude, min_longitude, zoom);
+        let (x_max, y_max) = project(min_latitude, max_longitude, zoom);
+        PixelArea {
+            zoom,
+            x: x_min.floor() as u32,
+            y: y_min.floor() as u32,
+            width: (x_max.ceil() - x_min.floor()) as u32,
+            height: (y_max.ceil() - y_min.floor()) as u32,
+        }
+    }
+
+    /// Returns the area of the XYZ tile.
+    pub fn from_tile(zoom: u8, x: u32, y: u32) -> Self {
+        PixelArea {
+            zoom,
+            x: x * TILE_SIZE,
+            y: y * TILE_SIZE,
+            width: TILE_SIZE,
+            height: TILE_SIZE,
+        }
+    }
+}
+
+/// Returns the global pixel coordinates of the position at the given zoom level.
+fn project(latitude: f64, longitude: f64, zoom: u8) -> (f64, f64) {
+    // the web mercator projection is only defined up to about 85.0511 degrees
+    let latitude = latitude.clamp(-85.0511, 85.0511).to_radians();
+    let world_size = f64::from(TILE_SIZE) * 2f64.powi(i32::from(zoom));
+    let x = (longitude + 180.) / 360. * world_size;
+    let y = (1. - latitude.tan().asinh() / PI) / 2. * world_size;
+    (x, y)
+}
+
+/// Returns the parameters of the start and the end of the part of the segment from `start` to
+/// `end` that lies inside the rectangle from `(0, 0)` to `(width, height)`.
+///
+/// If the segment lies completely outside of the rectangle, [`None`] is returned.
+fn clip_segment(
+    (x0, y0): (f64, f64),
+    (x1, y1): (f64, f64),
+    width: f64,
+    height: f64,
+) -> Option<(f64, f64)> {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let (mut t_start, mut t_end) = (0f64, 1f64);
+    // Liang-Barsky clipping against the left, right, top and bottom edge
+    for (p, q) in [(-dx, x0), (dx, width - x0), (-dy, y0), (dy, height - y0)] {
+        if p == 0. {
+            if q < 0. {
+                return None;
+            }
+        } else if p < 0. {
+            t_start = t_start.max(q / p);
+        } else {
+            t_end = t_end.min(q / p);
+        }
+    }
+    (t_start <= t_end).then_some((t_start, t_end))
+}
+
+/// Returns for every pixel of the area how often it is crossed by a track.
+///
+/// The counts are stored row by row.
+fn count_crossings<'a>(tracks: impl Iterator<Item = &'a [Position]>, area: PixelArea) -> Vec<u32> {
+    let (width, height) = (area.width as usize, area.height as usize);
+    let mut counts = vec![0u32; width * height];
+
+    for track in tracks {
+        let points: Vec<_> = track
+            .iter()
+            .map(|pos| {
+                let (x, y) = project(pos.latitude, pos.longitude, area.zoom);
+                (x - f64::from(area.x), y - f64::from(area.y))
+            })
+            .collect();
+
+        for segment in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (segment[0], segment[1]);
+            let Some((t_start, t_end)) = clip_segment(
+                (x0, y0),
+                (x1, y1),
+                f64::from(area.width),
+                f64::from(area.height),
+            ) else {
+                continue;
+            };
+            let (dx, dy) = ((x1 - x0) * (t_end - t_start), (y1 - y0) * (t_end - t_start));
+            let (x0, y0) = (x0 + (x1 - x0) * t_start, y0 + (y1 - y0) * t_start);
+            let steps = dx.abs().max(dy.abs()).ceil().max(1.);
+            // do not count the last point since it is the first one of the next segment
+            for step in 0..steps as usize {
+                let t = step as f64 / steps;
+                let (x, y) = (x0 + dx * t, y0 + dy * t);
+                if x >= 0. && y >= 0. && (x as usize) < width && (y as usize) < height {
+                    counts[y as usize * width + x as usize] += 1;
+                }
+            }
+        }
+    }
+
+    counts
+}
+
+/// Renders the tracks into a heatmap of the given area and returns it as PNG.
+///
+/// Every pixel is colored by the number of times it is crossed by a track.
+pub fn render_heatmap<'a>(tracks: impl Iterator<Item = &'a [Position]>, area: PixelArea) -> Bytes {
+    let counts = count_crossings(tracks, area);
+
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    let scale = f64::from(max_count).ln_1p();
+    let pixels: Vec<[u8; 4]> = counts
+        .into_iter()
+        .map(|count| {
+            if count == 0 {
+                return [0, 0, 0, 0];
+            }
+            // from transparent red for rarely used paths to opaque yellow for frequently used paths
+            let intensity = f64::from(count).ln_1p() / scale;
+            let green = (intensity * 255.).round() as u8;
+            let alpha = (96. + intensity * 159.).round() as u8;
+            [255, green, 0, alpha]
+        })
+        .collect();
+
+    Bytes::from(encode_png(area.width, area.height, &pixels))
+}
+
+/// Encodes the RGBA pixels as PNG.
+fn encode_png(width: u32, height: u32, pixels: &[[u8; 4]]) -> Vec<u8> {
+    fn chunk(png: &mut Vec<u8>, chunk_type: [u8; 4], data: &[u8]) {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(&chunk_type);
+        png.extend_from_slice(data);
+        let crc = crc32fast::hash(&png[start..]);
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // bit depth 8, color type RGBA, default compression, default filter, no interlace
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks(width as usize) {
+        // filter type none
+        let mut scanline = vec![0];
+        scanline.extend(row.iter().flatten());
+        // writing to a vec can not fail
+        encoder.write_all(&scanline).unwrap();
+    }
+    let data = encoder.finish().unwrap();
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, *b"IHDR", &header);
+    chunk(&mut png, *b"IDAT", &data);
+    chunk(&mut png, *b"IEND", &[]);
+    png
+}
+
+/// Identifies a rendered heatmap.
+///
+/// The epochs of the tracks and privacy zones ensure that outdated heatmaps are not used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeatmapKey {
+    pub user_id: UserId,
+    pub cardio_session_epoch: i64,
+    pub route_epoch: i64,
+    pub privacy_zone_epoch: Option<i64>,
+    pub area: PixelArea,
+}
+
+impl HeatmapKey {
+    pub fn new(
+        user_id: UserId,
+        cardio_session_epoch: Epoch,
+        route_epoch: Epoch,
+        privacy_zone_epoch: Option<Epoch>,
+        area: PixelArea,
+    ) -> Self {
+        HeatmapKey {
+            user_id,
+            cardio_session_epoch: cardio_session_epoch.0,
+            route_epoch: route_epoch.0,
+            privacy_zone_epoch: privacy_zone_epoch.map(|epoch| epoch.0),
+            area,
+        }
+    }
+}
+
+/// A cache for rendered heatmaps.
+#[derive(Debug, Clone, Default)]
+pub struct HeatmapCache(Arc<Mutex<HashMap<HeatmapKey, Bytes>>>);
+
+impl HeatmapCache {
+    pub fn get(&self, key: &HeatmapKey) -> Option<Bytes> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: HeatmapKey, heatmap: Bytes) {
+        let mut cache = self.0.lock().unwrap();
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            // outdated heatmaps can not be identified, so the whole cache is cleared
+            cache.clear();
+        }
+        cache.insert(key, heatmap);
+    }
+}
+
+impl FromRef<AppState> for HeatmapCache {
+    fn from_ref(state: &AppState) -> Self {
+        state.heatmap_cache.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+    use sport_log_types::Position;
+
+    use super::{clip_segment, count_crossings, encode_png, project, PixelArea};
+
+    fn position(latitude: f64, longitude: f64) -> Position {
+        Position {
+            latitude,
+            longitude,
+            elevation: 0.,
+            distance: 0.,
+            time: 0,
+        }
+    }
+
+    #[test]
+    fn project_center() {
+        assert_eq!(project(0., 0., 0), (128., 128.));
+        assert_eq!(project(0., 0., 1), (256., 256.));
+    }
+
+    #[test]
+    fn clip() {
+        // inside
+        assert_eq!(clip_segment((1., 1.), (3., 3.), 4., 4.), Some((0., 1.)));
+        // crossing the left and the right edge
+        assert_eq!(
+            clip_segment((-2., 1.), (6., 1.), 4., 4.),
+            Some((0.25, 0.75))
+        );
+        // outside
+        assert_eq!(clip_segment((-2., 1.), (-1., 3.), 4., 4.), None);
+        assert_eq!(clip_segment((5., 5.), (6., 6.), 4., 4.), None);
+    }
+
+    #[test]
+    fn crossings() {
+        let area = PixelArea {
+            zoom: 0,
+            x: 0,
+            y: 0,
+            width: 256,
+            height: 256,
+        };
+        // a horizontal line through the center of the world that leaves the area
+        let track = [position(0., 0.), position(0., 90.), position(0., 400.)];
+        let counts = count_crossings([track.as_slice()].into_iter(), area);
+        let row = &counts[128 * 256..129 * 256];
+        assert!(row[..128].iter().all(|&count| count == 0));
+        assert!(row[128..].iter().all(|&count| count == 1));
+        assert_eq!(counts.iter().sum::<u32>(), 128);
+
+        // a track far outside of the area does not count
+        let area = PixelArea::from_tile(2, 0, 0);
+        let counts = count_crossings([track.as_slice()].into_iter(), area);
+        assert!(counts.iter().all(|&count| count == 0));
+    }
+
+    #[test]
+    fn png() {
+        let pixels = [
+            [1, 2, 3, 4],
+            [5, 6, 7, 8],
+            [9, 10, 11, 12],
+            [13, 14, 15, 16],
+        ];
+        let png = encode_png(2, 2, &pixels);
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let mut chunks = vec![];
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (chunk_type, data) = (&rest[4..8], &rest[8..8 + length]);
+            let crc = u32::from_be_bytes(rest[8 + length..12 + length].try_into().unwrap());
+            assert_eq!(crc, crc32fast::hash(&rest[4..8 + length]));
+            chunks.push((chunk_type.to_vec(), data.to_vec()));
+            rest = &rest[12 + length..];
+        }
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].0, b"IHDR");
+        assert_eq!(chunks[0].1, [0, 0, 0, 2, 0, 0, 0, 2, 8, 6, 0, 0, 0]);
+        assert_eq!(chunks[1].0, b"IDAT");
+        let mut data = vec![];
+        ZlibDecoder::new(chunks[1].1.as_slice())
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(
+            data,
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 0, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+        assert_eq!(chunks[2], (b"IEND".to_vec(), vec![]));
+    }
+}
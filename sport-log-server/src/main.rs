@@ -31,6 +31,8 @@ use tracing_subscriber::EnvFilter;
 
 use crate::{
     config::Config,
+    elevation::DemCache,
+    heatmap::HeatmapCache,
     state::{AppState, DbPool},
 };
 
@@ -39,8 +41,10 @@ mod macros;
 mod auth;
 mod config;
 mod db;
+mod elevation;
 mod error;
 mod handler;
+mod heatmap;
 mod router;
 mod state;
 #[cfg(test)]
@@ -135,7 +139,12 @@ async fn main() -> ExitCode {
         }
     };
 
-    let state = AppState { db_pool, config };
+    let state = AppState {
+        db_pool,
+        config,
+        heatmap_cache: HeatmapCache::default(),
+        dem_cache: DemCache::default(),
+    };
 
     let router = router::get_router(state);
 
@@ -109,6 +109,12 @@ pub fn get_router(state: AppState) -> Router {
                 .get(get_action_events)
                 .put(update_action_events),
         )
+        .route(
+            STRENGTH_BLOCK,
+            post(create_strength_blocks)
+                .get(get_strength_blocks)
+                .put(update_strength_blocks),
+        )
         .route(
             STRENGTH_SESSION,
             post(create_strength_sessions)
@@ -121,13 +127,52 @@ pub fn get_router(state: AppState) -> Router {
                 .get(get_strength_sets)
                 .put(update_strength_sets),
         )
+        .route(STRENGTH_RECORD, get(get_strength_records))
+        .route(STRENGTH_SUGGESTION, get(get_strength_suggestions))
+        .route(
+            STRENGTH_PROGRAM,
+            post(create_strength_programs)
+                .get(get_strength_programs)
+                .put(update_strength_programs),
+        )
+        .route(
+            STRENGTH_PROGRAM_SET,
+            post(create_strength_program_sets)
+                .get(get_strength_program_sets)
+                .put(update_strength_program_sets),
+        )
+        .route(STRENGTH_PROGRAM_GENERATION, post(generate_strength_program))
+        .route(
+            STRENGTH_TEMPLATE,
+            post(create_strength_templates)
+                .get(get_strength_templates)
+                .put(update_strength_templates),
+        )
+        .route(
+            STRENGTH_TEMPLATE_MOVEMENT,
+            post(create_strength_template_movements)
+                .get(get_strength_template_movements)
+                .put(update_strength_template_movements),
+        )
+        .route(
+            STRENGTH_TEMPLATE_INSTANTIATION,
+            post(instantiate_strength_template),
+        )
         .route(EORM, get(get_eorms))
+        .route(EORM_PERCENTAGE, get(get_eorm_percentages))
+        .route(
+            USER_EORM,
+            post(create_user_eorms)
+                .get(get_user_eorms)
+                .put(update_user_eorms),
+        )
         .route(
             METCON_SESSION,
             post(create_metcon_sessions)
                 .get(get_metcon_sessions)
                 .put(update_metcon_sessions),
         )
+        .route(METCON_RECORD, get(get_metcon_records))
         .route(
             METCON,
             post(create_metcons).get(get_metcons).put(update_metcons),
@@ -138,16 +183,80 @@ pub fn get_router(state: AppState) -> Router {
                 .get(get_metcon_movements)
                 .put(update_metcon_movements),
         )
+        .route(
+            METCON_MOVEMENT_SCALING,
+            post(create_metcon_movement_scalings)
+                .get(get_metcon_movement_scalings)
+                .put(update_metcon_movement_scalings),
+        )
+        .route(
+            METCON_SESSION_SCALING,
+            post(create_metcon_session_scalings)
+                .get(get_metcon_session_scalings)
+                .put(update_metcon_session_scalings),
+        )
         .route(
             CARDIO_SESSION,
             post(create_cardio_sessions)
                 .get(get_cardio_sessions)
                 .put(update_cardio_sessions),
         )
+        .route(
+            CARDIO_SESSION_ELEVATION,
+            post(correct_cardio_session_elevation).delete(revert_cardio_session_elevation),
+        )
+        .route(
+            CARDIO_SESSION_SHARE,
+            post(share_cardio_session).delete(unshare_cardio_session),
+        )
+        .route(SHARED_CARDIO_SESSION, get(get_shared_cardio_session))
+        .route(CARDIO_SESSION_GPX, post(import_cardio_session_gpx))
+        .route(CARDIO_SESSION_FIT, post(import_cardio_session_fit))
+        .route(
+            CARDIO_LAP,
+            post(create_cardio_laps)
+                .get(get_cardio_laps)
+                .put(update_cardio_laps),
+        )
+        .route(
+            MULTISPORT_SESSION,
+            post(create_multisport_sessions)
+                .get(get_multisport_sessions)
+                .put(update_multisport_sessions),
+        )
+        .route(
+            MULTISPORT_SESSION_TOTALS,
+            get(get_multisport_session_totals),
+        )
+        .route(
+            MULTISPORT_LEG,
+            post(create_multisport_legs)
+                .get(get_multisport_legs)
+                .put(update_multisport_legs),
+        )
+        .route(GEAR, post(create_gears).get(get_gears).put(update_gears))
+        .route(GEAR_STATS, get(get_gear_stats))
         .route(
             ROUTE,
             post(create_routes).get(get_routes).put(update_routes),
         )
+        .route(ROUTE_SESSION, get(get_route_sessions))
+        .route(ROUTE_GPX, post(import_route_gpx))
+        .route(
+            ROUTE_SEGMENT,
+            post(create_route_segments)
+                .get(get_route_segments)
+                .put(update_route_segments),
+        )
+        .route(SEGMENT_EFFORT, get(get_segment_efforts))
+        .route(
+            PRIVACY_ZONE,
+            post(create_privacy_zones)
+                .get(get_privacy_zones)
+                .put(update_privacy_zones),
+        )
+        .route(HEATMAP, get(get_heatmap))
+        .route(HEATMAP_TILE, get(get_heatmap_tile))
         .route(
             DIARY,
             post(create_diaries).get(get_diaries).put(update_diaries),
@@ -158,7 +267,16 @@ pub fn get_router(state: AppState) -> Router {
             post(create_movements)
                 .get(get_movements)
                 .put(update_movements),
-        );
+        )
+        .route(TRAINING_VOLUME, get(get_training_volume))
+        .route(MUSCLE_GROUP_VOLUME, get(get_muscle_group_volume))
+        .route(
+            PLANNED_WORKOUT,
+            post(create_planned_workouts)
+                .get(get_planned_workouts)
+                .put(update_planned_workouts),
+        )
+        .route(PLAN_COMPLIANCE, get(get_plan_compliance));
 
     let trace_layer = ServiceBuilder::new()
         .layer(SetSensitiveRequestHeadersLayer::new(iter::once(
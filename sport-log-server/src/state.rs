@@ -8,12 +8,14 @@ use diesel_async::{
     AsyncPgConnection,
 };
 
-use crate::Config;
+use crate::{elevation::DemCache, heatmap::HeatmapCache, Config};
 
 #[derive(Clone)]
 pub struct AppState {
     pub db_pool: DbPool,
     pub config: &'static Config,
+    pub heatmap_cache: HeatmapCache,
+    pub dem_cache: DemCache,
 }
 
 pub type DbPool = Pool<AsyncPgConnection>;
@@ -25,18 +25,35 @@ use rand::Rng;
 use serde::de::DeserializeOwned;
 use sport_log_types::{
     uri::{
-        route_max_version, ACCOUNT_DATA, ADM_PLATFORM, AP_ACTION_PROVIDER, AP_PLATFORM, DIARY, USER,
+        route_max_version, ACCOUNT_DATA, ADM_PLATFORM, AP_ACTION_PROVIDER, AP_PLATFORM,
+        CARDIO_SESSION, CARDIO_SESSION_SHARE, DIARY, GEAR, HEATMAP, METCON,
+        METCON_MOVEMENT_SCALING, METCON_RECORD, METCON_SESSION, METCON_SESSION_SCALING,
+        MUSCLE_GROUP_VOLUME, PLANNED_WORKOUT, PLAN_COMPLIANCE, ROUTE_GPX, ROUTE_SEGMENT,
+        SHARED_CARDIO_SESSION, STRENGTH_PROGRAM_SET, STRENGTH_RECORD, STRENGTH_TEMPLATE_MOVEMENT,
+        USER,
     },
     AccountData, Action, ActionEvent, ActionEventId, ActionId, ActionProvider, ActionProviderId,
-    Diary, DiaryId, Epoch, EpochMap, EpochResponse, Platform, PlatformId, User, UserId,
-    ADMIN_USERNAME, ID_HEADER,
+    CardioSession, CardioSessionId, CardioSessionShare, CardioType, Diary, DiaryId, DistanceUnit,
+    EormFormula, Epoch, EpochMap, EpochResponse, Gear, GearId, GearType, Metcon, MetconId,
+    MetconMovement, MetconMovementId, MetconMovementScaling, MetconMovementScalingId, MetconRecord,
+    MetconSession, MetconSessionId, MetconSessionScaling, MetconSessionScalingId, MetconType,
+    Movement, MovementDimension, MovementId, MuscleGroup, MuscleGroupVolume, PlanCompliance,
+    PlannedWorkout, PlannedWorkoutId, PlannedWorkoutType, Platform, PlatformId, Position,
+    PrivacyZone, PrivacyZoneId, Route, RouteId, RouteSegment, RouteSegmentId, ScalingLevel,
+    StrengthProgram, StrengthProgramId, StrengthProgramSet, StrengthProgramSetId, StrengthRecords,
+    StrengthSession, StrengthSessionId, StrengthSet, StrengthSetId, StrengthTemplate,
+    StrengthTemplateId, StrengthTemplateMovement, StrengthTemplateMovementId, User, UserId,
+    WeightUnit, ADMIN_USERNAME, ID_HEADER,
 };
 use tower::Service;
 
 use crate::{
     config::Config,
     db::*,
-    get_config, router,
+    elevation::DemCache,
+    get_config,
+    heatmap::HeatmapCache,
+    router,
     state::{AppState, DbPool},
 };
 
@@ -47,12 +64,26 @@ static TEST_USER: LazyLock<User> = LazyLock::new(|| User {
     username: String::from("test-user-username-123456789"),
     password: String::from("test-user-Password-123456789"),
     email: String::from("test-user-email-123456789"),
+    eorm_formula: EormFormula::Table,
+    sex: None,
+    birthdate: None,
+    weight_unit: WeightUnit::Kg,
+    distance_unit: DistanceUnit::Km,
+    timezone: String::from("UTC"),
+    max_heart_rate: None,
 });
 static TEST_USER2: LazyLock<User> = LazyLock::new(|| User {
     id: UserId(213_456_789),
     username: String::from("test-user2-username-213456789"),
     password: String::from("test-user2-Password-213456789"),
     email: String::from("test-user2-email-213456789"),
+    eorm_formula: EormFormula::Table,
+    sex: None,
+    birthdate: None,
+    weight_unit: WeightUnit::Kg,
+    distance_unit: DistanceUnit::Km,
+    timezone: String::from("UTC"),
+    max_heart_rate: None,
 });
 static TEST_PLATFORM: LazyLock<Platform> = LazyLock::new(|| Platform {
     id: PlatformId(123_456_789),
@@ -122,6 +153,8 @@ async fn init() -> (Router, DbPool, &'static Config) {
     let state = AppState {
         db_pool: db_pool.clone(),
         config,
+        heatmap_cache: HeatmapCache::default(),
+        dem_cache: DemCache::default(),
     };
 
     let router = router::get_router(state);
@@ -988,6 +1021,50 @@ async fn get_account_data() {
     assert_eq!(account_data.diaries[0].id, TEST_DIARY.id);
 }
 
+#[tokio::test]
+async fn get_account_data_with_old_epoch_map() {
+    let (mut router, db_pool, _) = init().await;
+
+    DiaryDb::create(&TEST_DIARY, &mut db_pool.get().await.unwrap())
+        .await
+        .unwrap();
+
+    // epoch map of a client that does not know about the tables added later
+    let epoch_map = serde_json::json!({
+        "user": "0",
+        "diary": "0",
+        "wod": "0",
+        "movement": "0",
+        "strength_session": "0",
+        "strength_set": "0",
+        "metcon": "0",
+        "metcon_session": "0",
+        "metcon_movement": "0",
+        "cardio_session": "0",
+        "route": "0",
+        "platform": "0",
+        "platform_credential": "0",
+        "action_provider": "0",
+        "action": "0",
+        "action_rule": "0",
+        "action_event": "0",
+    });
+    let auth_header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::get(route_max_version("", ACCOUNT_DATA, None))
+            .header(auth_header.0, auth_header.1)
+            .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+            .body(epoch_map.to_string().into())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let account_data: AccountData = parse_body(response).await;
+    assert_eq!(account_data.diaries.len(), 1);
+}
+
 #[tokio::test]
 async fn epoch_from_create_and_update() {
     let (mut router, _, _) = init().await;
@@ -1032,6 +1109,1386 @@ async fn epoch_from_create_and_update() {
     assert_eq!(epoch_response.epoch, Epoch(epoch.0 + 2));
 }
 
+#[tokio::test]
+async fn privacy_zones_of_cardio_session() {
+    let (mut router, db_pool, _) = init().await;
+
+    // create ActionEvent to ensure access permission for ap
+    let action_event = ActionEvent {
+        id: ActionEventId(rnd()),
+        user_id: TEST_USER.id,
+        action_id: TEST_ACTION.id,
+        datetime: Utc::now() + Duration::try_days(1).unwrap(),
+        arguments: None,
+        enabled: true,
+        deleted: false,
+    };
+    // the first three positions (about 111m apart) lie inside of the privacy zone
+    let privacy_zone = PrivacyZone {
+        id: PrivacyZoneId(rnd()),
+        user_id: TEST_USER.id,
+        name: "home".to_owned(),
+        latitude: 0.,
+        longitude: 0.,
+        radius: 250,
+        deleted: false,
+    };
+    let cardio_session = CardioSession {
+        id: CardioSessionId(rnd()),
+        user_id: TEST_USER.id,
+        movement_id: MovementId(1),
+        cardio_type: CardioType::Training,
+        datetime: Utc::now(),
+        distance: None,
+        ascent: None,
+        descent: None,
+        uncorrected_ascent: None,
+        uncorrected_descent: None,
+        time: None,
+        calories: None,
+        track: Some(
+            (0..10)
+                .map(|i| Position {
+                    latitude: f64::from(i) * 0.001,
+                    longitude: 0.,
+                    elevation: 0.,
+                    distance: f64::from(i) * 111.,
+                    time: i * 1000,
+                })
+                .collect(),
+        ),
+        avg_cadence: None,
+        cadence: None,
+        avg_heart_rate: None,
+        heart_rate: None,
+        avg_power: None,
+        max_power: None,
+        normalized_power: None,
+        power: None,
+        speed: None,
+        temperature: None,
+        route_id: None,
+        gear_id: None,
+        planned_workout_id: None,
+        comments: None,
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        ActionEventDb::create(&action_event, &mut db).await.unwrap();
+        PrivacyZoneDb::create(&privacy_zone, &mut db).await.unwrap();
+        CardioSessionDb::create(&cardio_session, &mut db)
+            .await
+            .unwrap();
+    }
+
+    let id = cardio_session.id.0.to_string();
+    let cardio_session_route = route_max_version("", CARDIO_SESSION, Some(&[("id", &id)]));
+    let [basic_header, user_id_header] =
+        auth_as_headers(&TEST_AP.name, TEST_USER.id.0, &TEST_AP.password);
+
+    // the ap only gets the positions outside of the privacy zone
+    let response = request(
+        &mut router,
+        Request::get(&cardio_session_route)
+            .header(basic_header.0.clone(), basic_header.1.clone())
+            .header(user_id_header.0.clone(), user_id_header.1.clone())
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut ap_cardio_sessions: Vec<CardioSession> = parse_body(response).await;
+    let mut ap_cardio_session = ap_cardio_sessions.pop().unwrap();
+    assert_eq!(ap_cardio_session.track.as_ref().unwrap().len(), 7);
+
+    // sending back the trimmed track keeps the full track
+    ap_cardio_session.comments = Some("updated by ap".to_owned());
+    let response = request(
+        &mut router,
+        Request::put(&route_max_version("", CARDIO_SESSION, None))
+            .header(basic_header.0.clone(), basic_header.1.clone())
+            .header(user_id_header.0.clone(), user_id_header.1.clone())
+            .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+            .body(serde_json::to_string(&ap_cardio_session).unwrap().into())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let stored = CardioSessionDb::get_by_id(cardio_session.id, &mut db_pool.get().await.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(stored.comments.as_deref(), Some("updated by ap"));
+    assert_eq!(stored.track, cardio_session.track);
+
+    // changing the trimmed track is rejected
+    ap_cardio_session.track.as_mut().unwrap().pop();
+    let response = request(
+        &mut router,
+        Request::put(&route_max_version("", CARDIO_SESSION, None))
+            .header(basic_header.0, basic_header.1)
+            .header(user_id_header.0, user_id_header.1)
+            .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+            .body(serde_json::to_string(&ap_cardio_session).unwrap().into())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // share the cardio session
+    let share_route = route_max_version(
+        "",
+        CARDIO_SESSION_SHARE,
+        Some(&[("cardio_session_id", &id)]),
+    );
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::post(&share_route)
+            .header(header.0, header.1)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let share: CardioSessionShare = parse_body(response).await;
+    assert_eq!(share.cardio_session_id, cardio_session.id);
+
+    // the shared cardio session can be retrieved without authentication but without privacy zones
+    let shared_route =
+        route_max_version("", SHARED_CARDIO_SESSION, Some(&[("token", &share.token)]));
+    let response = request(
+        &mut router,
+        Request::get(&shared_route).body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let shared_cardio_session: CardioSession = parse_body(response).await;
+    assert_eq!(shared_cardio_session.id, cardio_session.id);
+    assert_eq!(shared_cardio_session.track.unwrap().len(), 7);
+
+    // unshare the cardio session
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::delete(&share_route)
+            .header(header.0, header.1)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = request(
+        &mut router,
+        Request::get(&shared_route).body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn elevation_correction_of_changed_track() {
+    let (mut router, db_pool, _) = init().await;
+
+    let mut cardio_session = CardioSession {
+        id: CardioSessionId(rnd()),
+        user_id: TEST_USER2.id,
+        movement_id: MovementId(1),
+        cardio_type: CardioType::Training,
+        datetime: Utc::now(),
+        distance: None,
+        ascent: Some(10),
+        descent: Some(10),
+        uncorrected_ascent: Some(20),
+        uncorrected_descent: Some(20),
+        time: None,
+        calories: None,
+        track: Some(
+            (0..10)
+                .map(|i| Position {
+                    latitude: 10. + f64::from(i) * 0.001,
+                    longitude: 10.,
+                    elevation: 100.,
+                    distance: f64::from(i) * 111.,
+                    time: i * 1000,
+                })
+                .collect(),
+        ),
+        avg_cadence: None,
+        cadence: None,
+        avg_heart_rate: None,
+        heart_rate: None,
+        avg_power: None,
+        max_power: None,
+        normalized_power: None,
+        power: None,
+        speed: None,
+        temperature: None,
+        route_id: None,
+        gear_id: None,
+        planned_workout_id: None,
+        comments: None,
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        CardioSessionDb::create(&cardio_session, &mut db)
+            .await
+            .unwrap();
+        CardioSessionDb::set_uncorrected_elevation(cardio_session.id, Some(&[90.; 10]), &mut db)
+            .await
+            .unwrap();
+    }
+
+    let header = auth_header(&TEST_USER2.username, &TEST_USER2.password);
+    let mut update = async |cardio_session: &CardioSession| {
+        let response = request(
+            &mut router,
+            Request::put(&route_max_version("", CARDIO_SESSION, None))
+                .header(header.0.clone(), header.1.clone())
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(serde_json::to_string(cardio_session).unwrap().into())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut db = db_pool.get().await.unwrap();
+        (
+            CardioSessionDb::get_by_id(cardio_session.id, &mut db)
+                .await
+                .unwrap(),
+            CardioSessionDb::get_uncorrected_elevation(cardio_session.id, &mut db)
+                .await
+                .unwrap(),
+        )
+    };
+
+    // the correction is kept as long as the track is not changed
+    cardio_session.comments = Some("updated".to_owned());
+    let (stored, uncorrected_elevation) = update(&cardio_session).await;
+    assert_eq!(stored.uncorrected_ascent, Some(20));
+    assert_eq!(uncorrected_elevation, Some(vec![90.; 10]));
+
+    // the correction belongs to the old track and is discarded
+    for pos in cardio_session.track.as_mut().unwrap() {
+        pos.longitude = 10.001;
+    }
+    let (stored, uncorrected_elevation) = update(&cardio_session).await;
+    assert_eq!(stored.uncorrected_ascent, None);
+    assert_eq!(stored.uncorrected_descent, None);
+    assert_eq!(uncorrected_elevation, None);
+}
+
+#[tokio::test]
+async fn heatmap_without_pixels() {
+    let (mut router, _, _) = init().await;
+
+    // both edges of the bounding box are projected to the same pixel border
+    let heatmap_route = route_max_version(
+        "",
+        HEATMAP,
+        Some(&[
+            ("zoom", "0"),
+            ("min_latitude", "0"),
+            ("min_longitude", "0"),
+            ("max_latitude", "0.00000000000001"),
+            ("max_longitude", "0.00000000000001"),
+        ]),
+    );
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::get(&heatmap_route)
+            .header(header.0, header.1)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn route_gpx_name() {
+    let (mut router, _, _) = init().await;
+
+    let gpx_name = format!("{}{}", rnd(), "x".repeat(100));
+    let gpx = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+  <metadata><name>{gpx_name}</name></metadata>
+  <trk><trkseg><trkpt lat="0" lon="0"></trkpt><trkpt lat="0.001" lon="0"></trkpt></trkseg></trk>
+</gpx>"#
+    );
+    let header = auth_header(&TEST_USER2.username, &TEST_USER2.password);
+    let mut import = async |query: Option<&[(&str, &str)]>| {
+        request(
+            &mut router,
+            Request::post(route_max_version("", ROUTE_GPX, query))
+                .header(header.0.clone(), header.1.clone())
+                .body(gpx.clone().into())
+                .unwrap(),
+        )
+        .await
+    };
+
+    // the name from the gpx file is shortened
+    let response = import(None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let route: Route = parse_body(response).await;
+    assert_eq!(route.name, gpx_name.chars().take(80).collect::<String>());
+
+    // a name that is given explicitly must not be too long
+    let response = import(Some(&[("name", &"y".repeat(81))])).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn gear_of_users() {
+    let (mut router, db_pool, _) = init().await;
+
+    let movement = Movement {
+        id: MovementId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: format!("test-movement-{}", rnd()),
+        description: None,
+        movement_dimension: MovementDimension::Distance,
+        cardio: true,
+        muscle_groups: vec![],
+        deleted: false,
+    };
+    let gear = |user: &User, default_movement_id| Gear {
+        id: GearId(rnd()),
+        user_id: user.id,
+        name: format!("test-gear-{}", rnd()),
+        gear_type: GearType::Shoe,
+        default_movement_id,
+        retirement_distance: None,
+        retired: false,
+        deleted: false,
+    };
+    let own_gear = gear(&TEST_USER, Some(movement.id));
+    {
+        let mut db = db_pool.get().await.unwrap();
+        MovementDb::create(&movement, &mut db).await.unwrap();
+        GearDb::create(&own_gear, &mut db).await.unwrap();
+    }
+
+    let header = auth_header(&TEST_USER2.username, &TEST_USER2.password);
+    let mut create = async |uri: &str, body: String| {
+        request(
+            &mut router,
+            Request::post(route_max_version("", uri, None))
+                .header(header.0.clone(), header.1.clone())
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(body.into())
+                .unwrap(),
+        )
+        .await
+        .status()
+    };
+
+    // the default movement must belong to the user or be predefined
+    let status = create(
+        GEAR,
+        serde_json::to_string(&gear(&TEST_USER2, Some(movement.id))).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let status = create(
+        GEAR,
+        serde_json::to_string(&gear(&TEST_USER2, Some(MovementId(1)))).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // the gear of other users can not be used
+    let cardio_session = CardioSession {
+        id: CardioSessionId(rnd()),
+        user_id: TEST_USER2.id,
+        movement_id: MovementId(1),
+        cardio_type: CardioType::Training,
+        datetime: Utc::now(),
+        distance: None,
+        ascent: None,
+        descent: None,
+        uncorrected_ascent: None,
+        uncorrected_descent: None,
+        time: None,
+        calories: None,
+        track: None,
+        avg_cadence: None,
+        cadence: None,
+        avg_heart_rate: None,
+        heart_rate: None,
+        avg_power: None,
+        max_power: None,
+        normalized_power: None,
+        power: None,
+        speed: None,
+        temperature: None,
+        route_id: None,
+        gear_id: Some(own_gear.id),
+        planned_workout_id: None,
+        comments: None,
+        deleted: false,
+    };
+    let status = create(
+        CARDIO_SESSION,
+        serde_json::to_string(&cardio_session).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn strength_program_sets_of_users() {
+    let (mut router, db_pool, _) = init().await;
+
+    let strength_program = StrengthProgram {
+        id: StrengthProgramId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: "test-strength-program".to_owned(),
+        description: None,
+        deleted: false,
+    };
+    StrengthProgramDb::create(&strength_program, &mut db_pool.get().await.unwrap())
+        .await
+        .unwrap();
+
+    let strength_program_set = |user: &User, strength_program_id| StrengthProgramSet {
+        id: StrengthProgramSetId(rnd()),
+        user_id: Some(user.id),
+        strength_program_id,
+        week: 5,
+        day: 1,
+        set_number: 0,
+        count: 5,
+        percentage: 0.5,
+        amrap: false,
+        deleted: false,
+    };
+
+    // both users can add the same set to a predefined strength program
+    for user in [&*TEST_USER, &*TEST_USER2] {
+        let header = auth_header(&user.username, &user.password);
+        let response = request(
+            &mut router,
+            Request::post(route_max_version("", STRENGTH_PROGRAM_SET, None))
+                .header(header.0, header.1)
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(
+                    serde_json::to_string(&strength_program_set(user, StrengthProgramId(1)))
+                        .unwrap()
+                        .into(),
+                )
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // the sets of other users are not part of the strength program
+    let program_sets = StrengthProgramSetDb::get_by_strength_program(
+        StrengthProgramId(1),
+        TEST_USER.id,
+        &mut db_pool.get().await.unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        program_sets
+            .iter()
+            .filter(|program_set| program_set.week == 5)
+            .count(),
+        1
+    );
+
+    // sets can not be added to the strength programs of other users
+    let header = auth_header(&TEST_USER2.username, &TEST_USER2.password);
+    let response = request(
+        &mut router,
+        Request::post(route_max_version("", STRENGTH_PROGRAM_SET, None))
+            .header(header.0, header.1)
+            .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+            .body(
+                serde_json::to_string(&strength_program_set(&TEST_USER2, strength_program.id))
+                    .unwrap()
+                    .into(),
+            )
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn strength_template_movements_of_users() {
+    let (mut router, db_pool, _) = init().await;
+
+    let strength_template = StrengthTemplate {
+        id: StrengthTemplateId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: "test-strength-template".to_owned(),
+        description: None,
+        deleted: false,
+    };
+    let strength_template2 = StrengthTemplate {
+        id: StrengthTemplateId(rnd()),
+        user_id: Some(TEST_USER2.id),
+        ..strength_template.clone()
+    };
+    let movement = Movement {
+        id: MovementId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: "test-movement".to_owned(),
+        description: None,
+        movement_dimension: MovementDimension::Reps,
+        cardio: false,
+        muscle_groups: vec![],
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        StrengthTemplateDb::create(&strength_template, &mut db)
+            .await
+            .unwrap();
+        StrengthTemplateDb::create(&strength_template2, &mut db)
+            .await
+            .unwrap();
+        MovementDb::create(&movement, &mut db).await.unwrap();
+    }
+
+    let template_movement = |strength_template_id, movement_id| StrengthTemplateMovement {
+        id: StrengthTemplateMovementId(rnd()),
+        user_id: Some(TEST_USER2.id),
+        strength_template_id,
+        movement_id,
+        movement_number: 0,
+        sets: 3,
+        count: 5,
+        weight: None,
+        percentage: None,
+        deleted: false,
+    };
+
+    // the strength template and the movement have to be predefined or belong to the user
+    for (strength_template_id, movement_id, status) in [
+        (strength_template2.id, MovementId(1), StatusCode::OK),
+        (strength_template.id, MovementId(1), StatusCode::FORBIDDEN),
+        (strength_template2.id, movement.id, StatusCode::FORBIDDEN),
+    ] {
+        let header = auth_header(&TEST_USER2.username, &TEST_USER2.password);
+        let response = request(
+            &mut router,
+            Request::post(route_max_version("", STRENGTH_TEMPLATE_MOVEMENT, None))
+                .header(header.0, header.1)
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(
+                    serde_json::to_string(&template_movement(strength_template_id, movement_id))
+                        .unwrap()
+                        .into(),
+                )
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), status);
+    }
+}
+
+#[tokio::test]
+async fn route_segments_of_users() {
+    let (mut router, db_pool, _) = init().await;
+
+    let route = Route {
+        id: RouteId(rnd()),
+        user_id: TEST_USER.id,
+        name: format!("test-route-{}", rnd()),
+        distance: None,
+        ascent: None,
+        descent: None,
+        track: None,
+        marked_positions: None,
+        deleted: false,
+    };
+    RouteDb::create(&route, &mut db_pool.get().await.unwrap())
+        .await
+        .unwrap();
+
+    let route_segment = |user: &User| RouteSegment {
+        id: RouteSegmentId(rnd()),
+        user_id: user.id,
+        route_id: route.id,
+        name: "test-route-segment".to_owned(),
+        track: (0..3)
+            .map(|i| Position {
+                longitude: 0.,
+                latitude: f64::from(i) * 0.001,
+                elevation: 0.,
+                distance: f64::from(i) * 111.,
+                time: i * 1000,
+            })
+            .collect(),
+        deleted: false,
+    };
+
+    for (user, status) in [
+        (&*TEST_USER2, StatusCode::FORBIDDEN),
+        (&*TEST_USER, StatusCode::OK),
+    ] {
+        let header = auth_header(&user.username, &user.password);
+        let response = request(
+            &mut router,
+            Request::post(route_max_version("", ROUTE_SEGMENT, None))
+                .header(header.0, header.1)
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(serde_json::to_string(&route_segment(user)).unwrap().into())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), status);
+    }
+}
+
+#[tokio::test]
+async fn metcon_records() {
+    let (mut router, db_pool, _) = init().await;
+
+    let metcon = Metcon {
+        id: MetconId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: format!("test-metcon-{}", rnd()),
+        metcon_type: MetconType::ForTime,
+        rounds: Some(3),
+        timecap: Some(1_200_000),
+        description: None,
+        deleted: false,
+    };
+    let metcon_session = |time, rounds, reps, rx| MetconSession {
+        id: MetconSessionId(rnd()),
+        user_id: TEST_USER.id,
+        metcon_id: metcon.id,
+        datetime: Utc::now(),
+        time,
+        rounds,
+        reps,
+        round_reps: None,
+        rx,
+        comments: None,
+        planned_workout_id: None,
+        deleted: false,
+    };
+    let metcon_movement = MetconMovement {
+        id: MetconMovementId(rnd()),
+        user_id: Some(TEST_USER.id),
+        metcon_id: metcon.id,
+        movement_id: MovementId(1),
+        distance_unit: None,
+        movement_number: 0,
+        count: 10,
+        male_weight: None,
+        female_weight: None,
+        deleted: false,
+    };
+    let unfinished = metcon_session(None, Some(2), Some(10), true);
+    let slow = metcon_session(Some(900_000), None, None, true);
+    let fast = metcon_session(Some(600_000), None, None, true);
+    let scaled = metcon_session(Some(300_000), None, None, false);
+    let foundations = metcon_session(Some(200_000), None, None, false);
+    let metcon_session_scaling = MetconSessionScaling {
+        id: MetconSessionScalingId(rnd()),
+        user_id: TEST_USER.id,
+        metcon_session_id: foundations.id,
+        metcon_movement_id: metcon_movement.id,
+        scaling_level: ScalingLevel::Foundations,
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        MetconDb::create(&metcon, &mut db).await.unwrap();
+        MetconMovementDb::create(&metcon_movement, &mut db)
+            .await
+            .unwrap();
+        MetconSessionDb::create_multiple(
+            &[
+                unfinished,
+                slow,
+                fast.clone(),
+                scaled.clone(),
+                foundations.clone(),
+            ],
+            &mut db,
+        )
+        .await
+        .unwrap();
+        MetconSessionScalingDb::create(&metcon_session_scaling, &mut db)
+            .await
+            .unwrap();
+    }
+
+    // the fastest session of every scaling level is the record
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::get(route_max_version(
+            "",
+            METCON_RECORD,
+            Some(&[("metcon_id", &metcon.id.0.to_string())]),
+        ))
+        .header(header.0, header.1)
+        .body(Body::empty())
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let metcon_records: Vec<MetconRecord> = parse_body(response).await;
+    let metcon_records: Vec<_> = metcon_records
+        .into_iter()
+        .map(|metcon_record| {
+            assert_eq!(metcon_record.metcon_id, metcon.id);
+            (metcon_record.scaling_level, metcon_record.metcon_session.id)
+        })
+        .collect();
+    assert_eq!(
+        metcon_records,
+        [
+            (ScalingLevel::Rx, fast.id),
+            (ScalingLevel::Scaled, scaled.id),
+            (ScalingLevel::Foundations, foundations.id)
+        ]
+    );
+
+    // the records of metcons of other users can not be requested
+    let header = auth_header(&TEST_USER2.username, &TEST_USER2.password);
+    let response = request(
+        &mut router,
+        Request::get(route_max_version(
+            "",
+            METCON_RECORD,
+            Some(&[("metcon_id", &metcon.id.0.to_string())]),
+        ))
+        .header(header.0, header.1)
+        .body(Body::empty())
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn strength_records() {
+    let (mut router, db_pool, _) = init().await;
+
+    let movement = Movement {
+        id: MovementId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: format!("test-movement-{}", rnd()),
+        description: None,
+        movement_dimension: MovementDimension::Reps,
+        cardio: false,
+        muscle_groups: vec![],
+        deleted: false,
+    };
+    let strength_session = |datetime| StrengthSession {
+        id: StrengthSessionId(rnd()),
+        user_id: TEST_USER.id,
+        datetime,
+        movement_id: movement.id,
+        interval: None,
+        comments: None,
+        strength_block_id: None,
+        block_position: None,
+        planned_workout_id: None,
+        deleted: false,
+    };
+    let strength_set =
+        |strength_session: &StrengthSession, set_number, count, weight, is_warmup| StrengthSet {
+            id: StrengthSetId(rnd()),
+            user_id: TEST_USER.id,
+            strength_session_id: strength_session.id,
+            set_number,
+            count,
+            weight: Some(weight),
+            rpe: None,
+            rir: None,
+            tempo: None,
+            rest: None,
+            is_warmup,
+            deleted: false,
+        };
+    let first = strength_session(Utc::now() - Duration::days(1));
+    let second = strength_session(Utc::now());
+    let percentages = {
+        let mut db = db_pool.get().await.unwrap();
+        MovementDb::create(&movement, &mut db).await.unwrap();
+        StrengthSessionDb::create_multiple(&[second.clone(), first.clone()], &mut db)
+            .await
+            .unwrap();
+        StrengthSetDb::create_multiple(
+            &[
+                strength_set(&first, 0, 1, 150., true),
+                strength_set(&first, 1, 5, 100., false),
+                strength_set(&first, 2, 1, 110., false),
+                strength_set(&second, 0, 3, 120., false),
+            ],
+            &mut db,
+        )
+        .await
+        .unwrap();
+        EormDb::get_by_user(TEST_USER.id, &mut db).await.unwrap()
+    };
+    let percentage = |reps| {
+        percentages
+            .iter()
+            .find(|eorm| eorm.reps == reps)
+            .unwrap()
+            .percentage
+    };
+
+    // the best non warmup set of every session is the estimated one rep max of the session
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::get(route_max_version(
+            "",
+            STRENGTH_RECORD,
+            Some(&[("movement_id", &movement.id.0.to_string())]),
+        ))
+        .header(header.0, header.1)
+        .body(Body::empty())
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let strength_records: Vec<StrengthRecords> = parse_body(response).await;
+    assert_eq!(strength_records.len(), 1);
+    assert_eq!(strength_records[0].movement_id, movement.id);
+    let e1rms: Vec<_> = strength_records[0]
+        .e1rms
+        .iter()
+        .map(|e1rm| (e1rm.strength_session_id, e1rm.e1rm))
+        .collect();
+    assert_eq!(
+        e1rms,
+        [
+            (first.id, (100. / percentage(5)).max(110. / percentage(1))),
+            (second.id, 120. / percentage(3)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn muscle_group_volume() {
+    let (mut router, db_pool, _) = init().await;
+
+    // a new user so that no other sessions are aggregated
+    let user = User {
+        id: UserId(rnd()),
+        username: format!("test-user-{}", rnd()),
+        password: String::from("test-user-Password-123456789"),
+        email: format!("test-user-email-{}", rnd()),
+        ..TEST_USER.clone()
+    };
+    let movement = |muscle_groups| Movement {
+        id: MovementId(rnd()),
+        user_id: Some(user.id),
+        name: format!("test-movement-{}", rnd()),
+        description: None,
+        movement_dimension: MovementDimension::Reps,
+        cardio: false,
+        muscle_groups,
+        deleted: false,
+    };
+    let push_up = movement(vec![MuscleGroup::Chest, MuscleGroup::Arms]);
+    let pull_up = movement(vec![MuscleGroup::Back, MuscleGroup::Arms]);
+    let metcon = Metcon {
+        id: MetconId(rnd()),
+        user_id: Some(user.id),
+        name: format!("test-metcon-{}", rnd()),
+        metcon_type: MetconType::Emom,
+        rounds: Some(2),
+        timecap: Some(120_000),
+        description: None,
+        deleted: false,
+    };
+    let metcon_movement = |movement: &Movement, movement_number, count| MetconMovement {
+        id: MetconMovementId(rnd()),
+        user_id: Some(user.id),
+        metcon_id: metcon.id,
+        movement_id: movement.id,
+        distance_unit: None,
+        movement_number,
+        count,
+        male_weight: None,
+        female_weight: None,
+        deleted: false,
+    };
+    let metcon_session = MetconSession {
+        id: MetconSessionId(rnd()),
+        user_id: user.id,
+        metcon_id: metcon.id,
+        datetime: Utc::now(),
+        time: None,
+        rounds: None,
+        reps: None,
+        round_reps: None,
+        rx: true,
+        comments: None,
+        planned_workout_id: None,
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        UserDb::create(&mut user.clone(), &mut db).await.unwrap();
+        MovementDb::create_multiple(&[push_up.clone(), pull_up.clone()], &mut db)
+            .await
+            .unwrap();
+        MetconDb::create(&metcon, &mut db).await.unwrap();
+        MetconMovementDb::create_multiple(
+            &[
+                metcon_movement(&push_up, 0, 10),
+                metcon_movement(&pull_up, 1, 5),
+            ],
+            &mut db,
+        )
+        .await
+        .unwrap();
+        MetconSessionDb::create(&metcon_session, &mut db)
+            .await
+            .unwrap();
+    }
+
+    // the session is counted once for the arms that are trained by both movements
+    let header = auth_header(&user.username, &user.password);
+    let response = request(
+        &mut router,
+        Request::get(route_max_version(
+            "",
+            MUSCLE_GROUP_VOLUME,
+            Some(&[("period", "Week")]),
+        ))
+        .header(header.0, header.1)
+        .body(Body::empty())
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let volumes: Vec<MuscleGroupVolume> = parse_body(response).await;
+    let volumes: Vec<_> = volumes
+        .into_iter()
+        .map(|volume| {
+            (
+                volume.muscle_group,
+                volume.sessions,
+                volume.sets,
+                volume.reps,
+            )
+        })
+        .collect();
+    assert_eq!(
+        volumes,
+        [
+            (MuscleGroup::Chest, 1, 2, 20),
+            (MuscleGroup::Back, 1, 2, 10),
+            (MuscleGroup::Arms, 1, 4, 30),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn planned_workouts_of_users() {
+    let (mut router, db_pool, _) = init().await;
+
+    let movement = Movement {
+        id: MovementId(rnd()),
+        user_id: Some(TEST_USER2.id),
+        name: format!("test-movement-{}", rnd()),
+        description: None,
+        movement_dimension: MovementDimension::Distance,
+        cardio: true,
+        muscle_groups: vec![],
+        deleted: false,
+    };
+    let planned_workout = |movement_id| PlannedWorkout {
+        id: PlannedWorkoutId(rnd()),
+        user_id: TEST_USER.id,
+        datetime: Utc::now() - Duration::hours(1),
+        planned_workout_type: PlannedWorkoutType::Cardio,
+        metcon_id: None,
+        movement_id: Some(movement_id),
+        strength_template_id: None,
+        distance: Some(10_000),
+        time: Some(3_000_000),
+        comments: None,
+        deleted: false,
+    };
+    let cardio_session = |user: &User, planned_workout_id, distance, time| CardioSession {
+        id: CardioSessionId(rnd()),
+        user_id: user.id,
+        movement_id: MovementId(1),
+        cardio_type: CardioType::Training,
+        datetime: Utc::now(),
+        distance: Some(distance),
+        ascent: None,
+        descent: None,
+        uncorrected_ascent: None,
+        uncorrected_descent: None,
+        time: Some(time),
+        calories: None,
+        track: None,
+        avg_cadence: None,
+        cadence: None,
+        avg_heart_rate: None,
+        heart_rate: None,
+        avg_power: None,
+        max_power: None,
+        normalized_power: None,
+        power: None,
+        speed: None,
+        temperature: None,
+        route_id: None,
+        gear_id: None,
+        planned_workout_id: Some(planned_workout_id),
+        comments: None,
+        deleted: false,
+    };
+    MovementDb::create(&movement, &mut db_pool.get().await.unwrap())
+        .await
+        .unwrap();
+
+    let mut create = async |user: &User, uri: &str, body: String| {
+        let header = auth_header(&user.username, &user.password);
+        request(
+            &mut router,
+            Request::post(route_max_version("", uri, None))
+                .header(header.0, header.1)
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(body.into())
+                .unwrap(),
+        )
+        .await
+        .status()
+    };
+
+    // the movements of other users can not be planned
+    let status = create(
+        &TEST_USER,
+        PLANNED_WORKOUT,
+        serde_json::to_string(&planned_workout(movement.id)).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let planned_workout = planned_workout(MovementId(1));
+    let status = create(
+        &TEST_USER,
+        PLANNED_WORKOUT,
+        serde_json::to_string(&planned_workout).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // sessions can not reference the planned workouts of other users
+    let status = create(
+        &TEST_USER2,
+        CARDIO_SESSION,
+        serde_json::to_string(&cardio_session(
+            &TEST_USER2,
+            planned_workout.id,
+            10_000,
+            2_000_000,
+        ))
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 10.5 km in 51:40 are faster than the planned pace of 10 km in 50:00
+    let status = create(
+        &TEST_USER,
+        CARDIO_SESSION,
+        serde_json::to_string(&cardio_session(
+            &TEST_USER,
+            planned_workout.id,
+            10_500,
+            3_100_000,
+        ))
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::get(route_max_version("", PLAN_COMPLIANCE, None))
+            .header(header.0, header.1)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let plan_compliance: PlanCompliance = parse_body(response).await;
+    let status = plan_compliance
+        .planned_workouts
+        .into_iter()
+        .find(|status| status.planned_workout_id == planned_workout.id)
+        .unwrap();
+    assert!(status.completed);
+    assert_eq!(status.target_met, Some(true));
+}
+
+#[tokio::test]
+async fn metcon_type_of_existing_sessions() {
+    let (mut router, db_pool, _) = init().await;
+
+    let metcon = Metcon {
+        id: MetconId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: format!("test-metcon-{}", rnd()),
+        metcon_type: MetconType::ForTime,
+        rounds: Some(3),
+        timecap: None,
+        description: None,
+        deleted: false,
+    };
+    let metcon_session = MetconSession {
+        id: MetconSessionId(rnd()),
+        user_id: TEST_USER.id,
+        metcon_id: metcon.id,
+        datetime: Utc::now(),
+        time: Some(600_000),
+        rounds: None,
+        reps: None,
+        round_reps: None,
+        rx: true,
+        comments: None,
+        planned_workout_id: None,
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        MetconDb::create(&metcon, &mut db).await.unwrap();
+        MetconSessionDb::create(&metcon_session, &mut db)
+            .await
+            .unwrap();
+    }
+
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let mut update = async |metcon: &Metcon| {
+        request(
+            &mut router,
+            Request::put(&route_max_version("", METCON, None))
+                .header(header.0.clone(), header.1.clone())
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(serde_json::to_string(metcon).unwrap().into())
+                .unwrap(),
+        )
+        .await
+        .status()
+    };
+
+    // the time of the session is not a valid score for an amrap
+    let amrap = Metcon {
+        metcon_type: MetconType::Amrap,
+        rounds: None,
+        timecap: Some(1_200_000),
+        ..metcon.clone()
+    };
+    assert_eq!(update(&amrap).await, StatusCode::BAD_REQUEST);
+
+    // changes that keep the type and the rounds are accepted
+    let renamed = Metcon {
+        name: format!("test-metcon-{}", rnd()),
+        timecap: Some(1_200_000),
+        ..metcon.clone()
+    };
+    assert_eq!(update(&renamed).await, StatusCode::OK);
+
+    // the time of the session is still a valid score with more rounds
+    let more_rounds = Metcon {
+        rounds: Some(5),
+        ..renamed
+    };
+    assert_eq!(update(&more_rounds).await, StatusCode::OK);
+
+    // the metcon can be changed once the session is deleted
+    {
+        let mut db = db_pool.get().await.unwrap();
+        MetconSessionDb::update(
+            &MetconSession {
+                deleted: true,
+                ..metcon_session
+            },
+            &mut db,
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(update(&amrap).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn metcon_scalings_of_users() {
+    let (mut router, db_pool, _) = init().await;
+
+    let movement = Movement {
+        id: MovementId(rnd()),
+        user_id: Some(TEST_USER2.id),
+        name: format!("test-movement-{}", rnd()),
+        description: None,
+        movement_dimension: MovementDimension::Reps,
+        cardio: false,
+        muscle_groups: vec![],
+        deleted: false,
+    };
+    let metcon = Metcon {
+        id: MetconId(rnd()),
+        user_id: Some(TEST_USER.id),
+        name: format!("test-metcon-{}", rnd()),
+        metcon_type: MetconType::ForTime,
+        rounds: Some(3),
+        timecap: None,
+        description: None,
+        deleted: false,
+    };
+    let metcon_movement = MetconMovement {
+        id: MetconMovementId(rnd()),
+        user_id: Some(TEST_USER.id),
+        metcon_id: metcon.id,
+        movement_id: MovementId(1),
+        distance_unit: None,
+        movement_number: 0,
+        count: 10,
+        male_weight: None,
+        female_weight: None,
+        deleted: false,
+    };
+    let metcon_session = MetconSession {
+        id: MetconSessionId(rnd()),
+        user_id: TEST_USER.id,
+        metcon_id: metcon.id,
+        datetime: Utc::now(),
+        time: Some(600_000),
+        rounds: None,
+        reps: None,
+        round_reps: None,
+        rx: true,
+        comments: None,
+        planned_workout_id: None,
+        deleted: false,
+    };
+    {
+        let mut db = db_pool.get().await.unwrap();
+        MovementDb::create(&movement, &mut db).await.unwrap();
+        MetconDb::create(&metcon, &mut db).await.unwrap();
+        MetconMovementDb::create(&metcon_movement, &mut db)
+            .await
+            .unwrap();
+        MetconSessionDb::create(&metcon_session, &mut db)
+            .await
+            .unwrap();
+    }
+    let metcon_movement_scaling = |movement_id, scaling_level| MetconMovementScaling {
+        id: MetconMovementScalingId(rnd()),
+        user_id: Some(TEST_USER.id),
+        metcon_movement_id: metcon_movement.id,
+        scaling_level,
+        movement_id,
+        distance_unit: None,
+        count: 5,
+        male_weight: None,
+        female_weight: None,
+        deleted: false,
+    };
+    let metcon_session_scaling = MetconSessionScaling {
+        id: MetconSessionScalingId(rnd()),
+        user_id: TEST_USER.id,
+        metcon_session_id: metcon_session.id,
+        metcon_movement_id: metcon_movement.id,
+        scaling_level: ScalingLevel::Scaled,
+        deleted: false,
+    };
+
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let mut create = async |uri: &str, body: String| {
+        request(
+            &mut router,
+            Request::post(route_max_version("", uri, None))
+                .header(header.0.clone(), header.1.clone())
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(body.into())
+                .unwrap(),
+        )
+        .await
+        .status()
+    };
+
+    // the movements of other users can not be used as scaling
+    let status = create(
+        METCON_MOVEMENT_SCALING,
+        serde_json::to_string(&metcon_movement_scaling(movement.id, ScalingLevel::Scaled)).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // the metcon movement itself is performed rx
+    let status = create(
+        METCON_MOVEMENT_SCALING,
+        serde_json::to_string(&metcon_movement_scaling(MovementId(1), ScalingLevel::Rx)).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let status = create(
+        METCON_MOVEMENT_SCALING,
+        serde_json::to_string(&metcon_movement_scaling(
+            MovementId(1),
+            ScalingLevel::Scaled,
+        ))
+        .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // rx sessions can not be scaled
+    let status = create(
+        METCON_SESSION_SCALING,
+        serde_json::to_string(&metcon_session_scaling).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    MetconSessionDb::update(
+        &MetconSession {
+            rx: false,
+            ..metcon_session.clone()
+        },
+        &mut db_pool.get().await.unwrap(),
+    )
+    .await
+    .unwrap();
+    let status = create(
+        METCON_SESSION_SCALING,
+        serde_json::to_string(&metcon_session_scaling).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // scaled sessions can not be marked as rx
+    let header = auth_header(&TEST_USER.username, &TEST_USER.password);
+    let response = request(
+        &mut router,
+        Request::put(route_max_version("", METCON_SESSION, None))
+            .header(header.0, header.1)
+            .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+            .body(serde_json::to_string(&metcon_session).unwrap().into())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn user_self_registration() {
     let (mut router, _, config) = init().await;
@@ -1042,6 +2499,13 @@ async fn user_self_registration() {
         username: format!("user{}", user_id.0),
         password: "Password1".to_owned(),
         email: format!("email{}", user_id.0),
+        eorm_formula: EormFormula::Table,
+        sex: None,
+        birthdate: None,
+        weight_unit: WeightUnit::Kg,
+        distance_unit: DistanceUnit::Km,
+        timezone: "UTC".to_owned(),
+        max_heart_rate: None,
     };
 
     let response = request(
@@ -9,6 +9,14 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "distance_unit"))]
     pub struct DistanceUnit;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "eorm_formula"))]
+    pub struct EormFormula;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "gear_type"))]
+    pub struct GearType;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "metcon_type"))]
     pub struct MetconType;
@@ -17,13 +25,41 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "movement_dimension"))]
     pub struct MovementDimension;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "muscle_group"))]
+    pub struct MuscleGroup;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "planned_workout_type"))]
+    pub struct PlannedWorkoutType;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "position"))]
     pub struct Position;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "sample"))]
+    pub struct Sample;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "scaling_level"))]
+    pub struct ScalingLevel;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "sex"))]
+    pub struct Sex;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "strength_block_type"))]
+    pub struct StrengthBlockType;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "weekday"))]
     pub struct Weekday;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "weight_unit"))]
+    pub struct WeightUnit;
 }
 
 diesel::table! {
@@ -90,10 +126,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+
+    cardio_lap (id) {
+        id -> Int8,
+        user_id -> Int8,
+        cardio_session_id -> Int8,
+        lap_number -> Int4,
+        start -> Int4,
+        time -> Int4,
+        distance -> Nullable<Int4>,
+        avg_heart_rate -> Nullable<Int4>,
+        rest -> Bool,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CardioType;
     use super::sql_types::Position;
+    use super::sql_types::Sample;
 
     cardio_session (id) {
         id -> Int8,
@@ -115,6 +170,18 @@ diesel::table! {
         comments -> Nullable<Text>,
         epoch -> Int8,
         deleted -> Bool,
+        uncorrected_ascent -> Nullable<Int4>,
+        uncorrected_descent -> Nullable<Int4>,
+        uncorrected_elevation -> Nullable<Array<Float8>>,
+        share_token -> Nullable<Text>,
+        avg_power -> Nullable<Int4>,
+        max_power -> Nullable<Int4>,
+        normalized_power -> Nullable<Int4>,
+        power -> Nullable<Array<Sample>>,
+        speed -> Nullable<Array<Sample>>,
+        temperature -> Nullable<Array<Sample>>,
+        gear_id -> Nullable<Int8>,
+        planned_workout_id -> Nullable<Int8>,
     }
 }
 
@@ -142,6 +209,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::GearType;
+
+    gear (id) {
+        id -> Int8,
+        user_id -> Int8,
+        #[max_length = 80]
+        name -> Varchar,
+        gear_type -> GearType,
+        default_movement_id -> Nullable<Int8>,
+        retirement_distance -> Nullable<Int4>,
+        retired -> Bool,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::MetconType;
@@ -179,6 +264,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::DistanceUnit;
+    use super::sql_types::ScalingLevel;
+
+    metcon_movement_scaling (id) {
+        id -> Int8,
+        user_id -> Nullable<Int8>,
+        metcon_movement_id -> Int8,
+        scaling_level -> ScalingLevel,
+        movement_id -> Int8,
+        distance_unit -> Nullable<DistanceUnit>,
+        count -> Int4,
+        male_weight -> Nullable<Float4>,
+        female_weight -> Nullable<Float4>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
 
@@ -194,12 +299,30 @@ diesel::table! {
         comments -> Nullable<Text>,
         epoch -> Int8,
         deleted -> Bool,
+        planned_workout_id -> Nullable<Int8>,
+        round_reps -> Nullable<Array<Int4>>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ScalingLevel;
+
+    metcon_session_scaling (id) {
+        id -> Int8,
+        user_id -> Int8,
+        metcon_session_id -> Int8,
+        metcon_movement_id -> Int8,
+        scaling_level -> ScalingLevel,
+        epoch -> Int8,
+        deleted -> Bool,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::MovementDimension;
+    use super::sql_types::MuscleGroup;
 
     movement (id) {
         id -> Int8,
@@ -211,6 +334,55 @@ diesel::table! {
         cardio -> Bool,
         epoch -> Int8,
         deleted -> Bool,
+        muscle_groups -> Array<MuscleGroup>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    multisport_leg (id) {
+        id -> Int8,
+        user_id -> Int8,
+        multisport_session_id -> Int8,
+        cardio_session_id -> Int8,
+        leg_number -> Int4,
+        transition_time -> Nullable<Int4>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    multisport_session (id) {
+        id -> Int8,
+        user_id -> Int8,
+        datetime -> Timestamptz,
+        comments -> Nullable<Text>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PlannedWorkoutType;
+
+    planned_workout (id) {
+        id -> Int8,
+        user_id -> Int8,
+        datetime -> Timestamptz,
+        planned_workout_type -> PlannedWorkoutType,
+        metcon_id -> Nullable<Int8>,
+        movement_id -> Nullable<Int8>,
+        distance -> Nullable<Int4>,
+        time -> Nullable<Int4>,
+        comments -> Nullable<Text>,
+        epoch -> Int8,
+        deleted -> Bool,
+        strength_template_id -> Nullable<Int8>,
     }
 }
 
@@ -243,6 +415,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+
+    privacy_zone (id) {
+        id -> Int8,
+        user_id -> Int8,
+        #[max_length = 80]
+        name -> Varchar,
+        latitude -> Float8,
+        longitude -> Float8,
+        radius -> Int4,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::Position;
@@ -259,6 +447,89 @@ diesel::table! {
         marked_positions -> Nullable<Array<Position>>,
         epoch -> Int8,
         deleted -> Bool,
+        min_latitude -> Nullable<Float8>,
+        max_latitude -> Nullable<Float8>,
+        min_longitude -> Nullable<Float8>,
+        max_longitude -> Nullable<Float8>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Position;
+
+    route_segment (id) {
+        id -> Int8,
+        user_id -> Int8,
+        route_id -> Int8,
+        #[max_length = 80]
+        name -> Varchar,
+        track -> Array<Position>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    segment_effort (id) {
+        id -> Int8,
+        user_id -> Int8,
+        route_segment_id -> Int8,
+        cardio_session_id -> Int8,
+        datetime -> Timestamptz,
+        time -> Int4,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::StrengthBlockType;
+
+    strength_block (id) {
+        id -> Int8,
+        user_id -> Int8,
+        datetime -> Timestamptz,
+        block_type -> StrengthBlockType,
+        interval -> Nullable<Int4>,
+        comments -> Nullable<Text>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    strength_program (id) {
+        id -> Int8,
+        user_id -> Nullable<Int8>,
+        #[max_length = 80]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    strength_program_set (id) {
+        id -> Int8,
+        user_id -> Nullable<Int8>,
+        strength_program_id -> Int8,
+        week -> Int4,
+        day -> Int4,
+        set_number -> Int4,
+        count -> Int4,
+        percentage -> Float4,
+        amrap -> Bool,
+        epoch -> Int8,
+        deleted -> Bool,
     }
 }
 
@@ -274,6 +545,9 @@ diesel::table! {
         comments -> Nullable<Text>,
         epoch -> Int8,
         deleted -> Bool,
+        strength_block_id -> Nullable<Int8>,
+        block_position -> Nullable<Int4>,
+        planned_workout_id -> Nullable<Int8>,
     }
 }
 
@@ -289,12 +563,54 @@ diesel::table! {
         weight -> Nullable<Float4>,
         epoch -> Int8,
         deleted -> Bool,
+        rpe -> Nullable<Float4>,
+        rir -> Nullable<Int4>,
+        #[max_length = 4]
+        tempo -> Nullable<Varchar>,
+        rest -> Nullable<Int4>,
+        is_warmup -> Bool,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
 
+    strength_template (id) {
+        id -> Int8,
+        user_id -> Nullable<Int8>,
+        #[max_length = 80]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    strength_template_movement (id) {
+        id -> Int8,
+        user_id -> Nullable<Int8>,
+        strength_template_id -> Int8,
+        movement_id -> Int8,
+        movement_number -> Int4,
+        sets -> Int4,
+        count -> Int4,
+        weight -> Nullable<Float4>,
+        percentage -> Nullable<Float4>,
+        epoch -> Int8,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::EormFormula;
+    use super::sql_types::Sex;
+    use super::sql_types::WeightUnit;
+    use super::sql_types::DistanceUnit;
+
     user (id) {
         id -> Int8,
         #[max_length = 80]
@@ -304,6 +620,27 @@ diesel::table! {
         #[max_length = 80]
         email -> Varchar,
         epoch -> Int8,
+        eorm_formula -> EormFormula,
+        sex -> Nullable<Sex>,
+        birthdate -> Nullable<Date>,
+        weight_unit -> WeightUnit,
+        distance_unit -> DistanceUnit,
+        #[max_length = 80]
+        timezone -> Varchar,
+        max_heart_rate -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    user_eorm (id) {
+        id -> Int8,
+        user_id -> Int8,
+        reps -> Int4,
+        percentage -> Float4,
+        epoch -> Int8,
+        deleted -> Bool,
     }
 }
 
@@ -326,24 +663,62 @@ diesel::joinable!(action_event -> user (user_id));
 diesel::joinable!(action_provider -> platform (platform_id));
 diesel::joinable!(action_rule -> action (action_id));
 diesel::joinable!(action_rule -> user (user_id));
+diesel::joinable!(cardio_lap -> cardio_session (cardio_session_id));
+diesel::joinable!(cardio_lap -> user (user_id));
+diesel::joinable!(cardio_session -> gear (gear_id));
 diesel::joinable!(cardio_session -> movement (movement_id));
+diesel::joinable!(cardio_session -> planned_workout (planned_workout_id));
 diesel::joinable!(cardio_session -> route (route_id));
 diesel::joinable!(cardio_session -> user (user_id));
 diesel::joinable!(diary -> user (user_id));
+diesel::joinable!(gear -> movement (default_movement_id));
+diesel::joinable!(gear -> user (user_id));
 diesel::joinable!(metcon -> user (user_id));
 diesel::joinable!(metcon_movement -> metcon (metcon_id));
 diesel::joinable!(metcon_movement -> movement (movement_id));
 diesel::joinable!(metcon_movement -> user (user_id));
+diesel::joinable!(metcon_movement_scaling -> metcon_movement (metcon_movement_id));
+diesel::joinable!(metcon_movement_scaling -> movement (movement_id));
+diesel::joinable!(metcon_movement_scaling -> user (user_id));
 diesel::joinable!(metcon_session -> metcon (metcon_id));
+diesel::joinable!(metcon_session -> planned_workout (planned_workout_id));
 diesel::joinable!(metcon_session -> user (user_id));
+diesel::joinable!(metcon_session_scaling -> metcon_movement (metcon_movement_id));
+diesel::joinable!(metcon_session_scaling -> metcon_session (metcon_session_id));
+diesel::joinable!(metcon_session_scaling -> user (user_id));
 diesel::joinable!(movement -> user (user_id));
+diesel::joinable!(multisport_leg -> cardio_session (cardio_session_id));
+diesel::joinable!(multisport_leg -> multisport_session (multisport_session_id));
+diesel::joinable!(multisport_leg -> user (user_id));
+diesel::joinable!(multisport_session -> user (user_id));
+diesel::joinable!(planned_workout -> metcon (metcon_id));
+diesel::joinable!(planned_workout -> movement (movement_id));
+diesel::joinable!(planned_workout -> strength_template (strength_template_id));
+diesel::joinable!(planned_workout -> user (user_id));
 diesel::joinable!(platform_credential -> platform (platform_id));
 diesel::joinable!(platform_credential -> user (user_id));
+diesel::joinable!(privacy_zone -> user (user_id));
 diesel::joinable!(route -> user (user_id));
+diesel::joinable!(route_segment -> route (route_id));
+diesel::joinable!(route_segment -> user (user_id));
+diesel::joinable!(segment_effort -> cardio_session (cardio_session_id));
+diesel::joinable!(segment_effort -> route_segment (route_segment_id));
+diesel::joinable!(segment_effort -> user (user_id));
+diesel::joinable!(strength_block -> user (user_id));
+diesel::joinable!(strength_program -> user (user_id));
+diesel::joinable!(strength_program_set -> strength_program (strength_program_id));
+diesel::joinable!(strength_program_set -> user (user_id));
 diesel::joinable!(strength_session -> movement (movement_id));
+diesel::joinable!(strength_session -> planned_workout (planned_workout_id));
+diesel::joinable!(strength_session -> strength_block (strength_block_id));
 diesel::joinable!(strength_session -> user (user_id));
 diesel::joinable!(strength_set -> strength_session (strength_session_id));
 diesel::joinable!(strength_set -> user (user_id));
+diesel::joinable!(strength_template -> user (user_id));
+diesel::joinable!(strength_template_movement -> movement (movement_id));
+diesel::joinable!(strength_template_movement -> strength_template (strength_template_id));
+diesel::joinable!(strength_template_movement -> user (user_id));
+diesel::joinable!(user_eorm -> user (user_id));
 diesel::joinable!(wod -> user (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
@@ -351,18 +726,34 @@ diesel::allow_tables_to_appear_in_same_query!(
     action_event,
     action_provider,
     action_rule,
+    cardio_lap,
     cardio_session,
     diary,
     eorm,
+    gear,
     metcon,
     metcon_movement,
+    metcon_movement_scaling,
     metcon_session,
+    metcon_session_scaling,
     movement,
+    multisport_leg,
+    multisport_session,
+    planned_workout,
     platform,
     platform_credential,
+    privacy_zone,
     route,
+    route_segment,
+    segment_effort,
+    strength_block,
+    strength_program,
+    strength_program_set,
     strength_session,
     strength_set,
+    strength_template,
+    strength_template_movement,
     user,
+    user_eorm,
     wod,
 );
@@ -2,19 +2,53 @@ use serde::{Deserialize, Serialize};
 
 use crate::*;
 
+/// The latest epoch of every table of a user account.
+///
+/// Epochs of tables that were added later default to `0` so that clients which do not know about
+/// them yet can still send their epoch map.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EpochMap {
     pub user: Epoch,
     pub diary: Epoch,
     pub wod: Epoch,
     pub movement: Epoch,
+    #[serde(default)]
+    pub planned_workout: Epoch,
+    #[serde(default)]
+    pub strength_block: Epoch,
     pub strength_session: Epoch,
     pub strength_set: Epoch,
+    #[serde(default)]
+    pub user_eorm: Epoch,
+    #[serde(default)]
+    pub strength_program: Epoch,
+    #[serde(default)]
+    pub strength_program_set: Epoch,
+    #[serde(default)]
+    pub strength_template: Epoch,
+    #[serde(default)]
+    pub strength_template_movement: Epoch,
     pub metcon: Epoch,
     pub metcon_session: Epoch,
     pub metcon_movement: Epoch,
+    #[serde(default)]
+    pub metcon_movement_scaling: Epoch,
+    #[serde(default)]
+    pub metcon_session_scaling: Epoch,
     pub cardio_session: Epoch,
+    #[serde(default)]
+    pub cardio_lap: Epoch,
+    #[serde(default)]
+    pub multisport_session: Epoch,
+    #[serde(default)]
+    pub multisport_leg: Epoch,
+    #[serde(default)]
+    pub gear: Epoch,
     pub route: Epoch,
+    #[serde(default)]
+    pub route_segment: Epoch,
+    #[serde(default)]
+    pub privacy_zone: Epoch,
     pub platform: Epoch,
     pub platform_credential: Epoch,
     pub action_provider: Epoch,
@@ -32,13 +66,28 @@ pub struct AccountData {
     pub diaries: Vec<Diary>,
     pub wods: Vec<Wod>,
     pub movements: Vec<Movement>,
+    pub planned_workouts: Vec<PlannedWorkout>,
+    pub strength_blocks: Vec<StrengthBlock>,
     pub strength_sessions: Vec<StrengthSession>,
     pub strength_sets: Vec<StrengthSet>,
+    pub user_eorms: Vec<UserEorm>,
+    pub strength_programs: Vec<StrengthProgram>,
+    pub strength_program_sets: Vec<StrengthProgramSet>,
+    pub strength_templates: Vec<StrengthTemplate>,
+    pub strength_template_movements: Vec<StrengthTemplateMovement>,
     pub metcons: Vec<Metcon>,
     pub metcon_sessions: Vec<MetconSession>,
     pub metcon_movements: Vec<MetconMovement>,
+    pub metcon_movement_scalings: Vec<MetconMovementScaling>,
+    pub metcon_session_scalings: Vec<MetconSessionScaling>,
     pub cardio_sessions: Vec<CardioSession>,
+    pub cardio_laps: Vec<CardioLap>,
+    pub multisport_sessions: Vec<MultisportSession>,
+    pub multisport_legs: Vec<MultisportLeg>,
+    pub gears: Vec<Gear>,
     pub routes: Vec<Route>,
+    pub route_segments: Vec<RouteSegment>,
+    pub privacy_zones: Vec<PrivacyZone>,
     pub platforms: Vec<Platform>,
     pub platform_credentials: Vec<PlatformCredential>,
     pub action_providers: Vec<ActionProvider>,
@@ -185,6 +185,18 @@ pub struct ActionEvent {
     pub deleted: bool,
 }
 
+/// An enabled [`ActionRule`] for which [`ActionEvents`](ActionEvent) can be created.
+///
+/// `weekday` and the time of `time` are interpreted in `timezone`, the time zone of the user.
+///
+/// Clients store `time` as the instant of the chosen local time on the day the rule was edited, so
+/// the local time is determined with the offset of `timezone` at the date of `time` and not at the
+/// date of the event. This keeps the local time stable across daylight saving time transitions.
+///
+/// Existing action rules are not migrated when the time zone of a user changes. Their local time is
+/// then determined in the new time zone, so the created events move by the difference of the
+/// offsets. Users with the default time zone UTC get the same events as before time zones were
+/// supported.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "db", derive(Queryable))]
 pub struct CreatableActionRule {
@@ -196,6 +208,7 @@ pub struct CreatableActionRule {
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub arguments: Option<String>,
     pub create_before: i32,
+    pub timezone: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -0,0 +1,62 @@
+//! Data and functions shared by the import and export of activity files.
+
+/// A lap of an imported GPX or FIT file.
+///
+/// `start` is the time in milliseconds since the start of the recording and `time` the duration of
+/// the lap in milliseconds.
+#[derive(Debug, Clone)]
+pub struct Lap {
+    pub start: i32,
+    pub time: i32,
+    pub distance: Option<i32>,
+    pub avg_heart_rate: Option<i32>,
+    pub rest: bool,
+}
+
+/// Returns the average rate (per minute) of the events (in milliseconds since the start).
+pub(crate) fn avg_rate(events: &[i32]) -> Option<i32> {
+    let (first, last) = (events.first()?, events.last()?);
+    let duration = last - first;
+    (duration > 0).then(|| {
+        (f64::from(events.len() as u32 - 1) * 60_000. / f64::from(duration)).round() as i32
+    })
+}
+
+/// Returns the average rate (per minute) of the events (in milliseconds since the start) between
+/// `start` and `end`.
+pub(crate) fn avg_rate_between(events: &[i32], start: i32, end: i32) -> Option<i32> {
+    let start = events.partition_point(|&event| event < start);
+    let end = events.partition_point(|&event| event <= end);
+    avg_rate(&events[start..end])
+}
+
+/// Returns the rate (per minute) of the events (in milliseconds since the start) at `time`.
+///
+/// The rate is averaged over the events within five seconds around `time`.
+pub(crate) fn rate_at(events: &[i32], time: i32) -> Option<i32> {
+    avg_rate_between(events, time - 5000, time + 5000)
+}
+
+/// Converts the rates (per minute) at the given times (in milliseconds) to the times of the single
+/// events.
+///
+/// Every rate is valid until the time of the next sample and the last one until `end`.
+pub(crate) fn rates_to_events(samples: &[(i32, f64)], end: i32) -> Vec<i32> {
+    let mut events = vec![];
+    let ends = samples.iter().skip(1).map(|&(time, _)| time).chain([end]);
+    for (&(start, rate), end) in samples.iter().zip(ends) {
+        if rate <= 0. {
+            continue;
+        }
+        let interval = 60_000. / rate;
+        let mut next_event = f64::from(start);
+        if let Some(&last_event) = events.last() {
+            next_event = next_event.max(f64::from(last_event) + interval);
+        }
+        while next_event < f64::from(end) {
+            events.push(next_event.round() as i32);
+            next_event += interval;
+        }
+    }
+    events
+}
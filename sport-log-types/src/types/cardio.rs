@@ -16,10 +16,13 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "db")]
 use crate::{
-    schema::{cardio_session, route},
-    Movement, User,
+    schema::{
+        cardio_lap, cardio_session, gear, multisport_leg, multisport_session, privacy_zone, route,
+        route_segment, segment_effort,
+    },
+    Movement, PlannedWorkout, User,
 };
-use crate::{types::IdString, MovementId, UserId};
+use crate::{types::IdString, MovementId, PlannedWorkoutId, UserId};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(
@@ -42,7 +45,7 @@ pub enum CardioType {
 /// `distance` is the distance in meter since the start of the recording.
 ///
 /// `time` is the time in seconds since the start of the recording.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(
     feature = "db",
     derive(FromSqlRow, AsExpression),
@@ -92,6 +95,37 @@ impl FromSql<crate::schema::sql_types::Position, Pg> for Position {
     }
 }
 
+/// A sample of a measured value like power or speed.
+///
+/// `time` is the time in milliseconds since the start of the recording.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "db",
+    derive(FromSqlRow, AsExpression),
+    diesel(sql_type = crate::schema::sql_types::Sample)
+)]
+pub struct Sample {
+    #[serde(rename(serialize = "v", deserialize = "v"))]
+    pub value: f64,
+    #[serde(rename(serialize = "t", deserialize = "t"))]
+    pub time: i32,
+}
+
+#[cfg(feature = "db")]
+impl ToSql<crate::schema::sql_types::Sample, Pg> for Sample {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        WriteTuple::<(Double, Integer)>::write_tuple(&(self.value, self.time), out)
+    }
+}
+
+#[cfg(feature = "db")]
+impl FromSql<crate::schema::sql_types::Sample, Pg> for Sample {
+    fn from_sql(bytes: <Pg as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let (value, time) = FromSql::<Record<(Double, Integer)>, Pg>::from_sql(bytes)?;
+        Ok(Sample { value, time })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
 #[derive_deftly(IdString)]
 #[serde(try_from = "IdString", into = "IdString")]
@@ -144,6 +178,13 @@ pub struct Route {
 )]
 pub struct CardioSessionId(pub i64);
 
+/// A cardio session like a run or a bike ride.
+///
+/// `uncorrected_ascent` and `uncorrected_descent` are the original ascent and descent before the
+/// elevation of the track has been corrected. They are only set after an elevation correction.
+///
+/// `power`, `speed` and `temperature` are sample series in watt, meter per second and degree
+/// Celsius respectively. `avg_power`, `max_power` and `normalized_power` are measured in watt.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -155,7 +196,14 @@ pub struct CardioSessionId(pub i64);
         Selectable,
         AsChangeset,
     ),
-    diesel(table_name = cardio_session, belongs_to(User), belongs_to(Movement), belongs_to(Route))
+    diesel(
+        table_name = cardio_session,
+        belongs_to(User),
+        belongs_to(Movement),
+        belongs_to(Route),
+        belongs_to(Gear),
+        belongs_to(PlannedWorkout)
+    )
 )]
 pub struct CardioSession {
     pub id: CardioSessionId,
@@ -170,6 +218,10 @@ pub struct CardioSession {
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub descent: Option<i32>,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub uncorrected_ascent: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub uncorrected_descent: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub time: Option<i32>,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub calories: Option<i32>,
@@ -184,8 +236,411 @@ pub struct CardioSession {
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub heart_rate: Option<Vec<i32>>,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub avg_power: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub max_power: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub normalized_power: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub power: Option<Vec<Sample>>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub speed: Option<Vec<Sample>>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub temperature: Option<Vec<Sample>>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub route_id: Option<RouteId>,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub gear_id: Option<GearId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub planned_workout_id: Option<PlannedWorkoutId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub comments: Option<String>,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct CardioLapId(pub i64);
+
+/// A lap or interval of a [`CardioSession`].
+///
+/// `start` is the time in milliseconds since the start of the cardio session.
+///
+/// `time` is the duration of the lap in milliseconds.
+///
+/// `rest` is `true` for rest intervals and `false` for work intervals.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = cardio_lap, belongs_to(User), belongs_to(CardioSession))
+)]
+pub struct CardioLap {
+    pub id: CardioLapId,
+    pub user_id: UserId,
+    pub cardio_session_id: CardioSessionId,
+    pub lap_number: i32,
+    pub start: i32,
+    pub time: i32,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub distance: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub avg_heart_rate: Option<i32>,
+    pub rest: bool,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct MultisportSessionId(pub i64);
+
+/// A multisport session like a triathlon or a brick workout that consists of several
+/// [`CardioSession`]s.
+///
+/// The cardio sessions are linked to the multisport session by [`MultisportLeg`]s.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = multisport_session, belongs_to(User))
+)]
+pub struct MultisportSession {
+    pub id: MultisportSessionId,
+    pub user_id: UserId,
+    pub datetime: DateTime<Utc>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub comments: Option<String>,
     pub deleted: bool,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct MultisportLegId(pub i64);
+
+/// A [`CardioSession`] that is part of a [`MultisportSession`].
+///
+/// `leg_number` defines the order of the legs within the multisport session.
+///
+/// `transition_time` is the time in milliseconds of the transition before the leg.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(
+        table_name = multisport_leg,
+        belongs_to(User),
+        belongs_to(MultisportSession),
+        belongs_to(CardioSession)
+    )
+)]
+pub struct MultisportLeg {
+    pub id: MultisportLegId,
+    pub user_id: UserId,
+    pub multisport_session_id: MultisportSessionId,
+    pub cardio_session_id: CardioSessionId,
+    pub leg_number: i32,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub transition_time: Option<i32>,
+    pub deleted: bool,
+}
+
+/// The totals of all legs of a [`MultisportSession`].
+///
+/// `time` is the sum of the times of the cardio sessions and does not include `transition_time`.
+///
+/// This struct is used for the `multisport_session_totals` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "db", derive(Queryable))]
+pub struct MultisportSessionTotals {
+    pub multisport_session_id: MultisportSessionId,
+    pub legs: i64,
+    pub distance: Option<i64>,
+    pub ascent: Option<i64>,
+    pub descent: Option<i64>,
+    pub time: Option<i64>,
+    pub transition_time: Option<i64>,
+    pub calories: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct RouteSegmentId(pub i64);
+
+/// A named part of a [`Route`] like a climb or a lap.
+///
+/// `track` is the sub-path of the track of the route that makes up the segment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = route_segment, belongs_to(User), belongs_to(Route))
+)]
+pub struct RouteSegment {
+    pub id: RouteSegmentId,
+    pub user_id: UserId,
+    pub route_id: RouteId,
+    pub name: String,
+    pub track: Vec<Position>,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct SegmentEffortId(pub i64);
+
+/// The effort of a [`CardioSession`] on a [`RouteSegment`].
+///
+/// Segment efforts are computed by the server for every cardio session whose track covers the
+/// segment.
+///
+/// `datetime` is the start time of the cardio session.
+///
+/// `time` is the time in milliseconds it took to complete the segment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(
+        table_name = segment_effort,
+        belongs_to(User),
+        belongs_to(RouteSegment),
+        belongs_to(CardioSession)
+    )
+)]
+pub struct SegmentEffort {
+    pub id: SegmentEffortId,
+    pub user_id: UserId,
+    pub route_segment_id: RouteSegmentId,
+    pub cardio_session_id: CardioSessionId,
+    pub datetime: DateTime<Utc>,
+    pub time: i32,
+    pub deleted: bool,
+}
+
+/// The best and the most recent [`SegmentEffort`] on a [`RouteSegment`].
+///
+/// This struct is used for the `segment_effort` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SegmentEfforts {
+    pub route_segment_id: RouteSegmentId,
+    pub best: SegmentEffort,
+    pub latest: SegmentEffort,
+}
+
+/// A share link of a [`CardioSession`].
+///
+/// Anyone who knows the `token` can get the cardio session without authentication. Positions
+/// inside the privacy zones of the owner are removed from the track of shared cardio sessions.
+///
+/// This struct is used for the `cardio_session_share` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardioSessionShare {
+    pub cardio_session_id: CardioSessionId,
+    pub token: String,
+}
+
+/// A [`CardioSession`] that follows a [`Route`].
+///
+/// This struct is used for the `route_session` endpoint which lists all sessions of a route.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "db", derive(Queryable))]
+pub struct RouteSession {
+    pub cardio_session_id: CardioSessionId,
+    pub movement_id: MovementId,
+    pub datetime: DateTime<Utc>,
+    pub distance: Option<i32>,
+    pub time: Option<i32>,
+    pub avg_heart_rate: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct PrivacyZoneId(pub i64);
+
+/// A circular area around a sensitive location like the home of the user.
+///
+/// Positions inside a privacy zone are removed from all tracks of the user before they are handed
+/// to anyone other than the user.
+///
+/// `latitude` and `longitude` of the center are measured in decimal degrees.
+///
+/// `radius` is the radius in meter.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = privacy_zone, belongs_to(User))
+)]
+pub struct PrivacyZone {
+    pub id: PrivacyZoneId,
+    pub user_id: UserId,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius: i32,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::GearType"
+)]
+pub enum GearType {
+    Shoe,
+    Bike,
+    Ski,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct GearId(pub i64);
+
+/// A piece of equipment like a pair of shoes or a bike that is used in [`CardioSession`]s.
+///
+/// If `default_movement_id` is set, the gear is assigned to all new cardio sessions of this
+/// movement that do not state a gear.
+///
+/// `retirement_distance` is the distance in meter after which the gear should be retired.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = gear, belongs_to(User))
+)]
+pub struct Gear {
+    pub id: GearId,
+    pub user_id: UserId,
+    pub name: String,
+    pub gear_type: GearType,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub default_movement_id: Option<MovementId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub retirement_distance: Option<i32>,
+    pub retired: bool,
+    pub deleted: bool,
+}
+
+/// The accumulated usage of a [`Gear`] over all cardio sessions it was used in.
+///
+/// `distance`, `retirement_distance` and `remaining_distance` are measured in meter and `time` in
+/// milliseconds.
+///
+/// `remaining_distance` is the distance left until the `retirement_distance` of the gear is reached
+/// and `None` if the gear has no `retirement_distance`.
+///
+/// `retirement_distance_reached` is the warning that the gear should be retired. It is `true` if
+/// the gear has a `retirement_distance` and `distance` is at least as long. Clients should warn
+/// the user about such gear as long as it is not `retired`.
+///
+/// This struct is used for the `gear_stats` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GearStats {
+    pub gear_id: GearId,
+    pub sessions: i64,
+    pub distance: i64,
+    pub time: i64,
+    pub retirement_distance: Option<i32>,
+    pub remaining_distance: Option<i64>,
+    pub retirement_distance_reached: bool,
+}
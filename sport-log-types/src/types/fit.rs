@@ -0,0 +1,591 @@
+//! Import of FIT activity files.
+//!
+//! Only the `file_id`, `record`, `lap` and `session` messages are decoded. All other messages as
+//! well as developer fields are skipped. If the file contains multiple sessions, the totals are
+//! taken from the first one.
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::{
+    activity::{avg_rate, rates_to_events, Lap},
+    Position, Sample,
+};
+
+/// Seconds between the unix epoch and the FIT epoch 1989-12-31T00:00:00Z.
+const FIT_EPOCH: i64 = 631_065_600;
+const DEGREES_PER_SEMICIRCLE: f64 = 180. / 2_147_483_648.;
+
+const FILE_ID: u16 = 0;
+const SESSION: u16 = 18;
+const LAP: u16 = 19;
+const RECORD: u16 = 20;
+
+/// The field number of the timestamp which is the same for all messages.
+const TIMESTAMP: u8 = 253;
+
+#[derive(Error, Debug)]
+pub enum FitError {
+    #[error("the file is not a fit file")]
+    NoFit,
+    #[error("the fit file is truncated")]
+    Truncated,
+    #[error("the checksum of the fit file is invalid")]
+    InvalidCrc,
+    #[error("the local message type {0} is used before it is defined")]
+    UndefinedMessage(u8),
+}
+
+/// The content of a FIT activity file.
+///
+/// The totals are taken from the session message. If they are missing, they are computed from the
+/// records instead.
+///
+/// `heart_rate` and `cadence` contain the time in milliseconds since the start of every beat and
+/// every step or revolution respectively, like the corresponding fields of a
+/// [`CardioSession`](crate::CardioSession).
+///
+/// `power`, `speed` and `temperature` are given in watt, meter per second and degree Celsius.
+#[derive(Debug, Clone)]
+pub struct Fit {
+    pub start_time: Option<DateTime<Utc>>,
+    pub distance: Option<i32>,
+    pub ascent: Option<i32>,
+    pub descent: Option<i32>,
+    pub time: Option<i32>,
+    pub calories: Option<i32>,
+    /// The positions of all records with coordinates. Empty for indoor activities.
+    pub track: Vec<Position>,
+    pub avg_cadence: Option<i32>,
+    pub cadence: Option<Vec<i32>>,
+    pub avg_heart_rate: Option<i32>,
+    pub heart_rate: Option<Vec<i32>>,
+    pub avg_power: Option<i32>,
+    pub max_power: Option<i32>,
+    pub normalized_power: Option<i32>,
+    pub power: Option<Vec<Sample>>,
+    pub speed: Option<Vec<Sample>>,
+    pub temperature: Option<Vec<Sample>>,
+    /// The laps with a timer time. Laps with the intensity rest are marked as rest.
+    pub laps: Vec<Lap>,
+}
+
+struct FieldDefinition {
+    number: u8,
+    size: u8,
+    base_type: u8,
+}
+
+struct MessageDefinition {
+    global_message: u16,
+    big_endian: bool,
+    fields: Vec<FieldDefinition>,
+    developer_fields_size: usize,
+}
+
+/// A data message with the values of all valid numeric fields.
+///
+/// The values are not scaled.
+struct Message {
+    global_message: u16,
+    fields: Vec<(u8, f64)>,
+}
+
+impl Message {
+    fn get(&self, number: u8) -> Option<f64> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == number)
+            .map(|(_, value)| *value)
+    }
+
+    fn get_i32(&self, number: u8, scale: f64) -> Option<i32> {
+        self.get(number).map(|value| (value / scale).round() as i32)
+    }
+
+    fn get_time(&self, number: u8) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.get(number)? as i64 + FIT_EPOCH, 0)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FitError> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or(FitError::Truncated)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, FitError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    const TABLE: [u16; 16] = [
+        0x0000, 0xCC01, 0xD801, 0x1400, 0xF001, 0x3C00, 0x2800, 0xE401, 0xA001, 0x6C00, 0x7800,
+        0xB401, 0x5000, 0x9C01, 0x8801, 0x4400,
+    ];
+
+    bytes.iter().fold(0, |crc, &byte| {
+        let crc = (crc >> 4) ^ TABLE[usize::from(crc & 0xF)] ^ TABLE[usize::from(byte & 0xF)];
+        (crc >> 4) ^ TABLE[usize::from(crc & 0xF)] ^ TABLE[usize::from(byte >> 4)]
+    })
+}
+
+/// Decodes a numeric value of the given base type.
+///
+/// Returns [`None`] for invalid values, strings and arrays.
+fn decode_value(base_type: u8, big_endian: bool, bytes: &[u8]) -> Option<f64> {
+    let mut raw = 0u64;
+    if big_endian {
+        for &byte in bytes {
+            raw = raw << 8 | u64::from(byte);
+        }
+    } else {
+        for &byte in bytes.iter().rev() {
+            raw = raw << 8 | u64::from(byte);
+        }
+    }
+
+    match (base_type & 0x1F, bytes.len()) {
+        // enum, uint8 and byte
+        (0 | 2 | 13, 1) => (raw != 0xFF).then_some(raw as f64),
+        (1, 1) => (raw != 0x7F).then_some(f64::from(raw as i8)),
+        (3, 2) => (raw != 0x7FFF).then_some(f64::from(raw as i16)),
+        (4, 2) => (raw != 0xFFFF).then_some(raw as f64),
+        (5, 4) => (raw != 0x7FFF_FFFF).then_some(f64::from(raw as i32)),
+        (6, 4) => (raw != 0xFFFF_FFFF).then_some(raw as f64),
+        (8, 4) => Some(f64::from(f32::from_bits(raw as u32))).filter(|value| value.is_finite()),
+        (9, 8) => Some(f64::from_bits(raw)).filter(|value| value.is_finite()),
+        // uint8z, uint16z, uint32z and uint64z
+        (10, 1) | (11, 2) | (12, 4) | (16, 8) => (raw != 0).then_some(raw as f64),
+        (14, 8) => (raw != 0x7FFF_FFFF_FFFF_FFFF).then_some(raw as i64 as f64),
+        (15, 8) => (raw != u64::MAX).then_some(raw as f64),
+        _ => None,
+    }
+}
+
+fn read_definition(
+    reader: &mut Reader,
+    has_developer_fields: bool,
+) -> Result<MessageDefinition, FitError> {
+    // the first byte is reserved
+    let big_endian = reader.take(2)?[1] == 1;
+    let global_message = reader.take(2)?;
+    let global_message = if big_endian {
+        u16::from_be_bytes([global_message[0], global_message[1]])
+    } else {
+        u16::from_le_bytes([global_message[0], global_message[1]])
+    };
+
+    let field_count = reader.byte()?;
+    let fields = (0..field_count)
+        .map(|_| {
+            reader.take(3).map(|field| FieldDefinition {
+                number: field[0],
+                size: field[1],
+                base_type: field[2],
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut developer_fields_size = 0;
+    if has_developer_fields {
+        for _ in 0..reader.byte()? {
+            developer_fields_size += usize::from(reader.take(3)?[1]);
+        }
+    }
+
+    Ok(MessageDefinition {
+        global_message,
+        big_endian,
+        fields,
+        developer_fields_size,
+    })
+}
+
+fn read_message(reader: &mut Reader, definition: &MessageDefinition) -> Result<Message, FitError> {
+    let mut fields = vec![];
+    for field in &definition.fields {
+        let bytes = reader.take(usize::from(field.size))?;
+        if let Some(value) = decode_value(field.base_type, definition.big_endian, bytes) {
+            fields.push((field.number, value));
+        }
+    }
+    reader.take(definition.developer_fields_size)?;
+
+    Ok(Message {
+        global_message: definition.global_message,
+        fields,
+    })
+}
+
+/// Returns all `file_id`, `record`, `lap` and `session` messages.
+fn read_messages(fit: &[u8]) -> Result<Vec<Message>, FitError> {
+    let header_size = usize::from(*fit.first().ok_or(FitError::NoFit)?);
+    if header_size < 12 || fit.len() < header_size || &fit[8..12] != b".FIT" {
+        return Err(FitError::NoFit);
+    }
+    let data_size = u32::from_le_bytes([fit[4], fit[5], fit[6], fit[7]]) as usize;
+    let end = header_size + data_size;
+    let crc = fit.get(end..end + 2).ok_or(FitError::Truncated)?;
+    if crc16(&fit[..end]) != u16::from_le_bytes([crc[0], crc[1]]) {
+        return Err(FitError::InvalidCrc);
+    }
+
+    let mut reader = Reader {
+        bytes: &fit[..end],
+        pos: header_size,
+    };
+    let mut definitions: [Option<MessageDefinition>; 16] = std::array::from_fn(|_| None);
+    let mut last_timestamp = 0;
+    let mut messages = vec![];
+    while !reader.is_empty() {
+        let header = reader.byte()?;
+        if header & 0x80 == 0 && header & 0x40 != 0 {
+            definitions[usize::from(header & 0x0F)] =
+                Some(read_definition(&mut reader, header & 0x20 != 0)?);
+            continue;
+        }
+
+        let local_message = if header & 0x80 == 0 {
+            header & 0x0F
+        } else {
+            (header >> 5) & 0x03
+        };
+        let definition = definitions[usize::from(local_message)]
+            .as_ref()
+            .ok_or(FitError::UndefinedMessage(local_message))?;
+        let mut message = read_message(&mut reader, definition)?;
+
+        if header & 0x80 == 0 {
+            if let Some(timestamp) = message.get(TIMESTAMP) {
+                last_timestamp = timestamp as u32;
+            }
+        } else {
+            // compressed timestamp header with the lower 5 bits of the timestamp
+            let offset = u32::from(header & 0x1F);
+            let mut timestamp = (last_timestamp & !0x1F) | offset;
+            if offset < last_timestamp & 0x1F {
+                timestamp += 0x20;
+            }
+            last_timestamp = timestamp;
+            message.fields.push((TIMESTAMP, f64::from(timestamp)));
+        }
+
+        if [FILE_ID, SESSION, LAP, RECORD].contains(&message.global_message) {
+            messages.push(message);
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Returns the normalized power in watt.
+///
+/// The normalized power is the fourth root of the mean of the fourth powers of the 30 second
+/// rolling average of the power. It is only defined for recordings of at least 30 seconds.
+fn normalized_power(power: &[Sample]) -> Option<i32> {
+    let first = power.first()?;
+    let mut window_start = 0;
+    let mut window_sum = 0.;
+    let mut sum = 0.;
+    let mut count = 0;
+    for (i, sample) in power.iter().enumerate() {
+        window_sum += sample.value;
+        while power[window_start].time <= sample.time - 30_000 {
+            window_sum -= power[window_start].value;
+            window_start += 1;
+        }
+        if sample.time - first.time >= 30_000 {
+            let rolling_avg = window_sum / (i + 1 - window_start) as f64;
+            sum += rolling_avg.powi(4);
+            count += 1;
+        }
+    }
+    (count > 0).then(|| (sum / f64::from(count)).powf(0.25).round() as i32)
+}
+
+/// Parses a FIT activity file.
+pub fn parse_fit(fit: &[u8]) -> Result<Fit, FitError> {
+    let messages = read_messages(fit)?;
+    let messages_of = |global_message: u16| {
+        messages
+            .iter()
+            .filter(move |message| message.global_message == global_message)
+    };
+    let session = messages_of(SESSION).next();
+
+    let start_time = session
+        .and_then(|session| session.get_time(2))
+        .or_else(|| messages_of(RECORD).find_map(|record| record.get_time(TIMESTAMP)))
+        .or_else(|| messages_of(FILE_ID).find_map(|file_id| file_id.get_time(4)));
+
+    // the cadence of running, walking and hiking is given in strides per minute
+    let cadence_factor = match session.and_then(|session| session.get(5)) {
+        Some(sport) if [1., 11., 17.].contains(&sport) => 2.,
+        _ => 1.,
+    };
+
+    let mut track: Vec<Position> = vec![];
+    let mut heart_rate = vec![];
+    let mut cadence = vec![];
+    let mut power = vec![];
+    let mut speed = vec![];
+    let mut temperature = vec![];
+    let mut record_distance = None;
+    let mut end = 0;
+    if let Some(start_time) = start_time {
+        for record in messages_of(RECORD) {
+            let Some(datetime) = record.get_time(TIMESTAMP) else {
+                continue;
+            };
+            let time = (datetime - start_time).num_milliseconds() as i32;
+            let distance = record.get(5).map(|distance| distance / 100.);
+
+            if let (Some(latitude), Some(longitude)) = (record.get(0), record.get(1)) {
+                let mut pos = Position {
+                    longitude: longitude * DEGREES_PER_SEMICIRCLE,
+                    latitude: latitude * DEGREES_PER_SEMICIRCLE,
+                    elevation: record
+                        .get(78)
+                        .or_else(|| record.get(2))
+                        .map_or(0., |altitude| altitude / 5. - 500.),
+                    distance: 0.,
+                    time,
+                };
+                pos.distance = match (distance, track.last()) {
+                    (Some(distance), _) => distance,
+                    (None, Some(last)) => last.distance + last.distance_to(&pos),
+                    (None, None) => 0.,
+                };
+                track.push(pos);
+            }
+            if let Some(rate) = record.get(3) {
+                heart_rate.push((time, rate));
+            }
+            if let Some(rate) = record.get(4) {
+                cadence.push((time, rate * cadence_factor));
+            }
+            if let Some(value) = record.get(7) {
+                power.push(Sample { value, time });
+            }
+            if let Some(value) = record.get(73).or_else(|| record.get(6)) {
+                speed.push(Sample {
+                    value: value / 1000.,
+                    time,
+                });
+            }
+            if let Some(value) = record.get(13) {
+                temperature.push(Sample { value, time });
+            }
+            record_distance = distance.or(record_distance);
+            end = end.max(time);
+        }
+    }
+    let heart_rate = (!heart_rate.is_empty()).then(|| rates_to_events(&heart_rate, end));
+    let cadence = (!cadence.is_empty()).then(|| rates_to_events(&cadence, end));
+
+    let avg_power = session
+        .and_then(|session| session.get_i32(20, 1.))
+        .or_else(|| {
+            (!power.is_empty()).then(|| {
+                (power.iter().map(|sample| sample.value).sum::<f64>() / power.len() as f64).round()
+                    as i32
+            })
+        });
+    let max_power = session
+        .and_then(|session| session.get_i32(21, 1.))
+        .or_else(|| power.iter().map(|sample| sample.value.round() as i32).max());
+    let normalized_power = session
+        .and_then(|session| session.get_i32(34, 1.))
+        .or_else(|| normalized_power(&power));
+
+    let (ascent, descent) = match session.and_then(|session| session.get_i32(22, 1.)) {
+        Some(ascent) => (
+            Some(ascent),
+            session.and_then(|session| session.get_i32(23, 1.)),
+        ),
+        None if !track.is_empty() => {
+            let (ascent, descent) = crate::geo::ascent_descent(&track);
+            (Some(ascent.round() as i32), Some(descent.round() as i32))
+        }
+        None => (None, None),
+    };
+
+    let laps = messages_of(LAP)
+        .filter_map(|lap| {
+            let start = (lap.get_time(2)? - start_time?).num_milliseconds() as i32;
+            Some(Lap {
+                start: start.max(0),
+                time: lap
+                    .get_i32(8, 1.)
+                    .or_else(|| lap.get_i32(7, 1.))
+                    .filter(|&time| time > 0)?,
+                distance: lap.get_i32(9, 100.),
+                avg_heart_rate: lap.get_i32(15, 1.),
+                // the intensity of rest laps is 1
+                rest: lap.get(23) == Some(1.),
+            })
+        })
+        .collect();
+
+    Ok(Fit {
+        start_time,
+        distance: session
+            .and_then(|session| session.get_i32(9, 100.))
+            .or_else(|| record_distance.map(|distance| distance.round() as i32))
+            .or_else(|| track.last().map(|pos| pos.distance.round() as i32)),
+        ascent,
+        descent,
+        time: session
+            .and_then(|session| session.get_i32(8, 1.).or_else(|| session.get_i32(7, 1.)))
+            .or_else(|| (end > 0).then_some(end)),
+        calories: session.and_then(|session| session.get_i32(11, 1.)),
+        track,
+        avg_cadence: session
+            .and_then(|session| session.get_i32(18, 1. / cadence_factor))
+            .or_else(|| avg_rate(cadence.as_deref()?)),
+        cadence,
+        avg_heart_rate: session
+            .and_then(|session| session.get_i32(16, 1.))
+            .or_else(|| avg_rate(heart_rate.as_deref()?)),
+        heart_rate,
+        avg_power,
+        max_power,
+        normalized_power,
+        power: (!power.is_empty()).then_some(power),
+        speed: (!speed.is_empty()).then_some(speed),
+        temperature: (!temperature.is_empty()).then_some(temperature),
+        laps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::{parse_fit, FitError};
+
+    const RUNNING: &[u8] = include_bytes!("../../tests/fixtures/running.fit");
+    const CYCLING: &[u8] = include_bytes!("../../tests/fixtures/cycling.fit");
+    const INDOOR: &[u8] = include_bytes!("../../tests/fixtures/indoor.fit");
+
+    fn start_time() -> DateTime<Utc> {
+        "2021-09-08T01:46:40Z".parse().unwrap()
+    }
+
+    #[test]
+    fn running() {
+        let fit = parse_fit(RUNNING).unwrap();
+
+        assert_eq!(fit.start_time, Some(start_time()));
+        assert_eq!(fit.distance, Some(180));
+        assert_eq!(fit.time, Some(60_000));
+        assert_eq!(fit.calories, Some(50));
+        assert_eq!((fit.ascent, fit.descent), (Some(5), Some(0)));
+
+        assert_eq!(fit.track.len(), 61);
+        let first = &fit.track[0];
+        assert!((first.latitude - 47.).abs() < 1e-6);
+        assert!((first.longitude - 11.).abs() < 1e-6);
+        assert!((first.elevation - 500.).abs() < 0.2);
+        let last = &fit.track[60];
+        assert_eq!(last.time, 60_000);
+        assert!((last.distance - 180.).abs() < 1e-6);
+
+        // the cadence is given in strides per minute
+        assert_eq!(fit.avg_cadence, Some(170));
+        assert_eq!(fit.cadence.unwrap().len(), 170);
+        assert_eq!(fit.avg_heart_rate, Some(150));
+        assert_eq!(fit.heart_rate.unwrap().len(), 150);
+
+        let speed = fit.speed.unwrap();
+        assert_eq!(speed.len(), 61);
+        assert!((speed[0].value - 3.).abs() < 1e-6);
+        assert!(fit.power.is_none());
+        assert_eq!(fit.avg_power, None);
+
+        assert_eq!(fit.laps.len(), 1);
+        assert_eq!(fit.laps[0].start, 0);
+        assert_eq!(fit.laps[0].time, 60_000);
+        assert_eq!(fit.laps[0].distance, Some(180));
+        assert_eq!(fit.laps[0].avg_heart_rate, Some(150));
+        assert!(!fit.laps[0].rest);
+    }
+
+    #[test]
+    fn cycling() {
+        let fit = parse_fit(CYCLING).unwrap();
+
+        // the totals are computed from the records since the session does not contain them
+        assert_eq!(fit.start_time, Some(start_time()));
+        assert_eq!(fit.distance, Some(960));
+        assert_eq!(fit.time, Some(120_000));
+        assert_eq!(fit.calories, None);
+        assert_eq!(fit.descent, Some(12));
+
+        assert_eq!(fit.track.len(), 121);
+        let last = &fit.track[120];
+        assert_eq!(last.time, 120_000);
+        assert!((last.latitude - 47.).abs() < 1e-6);
+        assert!((last.elevation - 588.).abs() < 0.2);
+
+        assert_eq!(fit.avg_cadence, Some(90));
+        assert_eq!(fit.avg_heart_rate, Some(130));
+
+        assert_eq!(fit.power.unwrap().len(), 121);
+        assert_eq!(fit.avg_power, Some(251));
+        assert_eq!(fit.max_power, Some(350));
+        assert_eq!(fit.normalized_power, Some(290));
+        assert!((fit.speed.unwrap()[120].value - 8.).abs() < 1e-6);
+        assert!((fit.temperature.unwrap()[0].value - 20.).abs() < 1e-6);
+
+        assert_eq!(fit.laps.len(), 2);
+        assert_eq!(fit.laps[1].start, 60_000);
+        assert_eq!(fit.laps[1].distance, Some(480));
+        assert!(!fit.laps[0].rest);
+        assert!(fit.laps[1].rest);
+    }
+
+    #[test]
+    fn indoor() {
+        let fit = parse_fit(INDOOR).unwrap();
+
+        assert!(fit.track.is_empty());
+        assert_eq!(fit.start_time, Some(start_time()));
+        assert_eq!(fit.distance, Some(750));
+        assert_eq!(fit.time, Some(300_000));
+        assert_eq!(fit.calories, Some(70));
+        assert_eq!((fit.ascent, fit.descent), (None, None));
+        assert_eq!(fit.avg_cadence, Some(160));
+        assert_eq!(fit.avg_heart_rate, Some(140));
+        // 140 beats per minute for five minutes
+        assert!((699..=701).contains(&fit.heart_rate.unwrap().len()));
+    }
+
+    #[test]
+    fn invalid() {
+        let mut fit = RUNNING.to_vec();
+        fit[100] ^= 0xFF;
+        assert!(matches!(parse_fit(&fit), Err(FitError::InvalidCrc)));
+
+        assert!(matches!(
+            parse_fit(&RUNNING[..RUNNING.len() - 10]),
+            Err(FitError::Truncated)
+        ));
+        assert!(matches!(parse_fit(b"<gpx></gpx>"), Err(FitError::NoFit)));
+    }
+}
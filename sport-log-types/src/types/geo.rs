@@ -0,0 +1,404 @@
+//! Geometric functions for [`Positions`](Position) and tracks.
+
+use crate::{Position, PrivacyZone};
+
+/// The mean radius of the earth in meter.
+const EARTH_RADIUS: f64 = 6_371_000.;
+
+/// Returns the great-circle distance between two points given in decimal degrees in meter.
+///
+/// The distance is calculated using the haversine formula.
+fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let lat1 = lat1.to_radians();
+    let lat2 = lat2.to_radians();
+    let delta_lat = lat2 - lat1;
+    let delta_lon = (lon2 - lon1).to_radians();
+
+    let a =
+        (delta_lat / 2.).sin().powi(2) + lat1.cos() * lat2.cos() * (delta_lon / 2.).sin().powi(2);
+    2. * EARTH_RADIUS * a.sqrt().asin()
+}
+
+impl Position {
+    /// Returns the great-circle distance to `other` in meter.
+    ///
+    /// The elevation is ignored.
+    pub fn distance_to(&self, other: &Position) -> f64 {
+        haversine_distance(
+            self.latitude,
+            self.longitude,
+            other.latitude,
+            other.longitude,
+        )
+    }
+}
+
+/// Returns the directed Hausdorff distance from `track` to `other` in meter.
+///
+/// The computation is aborted and [`None`] is returned as soon as the distance is known to be
+/// greater than `max_distance`.
+fn directed_hausdorff_distance(
+    track: &[Position],
+    other: &[Position],
+    max_distance: f64,
+) -> Option<f64> {
+    let mut distance: f64 = 0.;
+    for pos in track {
+        let mut min_distance = f64::INFINITY;
+        for other_pos in other {
+            min_distance = min_distance.min(pos.distance_to(other_pos));
+            if min_distance <= distance {
+                // this position can not increase the overall distance
+                break;
+            }
+        }
+        distance = distance.max(min_distance);
+        if distance > max_distance {
+            return None;
+        }
+    }
+    Some(distance)
+}
+
+/// Returns the (discrete) Hausdorff distance between `track` and `other` in meter.
+///
+/// This is the greatest distance from a position of one track to the closest position of the other
+/// track. Two tracks with a small Hausdorff distance follow the same path.
+///
+/// If the distance is greater than `max_distance` or one of the tracks is empty, [`None`] is
+/// returned. Since the computation is aborted early in this case, a small `max_distance` speeds up
+/// the comparison of tracks that do not match.
+pub fn hausdorff_distance(
+    track: &[Position],
+    other: &[Position],
+    max_distance: f64,
+) -> Option<f64> {
+    if track.is_empty() || other.is_empty() {
+        return None;
+    }
+
+    let distance = directed_hausdorff_distance(track, other, max_distance)?;
+    let other_distance = directed_hausdorff_distance(other, track, max_distance)?;
+    Some(distance.max(other_distance))
+}
+
+/// A range of latitudes and longitudes in decimal degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub min_longitude: f64,
+    pub max_longitude: f64,
+}
+
+/// Returns the bounding box of `track` extended by `margin` meter.
+///
+/// Every position that is at most `margin` meter away from a position of the track lies within the
+/// box. If the extended box contains a pole or crosses the antimeridian it spans all longitudes.
+///
+/// If the track is empty, [`None`] is returned.
+pub fn bounding_box(track: &[Position], margin: f64) -> Option<BoundingBox> {
+    let first = track.first()?;
+    let mut bounding_box = BoundingBox {
+        min_latitude: first.latitude,
+        max_latitude: first.latitude,
+        min_longitude: first.longitude,
+        max_longitude: first.longitude,
+    };
+    for pos in track {
+        bounding_box.min_latitude = bounding_box.min_latitude.min(pos.latitude);
+        bounding_box.max_latitude = bounding_box.max_latitude.max(pos.latitude);
+        bounding_box.min_longitude = bounding_box.min_longitude.min(pos.longitude);
+        bounding_box.max_longitude = bounding_box.max_longitude.max(pos.longitude);
+    }
+
+    let latitude_margin = (margin / EARTH_RADIUS).to_degrees();
+    bounding_box.min_latitude = (bounding_box.min_latitude - latitude_margin).max(-90.);
+    bounding_box.max_latitude = (bounding_box.max_latitude + latitude_margin).min(90.);
+
+    // the haversine formula gives sin(d / 2R) >= cos(lat) * sin(delta_lon / 2)
+    // where lat is the greatest absolute latitude within the box
+    let max_latitude = bounding_box
+        .min_latitude
+        .abs()
+        .max(bounding_box.max_latitude.abs());
+    let sin_half_delta_lon = (margin / (2. * EARTH_RADIUS)).sin() / max_latitude.to_radians().cos();
+    let longitude_margin = (2. * sin_half_delta_lon.asin()).to_degrees();
+    // asin returns NaN if the margin covers all longitudes
+    if longitude_margin.is_nan()
+        || bounding_box.min_longitude - longitude_margin < -180.
+        || bounding_box.max_longitude + longitude_margin > 180.
+    {
+        bounding_box.min_longitude = -180.;
+        bounding_box.max_longitude = 180.;
+    } else {
+        bounding_box.min_longitude -= longitude_margin;
+        bounding_box.max_longitude += longitude_margin;
+    }
+
+    Some(bounding_box)
+}
+
+/// Returns the indices of the positions of `track` that are the closest to `pos` within each run of
+/// consecutive positions that are at most `tolerance` meter away from `pos`.
+fn closest_indices(track: &[Position], pos: &Position, tolerance: f64) -> Vec<usize> {
+    let mut indices = vec![];
+    let mut closest: Option<(usize, f64)> = None;
+    for (i, track_pos) in track.iter().enumerate() {
+        let distance = track_pos.distance_to(pos);
+        if distance <= tolerance {
+            if !closest.is_some_and(|(_, min_distance)| distance >= min_distance) {
+                closest = Some((i, distance));
+            }
+        } else if let Some((index, _)) = closest.take() {
+            indices.push(index);
+        }
+    }
+    indices.extend(closest.map(|(index, _)| index));
+    indices
+}
+
+/// Returns the shortest time in milliseconds it took to cover `segment` on `track`.
+///
+/// A part of `track` covers the segment if it starts and ends within `tolerance` meter of the start
+/// and the end of the segment and if its Hausdorff distance to the segment is at most `tolerance`
+/// meter.
+///
+/// If `track` does not cover the segment [`None`] is returned.
+pub fn segment_time(track: &[Position], segment: &[Position], tolerance: f64) -> Option<i32> {
+    let (start, end) = (segment.first()?, segment.last()?);
+    let start_indices = closest_indices(track, start, tolerance);
+    let end_indices = closest_indices(track, end, tolerance);
+
+    start_indices
+        .iter()
+        .filter_map(|&start_index| {
+            let end_index = *end_indices.iter().find(|&&index| index > start_index)?;
+            hausdorff_distance(&track[start_index..=end_index], segment, tolerance)?;
+            Some(track[end_index].time - track[start_index].time)
+        })
+        .min()
+}
+
+/// Returns the ascent and the descent of `track` in meter.
+pub fn ascent_descent(track: &[Position]) -> (f64, f64) {
+    track
+        .windows(2)
+        .map(|window| window[1].elevation - window[0].elevation)
+        .fold((0., 0.), |(ascent, descent), delta| {
+            if delta > 0. {
+                (ascent + delta, descent)
+            } else {
+                (ascent, descent - delta)
+            }
+        })
+}
+
+/// Returns `true` if `pos` lies inside one of the `privacy_zones`.
+fn in_privacy_zone(pos: &Position, privacy_zones: &[PrivacyZone]) -> bool {
+    privacy_zones.iter().any(|zone| {
+        haversine_distance(pos.latitude, pos.longitude, zone.latitude, zone.longitude)
+            <= f64::from(zone.radius)
+    })
+}
+
+/// Removes all positions from `track` that lie inside one of the `privacy_zones`.
+pub fn remove_privacy_zones(track: &mut Vec<Position>, privacy_zones: &[PrivacyZone]) {
+    track.retain(|pos| !in_privacy_zone(pos, privacy_zones));
+}
+
+/// Splits `track` into the parts that lie outside of all `privacy_zones`.
+///
+/// In contrast to [`remove_privacy_zones`] the parts before and after a privacy zone are kept
+/// separate, so that a line drawn through the positions of a part never connects them across the
+/// zone.
+pub fn split_at_privacy_zones<'a>(
+    track: &'a [Position],
+    privacy_zones: &[PrivacyZone],
+) -> Vec<&'a [Position]> {
+    track
+        .split(|pos| in_privacy_zone(pos, privacy_zones))
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bounding_box, closest_indices, hausdorff_distance, remove_privacy_zones, segment_time,
+        split_at_privacy_zones,
+    };
+    use crate::{Position, PrivacyZone, PrivacyZoneId, UserId};
+
+    fn position(latitude: f64, longitude: f64) -> Position {
+        Position {
+            latitude,
+            longitude,
+            elevation: 0.,
+            distance: 0.,
+            time: 0,
+        }
+    }
+
+    /// Returns a track that heads north along the prime meridian with one position every 111m
+    /// and every second for each of the `latitudes` given in thousandths of a degree.
+    fn track(latitudes: impl IntoIterator<Item = i32>) -> Vec<Position> {
+        latitudes
+            .into_iter()
+            .enumerate()
+            .map(|(i, latitude)| Position {
+                time: i as i32 * 1000,
+                ..position(f64::from(latitude) * 0.001, 0.)
+            })
+            .collect()
+    }
+
+    fn privacy_zone(latitude: f64, longitude: f64, radius: i32) -> PrivacyZone {
+        PrivacyZone {
+            id: PrivacyZoneId(1),
+            user_id: UserId(1),
+            name: "home".to_owned(),
+            latitude,
+            longitude,
+            radius,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn remove_privacy_zones_from_track() {
+        let privacy_zones = [privacy_zone(0., 0., 150), privacy_zone(0.009, 0., 50)];
+
+        let mut trimmed = track(0..10);
+        remove_privacy_zones(&mut trimmed, &privacy_zones);
+        let latitudes: Vec<_> = trimmed.iter().map(|pos| pos.latitude).collect();
+        assert_eq!(latitudes, [0.002, 0.003, 0.004, 0.005, 0.006, 0.007, 0.008]);
+        // the remaining positions keep their times
+        assert_eq!(trimmed[0].time, 2000);
+
+        let mut untouched = track(0..10);
+        remove_privacy_zones(&mut untouched, &[]);
+        assert_eq!(untouched.len(), 10);
+    }
+
+    #[test]
+    fn split_track_at_privacy_zone() {
+        // 0.001 degrees of latitude are about 111m
+        let track: Vec<_> = (0..10)
+            .map(|i| position(f64::from(i) * 0.001, 0.))
+            .collect();
+        let privacy_zones = [privacy_zone(0.0045, 0., 100)];
+
+        let parts = split_at_privacy_zones(&track, &privacy_zones);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].len(), 4);
+        assert_eq!(parts[1].len(), 4);
+        assert_eq!(parts[1][0].latitude, 0.006);
+
+        let parts = split_at_privacy_zones(&track, &[]);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].len(), track.len());
+    }
+
+    #[test]
+    fn closest_indices_per_pass() {
+        // the track passes latitude 0.002 twice
+        let track = track([0, 1, 2, 3, 4, 3, 2, 1]);
+        let pos = position(0.002, 0.);
+
+        assert_eq!(closest_indices(&track, &pos, 10.), [2, 6]);
+        // both passes are a single run of close positions
+        assert_eq!(closest_indices(&track, &pos, 300.), [2]);
+        assert!(closest_indices(&track, &position(1., 1.), 10.).is_empty());
+    }
+
+    #[test]
+    fn segment_time_of_track() {
+        let segment = track(2..=5);
+
+        assert_eq!(segment_time(&track(0..10), &segment, 25.), Some(3000));
+        // the faster second pass is used
+        let mut track_twice = track([0, 1, 2, 3, 4, 5, 6, 1, 2, 3, 4, 5]);
+        for pos in &mut track_twice[8..] {
+            pos.time = 8000 + (pos.time - 8000) / 2;
+        }
+        assert_eq!(segment_time(&track_twice, &segment, 25.), Some(1500));
+        // the second pass skips a part of the segment
+        let track_twice = track([0, 1, 2, 3, 4, 5, 6, 1, 2, 5]);
+        assert_eq!(segment_time(&track_twice, &segment, 25.), Some(3000));
+        // the track goes the wrong way
+        assert_eq!(segment_time(&track((0..10).rev()), &segment, 25.), None);
+        // the track leaves the segment in between
+        let detour: Vec<_> = track(0..10)
+            .into_iter()
+            .map(|pos| {
+                if pos.time == 3000 {
+                    Position {
+                        longitude: 0.001,
+                        ..pos
+                    }
+                } else {
+                    pos
+                }
+            })
+            .collect();
+        assert_eq!(segment_time(&detour, &segment, 25.), None);
+        assert_eq!(segment_time(&track(0..10), &[], 25.), None);
+    }
+
+    #[test]
+    fn hausdorff_distance_of_tracks() {
+        let track1 = track(0..10);
+        let reversed: Vec<_> = track1.iter().rev().cloned().collect();
+        // 0.001 degrees of longitude at the equator are about 111m
+        let shifted: Vec<_> = track1
+            .iter()
+            .map(|pos| Position {
+                longitude: 0.001,
+                ..pos.clone()
+            })
+            .collect();
+
+        assert_eq!(hausdorff_distance(&track1, &track1, 0.), Some(0.));
+        // the direction does not matter
+        assert_eq!(hausdorff_distance(&track1, &reversed, 0.), Some(0.));
+
+        let distance = hausdorff_distance(&track1, &shifted, 200.).unwrap();
+        assert!((distance - 111.2).abs() < 0.1, "{distance}");
+        assert_eq!(hausdorff_distance(&track1, &shifted, 100.), None);
+
+        // a track that only covers a part of the other one is far away from the remaining part
+        let distance = hausdorff_distance(&track1, &track(0..5), 1000.).unwrap();
+        assert!((distance - 5. * 111.2).abs() < 1., "{distance}");
+        assert_eq!(hausdorff_distance(&track(0..5), &track1, 500.), None);
+
+        assert_eq!(hausdorff_distance(&track1, &[], 1000.), None);
+    }
+
+    #[test]
+    fn bounding_box_of_track() {
+        let track1 = track(0..10);
+        let bounds = bounding_box(&track1, 111.2).unwrap();
+        // 0.001 degrees of latitude are about 111m
+        assert!((bounds.min_latitude + 0.001).abs() < 1e-6, "{bounds:?}");
+        assert!((bounds.max_latitude - 0.01).abs() < 1e-6, "{bounds:?}");
+        assert!((bounds.min_longitude + 0.001).abs() < 1e-6, "{bounds:?}");
+        assert!((bounds.max_longitude - 0.001).abs() < 1e-6, "{bounds:?}");
+
+        // every position within the margin lies within the box
+        let shifted = position(0.005, 0.001);
+        assert!(shifted.distance_to(&track1[5]) < 111.2);
+        assert!(shifted.longitude <= bounds.max_longitude);
+
+        // the box contains the north pole
+        let bounds = bounding_box(&[position(89.999, 10.)], 1000.).unwrap();
+        assert_eq!(bounds.max_latitude, 90.);
+        assert_eq!((bounds.min_longitude, bounds.max_longitude), (-180., 180.));
+
+        // the box crosses the antimeridian
+        let bounds = bounding_box(&[position(0., 179.9999)], 1000.).unwrap();
+        assert_eq!((bounds.min_longitude, bounds.max_longitude), (-180., 180.));
+
+        assert_eq!(bounding_box(&[], 1000.), None);
+    }
+}
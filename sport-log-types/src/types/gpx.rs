@@ -0,0 +1,518 @@
+//! Import and export of GPX 1.1 files.
+//!
+//! When importing, track points of all tracks and track segments are merged into a single track. If the file has
+//! no track, the points of the first route are used instead. Waypoints are used as marked
+//! positions. If the file has multiple track segments, every segment is used as a lap.
+//!
+//! Heart rate and cadence are read from and written to the `hr` and `cad` elements of the Garmin
+//! `TrackPointExtension`.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use roxmltree::{Document, Node};
+use thiserror::Error;
+
+use crate::{
+    activity::{avg_rate, avg_rate_between, rate_at, rates_to_events, Lap},
+    CardioSession, Position, Route,
+};
+
+#[derive(Error, Debug)]
+pub enum GpxError {
+    #[error("invalid xml: {0}")]
+    Xml(#[from] roxmltree::Error),
+    #[error("the root element is not gpx")]
+    NoGpx,
+    #[error("the gpx file contains neither a track nor a route")]
+    NoTrack,
+    #[error("invalid value of {0}: {1}")]
+    InvalidValue(&'static str, String),
+}
+
+/// The content of a GPX file.
+///
+/// `heart_rate` and `cadence` contain the time in milliseconds since the start of every beat and
+/// every step or revolution respectively, like the corresponding fields of a
+/// [`CardioSession`](crate::CardioSession).
+///
+/// `has_elevation` is `false` if the track points have no elevation. The elevation of the
+/// positions is 0 in this case.
+#[derive(Debug, Clone)]
+pub struct Gpx {
+    pub name: Option<String>,
+    pub start_time: Option<DateTime<Utc>>,
+    pub track: Vec<Position>,
+    pub has_elevation: bool,
+    pub marked_positions: Vec<Position>,
+    pub heart_rate: Option<Vec<i32>>,
+    pub cadence: Option<Vec<i32>>,
+    pub laps: Vec<Lap>,
+}
+
+impl Gpx {
+    /// Returns the average heart rate in beats per minute.
+    pub fn avg_heart_rate(&self) -> Option<i32> {
+        avg_rate(self.heart_rate.as_deref()?)
+    }
+
+    /// Returns the average cadence in steps or revolutions per minute.
+    pub fn avg_cadence(&self) -> Option<i32> {
+        avg_rate(self.cadence.as_deref()?)
+    }
+
+    /// Returns the ascent and the descent of the track in meter.
+    ///
+    /// If the track has no elevation, [`None`] is returned.
+    pub fn ascent_descent(&self) -> Option<(i32, i32)> {
+        if !self.has_elevation {
+            return None;
+        }
+        let (ascent, descent) = crate::geo::ascent_descent(&self.track);
+        Some((ascent.round() as i32, descent.round() as i32))
+    }
+
+    /// Returns the total distance of the track in meter.
+    pub fn distance(&self) -> Option<i32> {
+        self.track.last().map(|pos| pos.distance.round() as i32)
+    }
+
+    /// Returns the total time of the track in milliseconds.
+    pub fn time(&self) -> Option<i32> {
+        self.track.last().map(|pos| pos.time)
+    }
+
+    /// Returns the GPX file.
+    ///
+    /// The timestamps of the track points are derived from `start_time` and [`Position::time`]. If
+    /// `start_time` is not set, the track points have no timestamps.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "\n",
+            r#"<gpx version="1.1" creator="Sport Log" xmlns="http://www.topografix.com/GPX/1/1" "#,
+            r#"xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">"#,
+            "\n",
+        ));
+
+        xml.push_str("  <metadata>\n");
+        if let Some(name) = &self.name {
+            writeln!(xml, "    <name>{}</name>", escape_xml(name)).unwrap();
+        }
+        if let Some(start_time) = self.start_time {
+            writeln!(xml, "    <time>{}</time>", format_time(start_time)).unwrap();
+        }
+        xml.push_str("  </metadata>\n");
+
+        for pos in &self.marked_positions {
+            writeln!(
+                xml,
+                r#"  <wpt lat="{}" lon="{}"><ele>{}</ele></wpt>"#,
+                pos.latitude, pos.longitude, pos.elevation
+            )
+            .unwrap();
+        }
+
+        xml.push_str("  <trk>\n");
+        if let Some(name) = &self.name {
+            writeln!(xml, "    <name>{}</name>", escape_xml(name)).unwrap();
+        }
+        xml.push_str("    <trkseg>\n");
+        for pos in &self.track {
+            write!(
+                xml,
+                r#"      <trkpt lat="{}" lon="{}"><ele>{}</ele>"#,
+                pos.latitude, pos.longitude, pos.elevation
+            )
+            .unwrap();
+            if let Some(start_time) = self.start_time {
+                write!(xml, "<time>{}</time>", position_time(start_time, pos)).unwrap();
+            }
+            let heart_rate = self
+                .heart_rate
+                .as_deref()
+                .and_then(|heart_rate| rate_at(heart_rate, pos.time));
+            let cadence = self
+                .cadence
+                .as_deref()
+                .and_then(|cadence| rate_at(cadence, pos.time));
+            if heart_rate.is_some() || cadence.is_some() {
+                xml.push_str("<extensions><gpxtpx:TrackPointExtension>");
+                if let Some(heart_rate) = heart_rate {
+                    write!(xml, "<gpxtpx:hr>{heart_rate}</gpxtpx:hr>").unwrap();
+                }
+                if let Some(cadence) = cadence {
+                    write!(xml, "<gpxtpx:cad>{cadence}</gpxtpx:cad>").unwrap();
+                }
+                xml.push_str("</gpxtpx:TrackPointExtension></extensions>");
+            }
+            xml.push_str("</trkpt>\n");
+        }
+        xml.push_str("    </trkseg>\n  </trk>\n</gpx>\n");
+
+        xml
+    }
+}
+
+impl From<CardioSession> for Gpx {
+    fn from(cardio_session: CardioSession) -> Self {
+        Gpx {
+            name: cardio_session.comments,
+            start_time: Some(cardio_session.datetime),
+            track: cardio_session.track.unwrap_or_default(),
+            has_elevation: true,
+            marked_positions: vec![],
+            heart_rate: cardio_session.heart_rate,
+            cadence: cardio_session.cadence,
+            laps: vec![],
+        }
+    }
+}
+
+impl From<Route> for Gpx {
+    fn from(route: Route) -> Self {
+        Gpx {
+            name: Some(route.name),
+            start_time: None,
+            track: route.track.unwrap_or_default(),
+            has_elevation: true,
+            marked_positions: route.marked_positions.unwrap_or_default(),
+            heart_rate: None,
+            cadence: None,
+            laps: vec![],
+        }
+    }
+}
+
+/// Escapes the special characters of XML.
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub(crate) fn format_time(datetime: DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Returns the absolute timestamp of the position.
+pub(crate) fn position_time(start_time: DateTime<Utc>, pos: &Position) -> String {
+    format_time(start_time + Duration::milliseconds(i64::from(pos.time)))
+}
+
+struct Point {
+    latitude: f64,
+    longitude: f64,
+    elevation: Option<f64>,
+    time: Option<DateTime<Utc>>,
+    heart_rate: Option<f64>,
+    cadence: Option<f64>,
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.tag_name().name() == name)
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.tag_name().name() == name)
+}
+
+fn parse_value<T: std::str::FromStr>(name: &'static str, value: &str) -> Result<T, GpxError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| GpxError::InvalidValue(name, value.to_owned()))
+}
+
+fn parse_child<T: std::str::FromStr>(
+    node: Node,
+    name: &'static str,
+) -> Result<Option<T>, GpxError> {
+    child(node, name)
+        .and_then(|child| child.text())
+        .map(|text| parse_value(name, text))
+        .transpose()
+}
+
+fn parse_time(node: Node) -> Result<Option<DateTime<Utc>>, GpxError> {
+    child(node, "time")
+        .and_then(|child| child.text())
+        .map(|text| {
+            DateTime::parse_from_rfc3339(text.trim())
+                .map(|datetime| datetime.with_timezone(&Utc))
+                .map_err(|_| GpxError::InvalidValue("time", text.to_owned()))
+        })
+        .transpose()
+}
+
+/// Returns the value of the first extension element with one of the `names`.
+fn parse_extension(node: Node, names: &[&'static str]) -> Result<Option<f64>, GpxError> {
+    let Some(extensions) = child(node, "extensions") else {
+        return Ok(None);
+    };
+    extensions
+        .descendants()
+        .find_map(|descendant| {
+            let name = descendant.tag_name().name();
+            let name = names.iter().find(|&&n| n.eq_ignore_ascii_case(name))?;
+            descendant.text().map(|text| parse_value(name, text))
+        })
+        .transpose()
+}
+
+fn parse_point(node: Node) -> Result<Point, GpxError> {
+    let attribute = |name: &'static str| {
+        node.attribute(name)
+            .ok_or_else(|| GpxError::InvalidValue(name, String::new()))
+            .and_then(|value| parse_value(name, value))
+    };
+
+    Ok(Point {
+        latitude: attribute("lat")?,
+        longitude: attribute("lon")?,
+        elevation: parse_child(node, "ele")?,
+        time: parse_time(node)?,
+        heart_rate: parse_extension(node, &["hr", "heartrate"])?,
+        cadence: parse_extension(node, &["cad", "cadence"])?,
+    })
+}
+
+/// Parses a GPX 1.1 file.
+pub fn parse_gpx(gpx: &str) -> Result<Gpx, GpxError> {
+    let document = Document::parse(gpx)?;
+    let root = document.root_element();
+    if root.tag_name().name() != "gpx" {
+        return Err(GpxError::NoGpx);
+    }
+
+    let segments: Vec<Vec<Node>> = children(root, "trk")
+        .flat_map(|trk| children(trk, "trkseg"))
+        .map(|trkseg| children(trkseg, "trkpt").collect::<Vec<_>>())
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let segment_lengths: Vec<usize> = segments.iter().map(Vec::len).collect();
+    let mut nodes: Vec<Node> = segments.into_iter().flatten().collect();
+    if nodes.is_empty() {
+        nodes = children(root, "rte")
+            .next()
+            .map(|rte| children(rte, "rtept").collect())
+            .unwrap_or_default();
+    }
+    if nodes.is_empty() {
+        return Err(GpxError::NoTrack);
+    }
+    let points = nodes
+        .into_iter()
+        .map(parse_point)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let name = [
+        child(root, "metadata"),
+        children(root, "trk").next(),
+        children(root, "rte").next(),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|node| child(node, "name")?.text())
+    .map(|name| name.trim().to_owned());
+    let start_time = match points.iter().find_map(|point| point.time) {
+        Some(time) => Some(time),
+        None => child(root, "metadata")
+            .map(parse_time)
+            .transpose()?
+            .flatten(),
+    };
+
+    let has_elevation = points.iter().any(|point| point.elevation.is_some());
+    let mut track: Vec<Position> = Vec::with_capacity(points.len());
+    let mut heart_rate = vec![];
+    let mut cadence = vec![];
+    for point in &points {
+        let time = match (point.time, start_time) {
+            (Some(time), Some(start_time)) => (time - start_time).num_milliseconds() as i32,
+            _ => track.last().map_or(0, |pos| pos.time),
+        };
+        let mut pos = Position {
+            longitude: point.longitude,
+            latitude: point.latitude,
+            elevation: point.elevation.unwrap_or(0.),
+            distance: 0.,
+            time,
+        };
+        if let Some(last) = track.last() {
+            pos.distance = last.distance + last.distance_to(&pos);
+        }
+        if let Some(rate) = point.heart_rate {
+            heart_rate.push((time, rate));
+        }
+        if let Some(rate) = point.cadence {
+            cadence.push((time, rate));
+        }
+        track.push(pos);
+    }
+
+    let marked_positions = children(root, "wpt")
+        .map(|wpt| {
+            let point = parse_point(wpt)?;
+            let mut pos = Position {
+                longitude: point.longitude,
+                latitude: point.latitude,
+                elevation: point.elevation.unwrap_or(0.),
+                distance: 0.,
+                time: 0,
+            };
+            // use the distance and time of the closest position of the track
+            if let Some(closest) = track
+                .iter()
+                .min_by(|pos1, pos2| pos1.distance_to(&pos).total_cmp(&pos2.distance_to(&pos)))
+            {
+                pos.distance = closest.distance;
+                pos.time = closest.time;
+            }
+            Ok(pos)
+        })
+        .collect::<Result<Vec<_>, GpxError>>()?;
+
+    let end = track.last().map_or(0, |pos| pos.time);
+    let heart_rate = (!heart_rate.is_empty()).then(|| rates_to_events(&heart_rate, end));
+    let cadence = (!cadence.is_empty()).then(|| rates_to_events(&cadence, end));
+
+    let mut laps = vec![];
+    if segment_lengths.len() > 1 {
+        let mut first = 0;
+        for len in segment_lengths {
+            let (start, end) = (&track[first], &track[first + len - 1]);
+            first += len;
+            if end.time <= start.time {
+                continue;
+            }
+            laps.push(Lap {
+                start: start.time,
+                time: end.time - start.time,
+                distance: Some((end.distance - start.distance).round() as i32),
+                avg_heart_rate: heart_rate
+                    .as_deref()
+                    .and_then(|heart_rate| avg_rate_between(heart_rate, start.time, end.time)),
+                rest: false,
+            });
+        }
+    }
+
+    Ok(Gpx {
+        name,
+        start_time,
+        track,
+        has_elevation,
+        marked_positions,
+        heart_rate,
+        cadence,
+        laps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_gpx, GpxError};
+
+    const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1"
+    xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">
+  <metadata><name> Morning Run </name></metadata>
+  <wpt lat="0.0021" lon="0"><ele>0</ele></wpt>
+  <trk>
+    <trkseg>
+      <trkpt lat="0" lon="0"><ele>100</ele><time>2024-01-01T08:00:00Z</time>
+        <extensions><gpxtpx:TrackPointExtension><gpxtpx:hr>120</gpxtpx:hr></gpxtpx:TrackPointExtension></extensions>
+      </trkpt>
+      <trkpt lat="0.001" lon="0"><ele>110</ele><time>2024-01-01T08:01:00Z</time></trkpt>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="0.002" lon="0"><ele>105</ele><time>2024-01-01T08:02:00Z</time></trkpt>
+      <trkpt lat="0.003" lon="0"><ele>108</ele><time>2024-01-01T08:03:00Z</time></trkpt>
+    </trkseg>
+  </trk>
+</gpx>"#;
+
+    #[test]
+    fn parse_track() {
+        let gpx = parse_gpx(GPX).unwrap();
+
+        assert_eq!(gpx.name.as_deref(), Some("Morning Run"));
+        assert_eq!(
+            gpx.start_time.unwrap().to_rfc3339(),
+            "2024-01-01T08:00:00+00:00"
+        );
+        assert_eq!(gpx.track.len(), 4);
+        assert_eq!(gpx.time(), Some(180_000));
+        // 0.001 degrees of latitude are about 111m
+        assert_eq!(gpx.distance(), Some(334));
+        assert_eq!(gpx.ascent_descent(), Some((13, 5)));
+
+        // the waypoint gets the distance and time of the closest position
+        assert_eq!(gpx.marked_positions.len(), 1);
+        assert_eq!(gpx.marked_positions[0].time, 120_000);
+
+        // 120 beats per minute until the end of the track
+        let heart_rate = gpx.heart_rate.as_deref().unwrap();
+        assert_eq!(heart_rate.len(), 360);
+        assert_eq!(gpx.avg_heart_rate(), Some(120));
+        assert!(gpx.cadence.is_none());
+
+        // every track segment is a lap
+        assert_eq!(gpx.laps.len(), 2);
+        assert_eq!(gpx.laps[1].start, 120_000);
+        assert_eq!(gpx.laps[1].time, 60_000);
+        assert_eq!(gpx.laps[1].avg_heart_rate, Some(120));
+    }
+
+    #[test]
+    fn parse_route_without_elevation() {
+        let gpx = parse_gpx(
+            r#"<gpx version="1.1"><rte><name>Loop</name>
+                <rtept lat="1" lon="2"/><rtept lat="1.001" lon="2"/>
+            </rte></gpx>"#,
+        )
+        .unwrap();
+
+        assert_eq!(gpx.name.as_deref(), Some("Loop"));
+        assert!(gpx.start_time.is_none());
+        assert_eq!(gpx.track.len(), 2);
+        assert_eq!(gpx.time(), Some(0));
+        assert!(!gpx.has_elevation);
+        assert_eq!(gpx.ascent_descent(), None);
+        assert!(gpx.laps.is_empty());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(matches!(parse_gpx("<gpx"), Err(GpxError::Xml(_))));
+        assert!(matches!(parse_gpx("<tcx/>"), Err(GpxError::NoGpx)));
+        assert!(matches!(
+            parse_gpx("<gpx><trk><trkseg/></trk></gpx>"),
+            Err(GpxError::NoTrack)
+        ));
+        assert!(matches!(
+            parse_gpx(r#"<gpx><trk><trkseg><trkpt lat="north" lon="0"/></trkseg></trk></gpx>"#),
+            Err(GpxError::InvalidValue("lat", _))
+        ));
+    }
+
+    #[test]
+    fn export_and_import() {
+        let gpx = parse_gpx(GPX).unwrap();
+        let reimported = parse_gpx(&gpx.to_xml()).unwrap();
+
+        assert_eq!(reimported.name, gpx.name);
+        assert_eq!(reimported.start_time, gpx.start_time);
+        assert_eq!(reimported.track, gpx.track);
+        assert_eq!(reimported.avg_heart_rate(), gpx.avg_heart_rate());
+    }
+}
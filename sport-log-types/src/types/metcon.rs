@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use chrono::{DateTime, Utc};
 use derive_deftly::Deftly;
 #[cfg(feature = "db")]
@@ -8,10 +10,12 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "db")]
 use crate::{
-    schema::{metcon, metcon_movement, metcon_session},
-    Movement, User,
+    schema::{
+        metcon, metcon_movement, metcon_movement_scaling, metcon_session, metcon_session_scaling,
+    },
+    Movement, PlannedWorkout, User,
 };
-use crate::{types::IdString, MovementId, UserId};
+use crate::{types::IdString, MovementId, PlannedWorkoutId, UserId};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(
@@ -23,6 +27,31 @@ pub enum MetconType {
     Amrap,
     Emom,
     ForTime,
+    /// Intervals of 20 seconds of work and 10 seconds of rest.
+    Tabata,
+    /// In minute N, N reps are performed until the reps can not be finished within the minute.
+    DeathBy,
+    /// The reps increase by one every round until the time cap is reached.
+    Ladder,
+    /// A long list of movements that is performed once.
+    Chipper,
+}
+
+/// The difficulty a movement of a [`Metcon`] is performed at.
+///
+/// Results of a metcon are only comparable if all movements were performed at the same level.
+/// The levels are ordered from the hardest to the easiest.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::ScalingLevel"
+)]
+pub enum ScalingLevel {
+    /// The movement as prescribed by its [`MetconMovement`].
+    Rx,
+    Scaled,
+    Foundations,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -62,6 +91,15 @@ pub struct MetconId(pub i64);
 ///
 /// If `metcon_type` is [`MetconType::ForTime`] `rounds` should be set and `timecap` can be None or
 /// have a value.
+///
+/// If `metcon_type` is [`MetconType::Tabata`] `rounds` and `timecap` should be set (`rounds` is the
+/// number of intervals and `timecap` the total duration).
+///
+/// If `metcon_type` is [`MetconType::DeathBy`] or [`MetconType::Chipper`] `rounds` should be `None`
+/// and `timecap` can be None or have a value.
+///
+/// If `metcon_type` is [`MetconType::Ladder`] `timecap` should be set and `rounds` can be None or
+/// contain the last round of the ladder.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -130,6 +168,55 @@ pub struct MetconMovement {
     pub deleted: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct MetconMovementScalingId(pub i64);
+
+/// An alternative [`ScalingLevel`] of a [`MetconMovement`].
+///
+/// The [`ScalingLevel::Rx`] level is defined by the metcon movement itself, so `scaling_level` is
+/// never [`ScalingLevel::Rx`]. The other fields replace those of the metcon movement.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(
+        table_name = metcon_movement_scaling,
+        belongs_to(MetconMovement),
+        belongs_to(Movement)
+    )
+)]
+pub struct MetconMovementScaling {
+    pub id: MetconMovementScalingId,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub user_id: Option<UserId>,
+    pub metcon_movement_id: MetconMovementId,
+    pub scaling_level: ScalingLevel,
+    pub movement_id: MovementId,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub distance_unit: Option<DistanceUnit>,
+    pub count: i32,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub male_weight: Option<f32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub female_weight: Option<f32>,
+    pub deleted: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
 #[derive_deftly(IdString)]
 #[serde(try_from = "IdString", into = "IdString")]
@@ -141,6 +228,21 @@ pub struct MetconMovement {
 )]
 pub struct MetconSessionId(pub i64);
 
+/// The result of a [`Metcon`].
+///
+/// The score depends on the [`MetconType`] of the metcon:
+/// - [`MetconType::Amrap`]: `rounds` and `reps` after the last completed round.
+/// - [`MetconType::Emom`]: no score.
+/// - [`MetconType::ForTime`]: `time` if the metcon was finished, otherwise `rounds` and `reps`.
+/// - [`MetconType::Tabata`]: the total `reps` and optionally the reps of every interval in
+///   `round_reps`.
+/// - [`MetconType::DeathBy`]: the completed minutes in `rounds` and optionally the `reps` of the
+///   failed minute.
+/// - [`MetconType::Ladder`]: `time` if the last round was finished, otherwise the completed
+///   `rounds` and the `reps` of the failed round.
+/// - [`MetconType::Chipper`]: `time` if the metcon was finished, otherwise the total `reps`.
+///
+/// Fields that are not part of the score are `None`.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -152,7 +254,12 @@ pub struct MetconSessionId(pub i64);
         Selectable,
         AsChangeset,
     ),
-    diesel(table_name = metcon_session, belongs_to(User), belongs_to(Metcon))
+    diesel(
+        table_name = metcon_session,
+        belongs_to(User),
+        belongs_to(Metcon),
+        belongs_to(PlannedWorkout)
+    )
 )]
 pub struct MetconSession {
     pub id: MetconSessionId,
@@ -165,8 +272,219 @@ pub struct MetconSession {
     pub rounds: Option<i32>,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub reps: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub round_reps: Option<Vec<i32>>,
     pub rx: bool,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub comments: Option<String>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub planned_workout_id: Option<PlannedWorkoutId>,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct MetconSessionScalingId(pub i64);
+
+/// The [`ScalingLevel`] a [`MetconMovement`] was performed at in a [`MetconSession`].
+///
+/// Movements without a metcon session scaling were performed at [`ScalingLevel::Rx`] if `rx` of
+/// the metcon session is `true`, so `scaling_level` must not be [`ScalingLevel::Rx`].
+///
+/// The metcon movement must belong to the metcon of the metcon session.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(
+        table_name = metcon_session_scaling,
+        belongs_to(User),
+        belongs_to(MetconSession),
+        belongs_to(MetconMovement)
+    )
+)]
+pub struct MetconSessionScaling {
+    pub id: MetconSessionScalingId,
+    pub user_id: UserId,
+    pub metcon_session_id: MetconSessionId,
+    pub metcon_movement_id: MetconMovementId,
+    pub scaling_level: ScalingLevel,
     pub deleted: bool,
 }
+
+impl MetconSession {
+    /// Returns the [`ScalingLevel`] of the session given the levels of its
+    /// [`MetconSessionScaling`]s.
+    ///
+    /// A session counts at the easiest level of its movements. Sessions without scalings count as
+    /// [`ScalingLevel::Rx`] if they were performed rx and as [`ScalingLevel::Scaled`] otherwise.
+    pub fn scaling_level(
+        &self,
+        scaling_levels: impl IntoIterator<Item = ScalingLevel>,
+    ) -> ScalingLevel {
+        scaling_levels.into_iter().max().unwrap_or(if self.rx {
+            ScalingLevel::Rx
+        } else {
+            ScalingLevel::Scaled
+        })
+    }
+
+    /// Compares the scores of two sessions of a metcon with type `metcon_type`.
+    ///
+    /// The better score is the greater one, a finished metcon is always better than an unfinished
+    /// one. For [`MetconType::Tabata`] the interval with the fewest reps counts first if both
+    /// sessions have their `round_reps`, otherwise only the total reps are compared.
+    pub fn cmp_score(&self, other: &Self, metcon_type: MetconType) -> Ordering {
+        let rounds_and_reps =
+            |session: &Self| (session.rounds.unwrap_or(0), session.reps.unwrap_or(0));
+        match metcon_type {
+            MetconType::Emom => Ordering::Equal,
+            MetconType::Amrap | MetconType::DeathBy => {
+                rounds_and_reps(self).cmp(&rounds_and_reps(other))
+            }
+            MetconType::Tabata => {
+                let min_reps = |session: &Self| {
+                    session
+                        .round_reps
+                        .as_ref()
+                        .and_then(|round_reps| round_reps.iter().min().copied())
+                };
+                let reps = |session: &Self| session.reps.unwrap_or(0);
+                match (min_reps(self), min_reps(other)) {
+                    (Some(min_reps), Some(other_min_reps)) => min_reps
+                        .cmp(&other_min_reps)
+                        .then_with(|| reps(self).cmp(&reps(other))),
+                    _ => reps(self).cmp(&reps(other)),
+                }
+            }
+            MetconType::ForTime | MetconType::Ladder | MetconType::Chipper => {
+                match (self.time, other.time) {
+                    (Some(time), Some(other_time)) => other_time.cmp(&time),
+                    (Some(_), None) => Ordering::Greater,
+                    (None, Some(_)) => Ordering::Less,
+                    (None, None) => rounds_and_reps(self).cmp(&rounds_and_reps(other)),
+                }
+            }
+        }
+    }
+}
+
+/// The best [`MetconSession`] of a user for a [`Metcon`] at a [`ScalingLevel`] according to
+/// [`MetconSession::cmp_score`].
+///
+/// Sessions are only compared to sessions with the same [`MetconSession::scaling_level`].
+/// [`MetconType::Emom`] metcons have no score and therefore no record.
+///
+/// This struct is used for the `metcon_record` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MetconRecord {
+    pub metcon_id: MetconId,
+    pub scaling_level: ScalingLevel,
+    pub metcon_session: MetconSession,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use chrono::Utc;
+
+    use super::{MetconId, MetconSession, MetconSessionId, MetconType, ScalingLevel};
+    use crate::UserId;
+
+    fn session(time: Option<i32>, rounds: Option<i32>, reps: Option<i32>) -> MetconSession {
+        MetconSession {
+            id: MetconSessionId(1),
+            user_id: UserId(1),
+            metcon_id: MetconId(1),
+            datetime: Utc::now(),
+            time,
+            rounds,
+            reps,
+            round_reps: None,
+            rx: true,
+            comments: None,
+            planned_workout_id: None,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn cmp_score_rounds_and_reps() {
+        let better = session(None, Some(5), Some(3));
+        let worse = session(None, Some(4), Some(20));
+        for metcon_type in [MetconType::Amrap, MetconType::DeathBy] {
+            assert_eq!(better.cmp_score(&worse, metcon_type), Ordering::Greater);
+            assert_eq!(worse.cmp_score(&better, metcon_type), Ordering::Less);
+        }
+        assert_eq!(better.cmp_score(&worse, MetconType::Emom), Ordering::Equal);
+    }
+
+    #[test]
+    fn cmp_score_time() {
+        let fast = session(Some(300_000), None, None);
+        let slow = session(Some(400_000), None, None);
+        let unfinished = session(None, Some(2), Some(10));
+        let less_unfinished = session(None, Some(3), Some(0));
+        for metcon_type in [MetconType::ForTime, MetconType::Ladder, MetconType::Chipper] {
+            assert_eq!(fast.cmp_score(&slow, metcon_type), Ordering::Greater);
+            assert_eq!(slow.cmp_score(&unfinished, metcon_type), Ordering::Greater);
+            assert_eq!(unfinished.cmp_score(&fast, metcon_type), Ordering::Less);
+            assert_eq!(
+                less_unfinished.cmp_score(&unfinished, metcon_type),
+                Ordering::Greater
+            );
+        }
+    }
+
+    #[test]
+    fn cmp_score_tabata() {
+        let mut even = session(None, None, Some(80));
+        even.round_reps = Some(vec![10; 8]);
+        let mut uneven = session(None, None, Some(100));
+        uneven.round_reps = Some(vec![20, 20, 20, 9, 9, 9, 9, 4]);
+        assert_eq!(
+            even.cmp_score(&uneven, MetconType::Tabata),
+            Ordering::Greater
+        );
+        let without_round_reps = session(None, None, Some(90));
+        assert_eq!(
+            even.cmp_score(&without_round_reps, MetconType::Tabata),
+            Ordering::Less
+        );
+        assert_eq!(
+            uneven.cmp_score(&without_round_reps, MetconType::Tabata),
+            Ordering::Greater
+        );
+        assert_eq!(
+            session(None, None, Some(80)).cmp_score(&without_round_reps, MetconType::Tabata),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn scaling_level() {
+        let mut session = session(None, Some(5), Some(3));
+        assert_eq!(session.scaling_level([]), ScalingLevel::Rx);
+        assert_eq!(
+            session.scaling_level([ScalingLevel::Scaled, ScalingLevel::Foundations]),
+            ScalingLevel::Foundations
+        );
+        session.rx = false;
+        assert_eq!(session.scaling_level([]), ScalingLevel::Scaled);
+    }
+}
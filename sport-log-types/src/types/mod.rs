@@ -3,14 +3,20 @@ use serde::{Deserialize, Serialize};
 
 mod account;
 mod action;
+pub mod activity;
 mod admin;
 mod cardio;
 mod diary_wod;
 mod epoch;
+pub mod fit;
+pub mod geo;
+pub mod gpx;
 mod metcon;
 mod movement;
+mod planned_workout;
 mod platform;
 mod strength;
+pub mod tcx;
 pub mod uri;
 mod user;
 mod version;
@@ -23,6 +29,7 @@ pub use diary_wod::*;
 pub use epoch::*;
 pub use metcon::*;
 pub use movement::*;
+pub use planned_workout::*;
 pub use platform::*;
 pub use strength::*;
 pub use user::*;
@@ -1,3 +1,4 @@
+use chrono::{Datelike, Days, NaiveDate};
 use derive_deftly::Deftly;
 #[cfg(feature = "db")]
 use diesel::{deserialize::FromSqlRow, expression::AsExpression, prelude::*, sql_types::BigInt};
@@ -22,6 +23,24 @@ pub enum MovementDimension {
     Distance,
 }
 
+/// A muscle group that is trained by a [`Movement`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::MuscleGroup"
+)]
+pub enum MuscleGroup {
+    Chest,
+    Back,
+    Shoulders,
+    Arms,
+    Core,
+    Quadriceps,
+    Hamstrings,
+    Glutes,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deftly)]
 #[derive_deftly(IdString)]
 #[serde(try_from = "IdString", into = "IdString")]
@@ -40,6 +59,9 @@ pub struct MovementId(pub i64);
 ///
 /// `categories` decides whether the Movement can be used in Cardio or Strength Sessions or both.
 /// For Metcons the `categories` does not matter.
+///
+/// `muscle_groups` contains the [`MuscleGroup`]s the movement trains and is used to aggregate the
+/// training volume per muscle group.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -62,5 +84,73 @@ pub struct Movement {
     pub description: Option<String>,
     pub movement_dimension: MovementDimension,
     pub cardio: bool,
+    pub muscle_groups: Vec<MuscleGroup>,
     pub deleted: bool,
 }
+
+/// The length of the periods the training volume is aggregated over.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumePeriod {
+    /// A calendar week starting on Monday.
+    Week,
+    /// A calendar month.
+    Month,
+}
+
+impl VolumePeriod {
+    /// Returns the first day of the period that contains `date`.
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        let days = match self {
+            Self::Week => date.weekday().num_days_from_monday(),
+            Self::Month => date.day0(),
+        };
+        date - Days::new(u64::from(days))
+    }
+}
+
+/// The training volume of a [`Movement`] in the period starting at `period_start`.
+///
+/// The volume contains all strength sessions and all metcon sessions of the movement except for
+/// warm-up sets. Metcon movements are only included if they are measured in reps and contribute one
+/// set for every round they are performed in. The reps of a round that was not finished are
+/// assigned to the movements of the round in order.
+///
+/// Depending on the [`MetconType`](crate::MetconType) of the metcon a round is
+/// - a round for [`Amrap`](crate::MetconType::Amrap), [`Emom`](crate::MetconType::Emom) and
+///   [`ForTime`](crate::MetconType::ForTime),
+/// - a minute for [`DeathBy`](crate::MetconType::DeathBy) and a round of
+///   [`Ladder`](crate::MetconType::Ladder), in which the count of the movements is multiplied by the
+///   number of the minute or round,
+/// - the whole metcon for [`Chipper`](crate::MetconType::Chipper) and
+/// - an interval for [`Tabata`](crate::MetconType::Tabata). The intervals alternate between the
+///   movements and the reps of an interval are taken from `round_reps`. Tabata sessions without
+///   `round_reps` are only included if the metcon has a single movement measured in reps.
+///
+/// `tonnage` is the sum of reps times weight.
+///
+/// This struct is used for the `training_volume` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrainingVolume {
+    pub movement_id: MovementId,
+    pub period_start: NaiveDate,
+    pub sessions: i64,
+    pub sets: i64,
+    pub reps: i64,
+    pub tonnage: f32,
+}
+
+/// The training volume of a [`MuscleGroup`] in the period starting at `period_start`.
+///
+/// It is the sum of the [`TrainingVolume`] of all movements that train the muscle group. A session
+/// that contains several of these movements is counted once.
+///
+/// This struct is used for the `muscle_group_volume` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MuscleGroupVolume {
+    pub muscle_group: MuscleGroup,
+    pub period_start: NaiveDate,
+    pub sessions: i64,
+    pub sets: i64,
+    pub reps: i64,
+    pub tonnage: f32,
+}
@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use derive_deftly::Deftly;
+#[cfg(feature = "db")]
+use diesel::{deserialize::FromSqlRow, expression::AsExpression, prelude::*, sql_types::BigInt};
+#[cfg(feature = "db")]
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "db")]
+use crate::{schema::planned_workout, Metcon, Movement, StrengthTemplate, User};
+use crate::{types::IdString, MetconId, MovementId, StrengthTemplateId, UserId};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::PlannedWorkoutType"
+)]
+pub enum PlannedWorkoutType {
+    /// A strength session of the movement or the strength sessions of the strength template.
+    Strength,
+    /// A metcon session of the metcon.
+    Metcon,
+    /// A cardio session of the movement with an optional distance and time target.
+    Cardio,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct PlannedWorkoutId(pub i64);
+
+/// A workout the user plans to do at `datetime`.
+///
+/// `metcon_id` is set for [`PlannedWorkoutType::Metcon`], `movement_id` for
+/// [`PlannedWorkoutType::Cardio`] and either `movement_id` or `strength_template_id` for
+/// [`PlannedWorkoutType::Strength`].
+///
+/// `distance` in meter and `time` in milliseconds are only used for [`PlannedWorkoutType::Cardio`].
+/// They are the targets of the workout: at least `distance` if only the distance is set, at least
+/// `time` if only the time is set, or at least `distance` at the pace of `distance` in `time` if
+/// both are set.
+///
+/// Sessions that were done according to the plan reference it by their `planned_workout_id`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(
+        table_name = planned_workout,
+        belongs_to(User),
+        belongs_to(Metcon),
+        belongs_to(Movement),
+        belongs_to(StrengthTemplate)
+    )
+)]
+pub struct PlannedWorkout {
+    pub id: PlannedWorkoutId,
+    pub user_id: UserId,
+    pub datetime: DateTime<Utc>,
+    pub planned_workout_type: PlannedWorkoutType,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub metcon_id: Option<MetconId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub movement_id: Option<MovementId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub strength_template_id: Option<StrengthTemplateId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub distance: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub time: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub comments: Option<String>,
+    pub deleted: bool,
+}
+
+/// Whether a [`PlannedWorkout`] has been done.
+///
+/// A planned workout is `completed` if at least one session references it.
+/// Otherwise it is `missed` if its `datetime` is in the past.
+///
+/// `target_met` is set for completed [`PlannedWorkoutType::Cardio`] workouts with a distance or
+/// time target and is `true` if one of the cardio sessions that reference the workout reached the
+/// target. See [`PlannedWorkout`] for the meaning of the targets.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlannedWorkoutStatus {
+    pub planned_workout_id: PlannedWorkoutId,
+    pub datetime: DateTime<Utc>,
+    pub completed: bool,
+    pub missed: bool,
+    pub target_met: Option<bool>,
+}
+
+/// The compliance of the user with the planned workouts in a time range.
+///
+/// `compliance` is the share of completed workouts among the workouts that are no longer upcoming,
+/// or `None` if there are none.
+///
+/// This struct is used for the `plan_compliance` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlanCompliance {
+    pub planned: i64,
+    pub completed: i64,
+    pub missed: i64,
+    pub compliance: Option<f32>,
+    pub planned_workouts: Vec<PlannedWorkoutStatus>,
+}
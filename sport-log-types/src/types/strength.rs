@@ -2,14 +2,74 @@ use chrono::{DateTime, Utc};
 use derive_deftly::Deftly;
 #[cfg(feature = "db")]
 use diesel::{deserialize::FromSqlRow, expression::AsExpression, prelude::*, sql_types::BigInt};
+#[cfg(feature = "db")]
+use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "db")]
 use crate::{
-    schema::{eorm, strength_session, strength_set},
-    Movement, User,
+    schema::{
+        eorm, strength_block, strength_program, strength_program_set, strength_session,
+        strength_set, strength_template, strength_template_movement, user_eorm,
+    },
+    Movement, PlannedWorkout, User,
 };
-use crate::{types::IdString, MovementId, UserId};
+use crate::{types::IdString, MovementId, PlannedWorkoutId, UserId};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::StrengthBlockType"
+)]
+pub enum StrengthBlockType {
+    /// The strength sessions are alternated set by set.
+    Superset,
+    /// The strength sessions are performed one after the other for several rounds.
+    Circuit,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct StrengthBlockId(pub i64);
+
+/// A superset or circuit that groups several [`StrengthSession`]s.
+///
+/// The strength sessions are linked to the block by their `strength_block_id` and ordered by
+/// their `block_position`.
+///
+/// `interval` is the rest in milliseconds that is shared by all strength sessions of the block.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = strength_block, belongs_to(User))
+)]
+pub struct StrengthBlock {
+    pub id: StrengthBlockId,
+    pub user_id: UserId,
+    pub datetime: DateTime<Utc>,
+    pub block_type: StrengthBlockType,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub interval: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub comments: Option<String>,
+    pub deleted: bool,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deftly)]
 #[derive_deftly(IdString)]
@@ -22,6 +82,8 @@ use crate::{types::IdString, MovementId, UserId};
 )]
 pub struct StrengthSessionId(pub i64);
 
+/// If the strength session is part of a [`StrengthBlock`], `block_position` is its position within
+/// the block.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -33,7 +95,13 @@ pub struct StrengthSessionId(pub i64);
         Selectable,
         AsChangeset,
     ),
-    diesel(table_name = strength_session,belongs_to(User),belongs_to(Movement))
+    diesel(
+        table_name = strength_session,
+        belongs_to(User),
+        belongs_to(Movement),
+        belongs_to(StrengthBlock),
+        belongs_to(PlannedWorkout)
+    )
 )]
 pub struct StrengthSession {
     pub id: StrengthSessionId,
@@ -44,6 +112,12 @@ pub struct StrengthSession {
     pub interval: Option<i32>,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub comments: Option<String>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub strength_block_id: Option<StrengthBlockId>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub block_position: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub planned_workout_id: Option<PlannedWorkoutId>,
     pub deleted: bool,
 }
 
@@ -58,6 +132,19 @@ pub struct StrengthSession {
 )]
 pub struct StrengthSetId(pub i64);
 
+/// A single set of a [`StrengthSession`].
+///
+/// `rpe` is the rate of perceived exertion between 1 and 10 in steps of 0.5 and `rir` the number
+/// of reps in reserve.
+///
+/// `tempo` consists of four characters for the eccentric, bottom, concentric and top phase of a
+/// rep. Each character is either the duration in seconds or `X` for an explosive phase, e.g.
+/// `31X0`.
+///
+/// `rest` is the rest after the set in milliseconds.
+///
+/// Warm-up sets are excluded from all analytics. `is_warmup` defaults to `false` if it is missing
+/// in the request.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -79,6 +166,16 @@ pub struct StrengthSet {
     pub count: i32,
     #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
     pub weight: Option<f32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub rpe: Option<f32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub rir: Option<i32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub tempo: Option<String>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub rest: Option<i32>,
+    #[serde(default)]
+    pub is_warmup: bool,
     pub deleted: bool,
 }
 
@@ -93,6 +190,7 @@ pub struct StrengthSet {
 )]
 pub struct EormId(pub i64);
 
+/// The percentage of the one rep max that can be lifted for `reps` reps.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -104,3 +202,554 @@ pub struct Eorm {
     pub reps: i32,
     pub percentage: f32,
 }
+
+/// The percentage of the one rep max of a user that can be lifted for `reps` reps.
+///
+/// It is taken from the [`Eorm`] table, the [`EormFormula`] or the [`UserEorm`] table of the user,
+/// so it has no id. The `eorm_percentage` endpoint returns the table according to the
+/// [`EormFormula`] of the user.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct EormPercentage {
+    pub reps: i32,
+    pub percentage: f32,
+}
+
+/// The way the one rep max of a user is estimated from the weight and the number of reps of a set.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::EormFormula"
+)]
+pub enum EormFormula {
+    /// The default [`Eorm`] table.
+    Table,
+    /// `weight * (1 + reps / 30)`
+    Epley,
+    /// `weight * 36 / (37 - reps)`
+    Brzycki,
+    /// `weight * reps ^ 0.1`
+    Lombardi,
+    /// The [`UserEorm`] table of the user with the default [`Eorm`] table as fallback.
+    Custom,
+}
+
+impl EormFormula {
+    /// Returns the percentage of the one rep max that can be lifted for `reps` reps.
+    ///
+    /// `None` is returned for [`Table`](EormFormula::Table) and [`Custom`](EormFormula::Custom)
+    /// and if the formula is not defined for `reps`.
+    pub fn percentage(self, reps: i32) -> Option<f32> {
+        match (self, reps) {
+            (Self::Table | Self::Custom, _) | (_, ..=0) | (Self::Brzycki, 37..) => None,
+            (_, 1) => Some(1.),
+            (Self::Epley, _) => Some(30. / (30. + reps as f32)),
+            (Self::Brzycki, _) => Some((37. - reps as f32) / 36.),
+            (Self::Lombardi, _) => Some((reps as f32).powf(-0.1)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct UserEormId(pub i64);
+
+/// A user defined percentage of the one rep max that can be lifted for `reps` reps.
+///
+/// It replaces the [`Eorm`] with the same `reps` if the [`EormFormula`] of the user is
+/// [`Custom`](EormFormula::Custom).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = user_eorm, belongs_to(User))
+)]
+pub struct UserEorm {
+    pub id: UserEormId,
+    pub user_id: UserId,
+    pub reps: i32,
+    pub percentage: f32,
+    pub deleted: bool,
+}
+
+/// The best estimated one rep max of all sets of a [`StrengthSession`].
+///
+/// The estimation is based on the [`EormFormula`] of the user.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct E1rm {
+    pub strength_session_id: StrengthSessionId,
+    pub datetime: DateTime<Utc>,
+    pub e1rm: f32,
+}
+
+/// The heaviest weight that was lifted for exactly `count` reps and the [`StrengthSession`] it was
+/// first lifted in.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "db", derive(Queryable))]
+pub struct RepMax {
+    pub count: i32,
+    pub weight: f32,
+    pub strength_session_id: StrengthSessionId,
+    pub datetime: DateTime<Utc>,
+}
+
+/// The personal records of a user for a [`Movement`](crate::Movement).
+///
+/// `e1rms` contains the best [`E1rm`] of every strength session ordered by datetime and
+/// `rep_maxes` one [`RepMax`] for every rep count ordered by count.
+///
+/// Warm-up sets are ignored.
+///
+/// This struct is used for the `strength_record` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StrengthRecords {
+    pub movement_id: MovementId,
+    pub e1rms: Vec<E1rm>,
+    pub rep_maxes: Vec<RepMax>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct StrengthProgramId(pub i64);
+
+/// A multi-week strength program like 5/3/1 or the Texas method.
+///
+/// Strength programs can be predefined (`user_id` is [`None`]) or can be user-defined (`user_id`
+/// contains the id of the user).
+///
+/// The sets of the program are defined by [`StrengthProgramSet`]s.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = strength_program, belongs_to(User))
+)]
+pub struct StrengthProgram {
+    pub id: StrengthProgramId,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub user_id: Option<UserId>,
+    pub name: String,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub description: Option<String>,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct StrengthProgramSetId(pub i64);
+
+/// A set of a [`StrengthProgram`] on day `day` of week `week`.
+///
+/// `percentage` is the percentage of the training max that should be lifted for `count` reps.
+/// If `amrap` is `true`, `count` is the minimum and as many reps as possible should be performed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = strength_program_set, belongs_to(StrengthProgram))
+)]
+pub struct StrengthProgramSet {
+    pub id: StrengthProgramSetId,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub user_id: Option<UserId>,
+    pub strength_program_id: StrengthProgramId,
+    pub week: i32,
+    pub day: i32,
+    pub set_number: i32,
+    pub count: i32,
+    pub percentage: f32,
+    pub amrap: bool,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct StrengthTemplateId(pub i64);
+
+/// A template for one or more [`StrengthSession`]s.
+///
+/// Strength templates can be predefined (`user_id` is [`None`]) or can be user-defined (`user_id`
+/// contains the id of the user).
+///
+/// The movements of the template are defined by [`StrengthTemplateMovement`]s.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(table_name = strength_template, belongs_to(User))
+)]
+pub struct StrengthTemplate {
+    pub id: StrengthTemplateId,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub user_id: Option<UserId>,
+    pub name: String,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub description: Option<String>,
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
+#[derive_deftly(IdString)]
+#[serde(try_from = "IdString", into = "IdString")]
+#[cfg_attr(
+    feature = "db",
+    derive(Hash, FromSqlRow, AsExpression),
+    derive_deftly(IntoPgBigInt, FromPgBigInt),
+    diesel(sql_type = BigInt)
+)]
+pub struct StrengthTemplateMovementId(pub i64);
+
+/// A movement of a [`StrengthTemplate`] that should be performed for `sets` sets of `count` reps.
+///
+/// The movements are performed in the order of `movement_number`.
+///
+/// The target weight is either the absolute `weight` in kg or the `percentage` of the estimated
+/// one rep max of the movement. If both are [`None`] the sets have no weight.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    feature = "db",
+    derive(
+        Insertable,
+        Associations,
+        Identifiable,
+        Queryable,
+        Selectable,
+        AsChangeset,
+    ),
+    diesel(
+        table_name = strength_template_movement,
+        belongs_to(StrengthTemplate),
+        belongs_to(Movement)
+    )
+)]
+pub struct StrengthTemplateMovement {
+    pub id: StrengthTemplateMovementId,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub user_id: Option<UserId>,
+    pub strength_template_id: StrengthTemplateId,
+    pub movement_id: MovementId,
+    pub movement_number: i32,
+    pub sets: i32,
+    pub count: i32,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub weight: Option<f32>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub percentage: Option<f32>,
+    pub deleted: bool,
+}
+
+/// The weight difference in kg that suggested and generated weights are rounded to.
+const WEIGHT_STEP: f32 = 0.5;
+
+/// A scheme to progress a movement from one [`StrengthSession`] to the next.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressionScheme {
+    /// The weight of every set is increased by `increment`.
+    Linear,
+    /// The reps of every set are increased until all sets reach `max_reps`, then the weight is
+    /// increased by `increment` and the reps start again at `min_reps`.
+    DoubleProgression,
+    /// The weight of every set is chosen so that the set is performed at `rpe` based on the
+    /// estimated one rep max of the last strength session.
+    RpeBased,
+}
+
+/// The parameters of a [`ProgressionScheme`].
+///
+/// `increment` is measured in kg.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressionParameters {
+    pub increment: f32,
+    pub min_reps: i32,
+    pub max_reps: i32,
+    pub rpe: f32,
+}
+
+/// A set that is suggested for the next [`StrengthSession`] of a movement.
+///
+/// This struct is used for the `strength_suggestion` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SuggestedSet {
+    pub set_number: i32,
+    pub count: i32,
+    pub weight: Option<f32>,
+}
+
+/// Rounds the weight to a multiple of the smallest weight step.
+pub fn round_weight(weight: f32) -> f32 {
+    (weight / WEIGHT_STEP).round() * WEIGHT_STEP
+}
+
+fn eorm_percentage(eorms: &[EormPercentage], reps: i32) -> Option<f32> {
+    eorms
+        .iter()
+        .find(|eorm| eorm.reps == reps)
+        .map(|eorm| eorm.percentage)
+}
+
+impl ProgressionScheme {
+    /// Returns the suggested sets for the next strength session of a movement based on the sets of
+    /// the last strength session of the movement.
+    ///
+    /// Warm-up sets are ignored. `eorms` is the eorm table of the user that is used to estimate the
+    /// one rep max.
+    pub fn suggest(
+        self,
+        last_sets: &[StrengthSet],
+        eorms: &[EormPercentage],
+        parameters: ProgressionParameters,
+    ) -> Vec<SuggestedSet> {
+        let last_sets: Vec<_> = last_sets.iter().filter(|set| !set.is_warmup).collect();
+        let increase =
+            |weight: Option<f32>| weight.map(|weight| round_weight(weight + parameters.increment));
+
+        match self {
+            Self::Linear => last_sets
+                .iter()
+                .map(|set| SuggestedSet {
+                    set_number: set.set_number,
+                    count: set.count,
+                    weight: increase(set.weight),
+                })
+                .collect(),
+            Self::DoubleProgression => {
+                let max_reps_reached = last_sets.iter().all(|set| set.count >= parameters.max_reps);
+                last_sets
+                    .iter()
+                    .map(|set| {
+                        if max_reps_reached {
+                            SuggestedSet {
+                                set_number: set.set_number,
+                                count: parameters.min_reps,
+                                weight: increase(set.weight),
+                            }
+                        } else {
+                            SuggestedSet {
+                                set_number: set.set_number,
+                                count: (set.count + 1)
+                                    .min(parameters.max_reps)
+                                    .max(parameters.min_reps),
+                                weight: set.weight,
+                            }
+                        }
+                    })
+                    .collect()
+            }
+            Self::RpeBased => {
+                let e1rm = last_sets
+                    .iter()
+                    .filter_map(|set| {
+                        // sets without rpe and rir are assumed to be performed until failure
+                        let rir = set
+                            .rir
+                            .or_else(|| set.rpe.map(|rpe| (10. - rpe).round() as i32))
+                            .unwrap_or(0);
+                        Some(set.weight? / eorm_percentage(eorms, set.count + rir)?)
+                    })
+                    .reduce(f32::max);
+                let rir = (10. - parameters.rpe).round() as i32;
+                last_sets
+                    .iter()
+                    .map(|set| SuggestedSet {
+                        set_number: set.set_number,
+                        count: set.count,
+                        weight: e1rm
+                            .zip(eorm_percentage(eorms, set.count + rir))
+                            .map(|(e1rm, percentage)| round_weight(e1rm * percentage))
+                            .or(set.weight),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::slice;
+
+    use super::{
+        EormFormula, EormPercentage, ProgressionParameters, ProgressionScheme, StrengthSet,
+        StrengthSetId, SuggestedSet,
+    };
+    use crate::{StrengthSessionId, UserId};
+
+    const PARAMETERS: ProgressionParameters = ProgressionParameters {
+        increment: 2.5,
+        min_reps: 8,
+        max_reps: 10,
+        rpe: 8.,
+    };
+
+    fn set(set_number: i32, count: i32, weight: f32) -> StrengthSet {
+        StrengthSet {
+            id: StrengthSetId(i64::from(set_number)),
+            user_id: UserId(1),
+            strength_session_id: StrengthSessionId(1),
+            set_number,
+            count,
+            weight: Some(weight),
+            rpe: None,
+            rir: None,
+            tempo: None,
+            rest: None,
+            is_warmup: false,
+            deleted: false,
+        }
+    }
+
+    fn counts_and_weights(sets: &[SuggestedSet]) -> Vec<(i32, Option<f32>)> {
+        sets.iter().map(|set| (set.count, set.weight)).collect()
+    }
+
+    fn assert_percentage(eorm_formula: EormFormula, reps: i32, expected: f32) {
+        let percentage = eorm_formula.percentage(reps).unwrap();
+        assert!(
+            (percentage - expected).abs() < 1e-4,
+            "{eorm_formula:?} for {reps} reps: {percentage} != {expected}"
+        );
+    }
+
+    #[test]
+    fn eorm_formula_percentage() {
+        for eorm_formula in [
+            EormFormula::Epley,
+            EormFormula::Brzycki,
+            EormFormula::Lombardi,
+        ] {
+            assert_percentage(eorm_formula, 1, 1.);
+            assert_eq!(eorm_formula.percentage(0), None);
+            assert_eq!(eorm_formula.percentage(-1), None);
+        }
+
+        // 1 / (1 + 10 / 30)
+        assert_percentage(EormFormula::Epley, 10, 0.75);
+        assert_percentage(EormFormula::Epley, 30, 0.5);
+        // (37 - 10) / 36
+        assert_percentage(EormFormula::Brzycki, 10, 0.75);
+        assert_percentage(EormFormula::Brzycki, 36, 1. / 36.);
+        assert_eq!(EormFormula::Brzycki.percentage(37), None);
+        // 10 ^ -0.1
+        assert_percentage(EormFormula::Lombardi, 10, 0.794_328_2);
+
+        assert_eq!(EormFormula::Table.percentage(5), None);
+        assert_eq!(EormFormula::Custom.percentage(5), None);
+    }
+
+    #[test]
+    fn suggest_linear() {
+        let warmup = StrengthSet {
+            is_warmup: true,
+            ..set(0, 10, 20.)
+        };
+        let last_sets = [warmup, set(1, 5, 100.), set(2, 5, 101.)];
+
+        let suggested = ProgressionScheme::Linear.suggest(&last_sets, &[], PARAMETERS);
+        assert_eq!(suggested[0].set_number, 1);
+        assert_eq!(
+            counts_and_weights(&suggested),
+            [(5, Some(102.5)), (5, Some(103.5))]
+        );
+    }
+
+    #[test]
+    fn suggest_double_progression() {
+        let scheme = ProgressionScheme::DoubleProgression;
+
+        let suggested = scheme.suggest(&[set(1, 10, 50.), set(2, 9, 50.)], &[], PARAMETERS);
+        assert_eq!(
+            counts_and_weights(&suggested),
+            [(10, Some(50.)), (10, Some(50.))]
+        );
+
+        let suggested = scheme.suggest(&[set(1, 10, 50.), set(2, 10, 50.)], &[], PARAMETERS);
+        assert_eq!(
+            counts_and_weights(&suggested),
+            [(8, Some(52.5)), (8, Some(52.5))]
+        );
+    }
+
+    #[test]
+    fn suggest_rpe_based() {
+        let eorms: Vec<_> = [(1, 1.), (3, 0.9), (5, 0.8), (7, 0.75)]
+            .into_iter()
+            .map(|(reps, percentage)| EormPercentage { reps, percentage })
+            .collect();
+        // 80 kg for 3 reps at rpe 8 (2 reps in reserve) estimate a one rep max of 100 kg
+        let last_set = StrengthSet {
+            rpe: Some(8.),
+            ..set(1, 3, 80.)
+        };
+
+        let suggested =
+            ProgressionScheme::RpeBased.suggest(slice::from_ref(&last_set), &eorms, PARAMETERS);
+        assert_eq!(counts_and_weights(&suggested), [(3, Some(80.))]);
+
+        let parameters = ProgressionParameters {
+            rpe: 10.,
+            ..PARAMETERS
+        };
+        let suggested = ProgressionScheme::RpeBased.suggest(&[last_set], &eorms, parameters);
+        assert_eq!(counts_and_weights(&suggested), [(3, Some(90.))]);
+
+        // without a matching eorm the last weight is kept
+        let suggested = ProgressionScheme::RpeBased.suggest(&[set(1, 20, 40.)], &eorms, PARAMETERS);
+        assert_eq!(counts_and_weights(&suggested), [(20, Some(40.))]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Export of cardio sessions as TCX files.
+
+use std::fmt::Write;
+
+use crate::{
+    activity::rate_at,
+    gpx::{escape_xml, format_time, position_time},
+    CardioSession,
+};
+
+/// The sport of a TCX activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcxSport {
+    Running,
+    Biking,
+    Other,
+}
+
+/// Returns the cardio session as TCX file with a single lap.
+///
+/// The timestamps of the track points are derived from `datetime` and
+/// [`Position::time`](crate::Position).
+pub fn to_tcx(cardio_session: &CardioSession, sport: TcxSport) -> String {
+    let mut xml = String::new();
+    xml.push_str(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2">"#,
+        "\n",
+    ));
+
+    let sport = match sport {
+        TcxSport::Running => "Running",
+        TcxSport::Biking => "Biking",
+        TcxSport::Other => "Other",
+    };
+    let start_time = format_time(cardio_session.datetime);
+    xml.push_str("  <Activities>\n");
+    writeln!(xml, r#"    <Activity Sport="{sport}">"#).unwrap();
+    writeln!(xml, "      <Id>{start_time}</Id>").unwrap();
+    writeln!(xml, r#"      <Lap StartTime="{start_time}">"#).unwrap();
+    writeln!(
+        xml,
+        "        <TotalTimeSeconds>{}</TotalTimeSeconds>",
+        f64::from(cardio_session.time.unwrap_or(0)) / 1000.
+    )
+    .unwrap();
+    writeln!(
+        xml,
+        "        <DistanceMeters>{}</DistanceMeters>",
+        cardio_session.distance.unwrap_or(0)
+    )
+    .unwrap();
+    writeln!(
+        xml,
+        "        <Calories>{}</Calories>",
+        cardio_session.calories.unwrap_or(0)
+    )
+    .unwrap();
+    if let Some(avg_heart_rate) = cardio_session.avg_heart_rate {
+        writeln!(
+            xml,
+            "        <AverageHeartRateBpm><Value>{avg_heart_rate}</Value></AverageHeartRateBpm>"
+        )
+        .unwrap();
+    }
+    xml.push_str("        <Intensity>Active</Intensity>\n");
+    xml.push_str("        <TriggerMethod>Manual</TriggerMethod>\n");
+
+    if let Some(track) = &cardio_session.track {
+        xml.push_str("        <Track>\n");
+        for pos in track {
+            write!(
+                xml,
+                "          <Trackpoint><Time>{}</Time><Position>\
+                <LatitudeDegrees>{}</LatitudeDegrees><LongitudeDegrees>{}</LongitudeDegrees>\
+                </Position><AltitudeMeters>{}</AltitudeMeters>\
+                <DistanceMeters>{}</DistanceMeters>",
+                position_time(cardio_session.datetime, pos),
+                pos.latitude,
+                pos.longitude,
+                pos.elevation,
+                pos.distance
+            )
+            .unwrap();
+            if let Some(heart_rate) = cardio_session
+                .heart_rate
+                .as_deref()
+                .and_then(|heart_rate| rate_at(heart_rate, pos.time))
+            {
+                write!(
+                    xml,
+                    "<HeartRateBpm><Value>{heart_rate}</Value></HeartRateBpm>"
+                )
+                .unwrap();
+            }
+            if let Some(cadence) = cardio_session
+                .cadence
+                .as_deref()
+                .and_then(|cadence| rate_at(cadence, pos.time))
+            {
+                write!(xml, "<Cadence>{}</Cadence>", cadence.min(254)).unwrap();
+            }
+            xml.push_str("</Trackpoint>\n");
+        }
+        xml.push_str("        </Track>\n");
+    }
+
+    xml.push_str("      </Lap>\n");
+    if let Some(comments) = &cardio_session.comments {
+        writeln!(xml, "      <Notes>{}</Notes>", escape_xml(comments)).unwrap();
+    }
+    xml.push_str("    </Activity>\n  </Activities>\n</TrainingCenterDatabase>\n");
+
+    xml
+}
@@ -36,21 +36,58 @@ pub const ACTION: &str = "/action";
 pub const ACTION_RULE: &str = "/action_rule";
 pub const ACTION_EVENT: &str = "/action_event";
 
+pub const STRENGTH_BLOCK: &str = "/strength_block";
 pub const STRENGTH_SESSION: &str = "/strength_session";
 pub const STRENGTH_SET: &str = "/strength_set";
+pub const STRENGTH_RECORD: &str = "/strength_record";
+pub const STRENGTH_SUGGESTION: &str = "/strength_suggestion";
+pub const STRENGTH_PROGRAM: &str = "/strength_program";
+pub const STRENGTH_PROGRAM_SET: &str = "/strength_program_set";
+pub const STRENGTH_PROGRAM_GENERATION: &str = "/strength_program_generation";
+pub const STRENGTH_TEMPLATE: &str = "/strength_template";
+pub const STRENGTH_TEMPLATE_MOVEMENT: &str = "/strength_template_movement";
+pub const STRENGTH_TEMPLATE_INSTANTIATION: &str = "/strength_template_instantiation";
+pub const USER_EORM: &str = "/user_eorm";
 pub const EORM: &str = "/eorm";
+pub const EORM_PERCENTAGE: &str = "/eorm_percentage";
 
 pub const METCON_SESSION: &str = "/metcon_session";
 pub const METCON: &str = "/metcon";
 pub const METCON_MOVEMENT: &str = "/metcon_movement";
+pub const METCON_MOVEMENT_SCALING: &str = "/metcon_movement_scaling";
+pub const METCON_SESSION_SCALING: &str = "/metcon_session_scaling";
+pub const METCON_RECORD: &str = "/metcon_record";
 
 pub const CARDIO_SESSION: &str = "/cardio_session";
+pub const CARDIO_SESSION_ELEVATION: &str = "/cardio_session_elevation";
+pub const CARDIO_SESSION_SHARE: &str = "/cardio_session_share";
+pub const SHARED_CARDIO_SESSION: &str = "/shared_cardio_session";
+pub const CARDIO_SESSION_GPX: &str = "/cardio_session_gpx";
+pub const CARDIO_SESSION_FIT: &str = "/cardio_session_fit";
+pub const CARDIO_LAP: &str = "/cardio_lap";
+pub const MULTISPORT_SESSION: &str = "/multisport_session";
+pub const MULTISPORT_SESSION_TOTALS: &str = "/multisport_session_totals";
+pub const MULTISPORT_LEG: &str = "/multisport_leg";
+pub const GEAR: &str = "/gear";
+pub const GEAR_STATS: &str = "/gear_stats";
 pub const ROUTE: &str = "/route";
+pub const ROUTE_SESSION: &str = "/route_session";
+pub const ROUTE_GPX: &str = "/route_gpx";
+pub const ROUTE_SEGMENT: &str = "/route_segment";
+pub const SEGMENT_EFFORT: &str = "/segment_effort";
+pub const PRIVACY_ZONE: &str = "/privacy_zone";
+pub const HEATMAP: &str = "/heatmap";
+pub const HEATMAP_TILE: &str = "/heatmap_tile";
 
 pub const DIARY: &str = "/diary";
 pub const WOD: &str = "/wod";
 
 pub const MOVEMENT: &str = "/movement";
+pub const TRAINING_VOLUME: &str = "/training_volume";
+pub const MUSCLE_GROUP_VOLUME: &str = "/muscle_group_volume";
+
+pub const PLANNED_WORKOUT: &str = "/planned_workout";
+pub const PLAN_COMPLIANCE: &str = "/plan_compliance";
 
 // admin URIs
 
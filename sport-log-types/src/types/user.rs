@@ -1,11 +1,14 @@
+use chrono::NaiveDate;
 use derive_deftly::Deftly;
 #[cfg(feature = "db")]
 use diesel::{deserialize::FromSqlRow, expression::AsExpression, prelude::*, sql_types::BigInt};
+#[cfg(feature = "db")]
+use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "db")]
 use crate::schema::user;
-use crate::types::IdString;
+use crate::{types::IdString, DistanceUnit, EormFormula};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Deftly)]
 #[derive_deftly(IdString)]
@@ -18,6 +21,45 @@ use crate::types::IdString;
 )]
 pub struct UserId(pub i64);
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::Sex"
+)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "db",
+    derive(DbEnum),
+    ExistingTypePath = "crate::schema::sql_types::WeightUnit"
+)]
+pub enum WeightUnit {
+    Kg,
+    Lb,
+}
+
+/// A user and their profile.
+///
+/// `sex` determines whether the male or female weight of metcon movements is used in analytics.
+///
+/// `weight_unit` and `distance_unit` are the units the user prefers for display. All values are
+/// still stored in kg and meter.
+///
+/// `timezone` is an IANA time zone name like `Europe/Vienna`. It determines the calendar periods
+/// in analytics and the local time at which [`ActionRules`](crate::ActionRule) are scheduled.
+/// Changing it therefore also moves the [`ActionEvents`](crate::ActionEvent) that are created for
+/// existing action rules, see [`CreatableActionRule`](crate::CreatableActionRule).
+///
+/// `eorm_formula`, `weight_unit`, `distance_unit` and `timezone` default to the database defaults
+/// table, kg, km and UTC if they are missing in the request. Updating a user replaces the whole
+/// profile, so clients have to send the profile they received back.
+///
+/// `max_heart_rate` is in beats per minute.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(
     feature = "db",
@@ -35,4 +77,34 @@ pub struct User {
     pub username: String,
     pub password: String,
     pub email: String,
+    #[serde(default = "default_eorm_formula")]
+    pub eorm_formula: EormFormula,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub sex: Option<Sex>,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub birthdate: Option<NaiveDate>,
+    #[serde(default = "default_weight_unit")]
+    pub weight_unit: WeightUnit,
+    #[serde(default = "default_distance_unit")]
+    pub distance_unit: DistanceUnit,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    #[cfg_attr(feature = "db", diesel(treat_none_as_null = true))]
+    pub max_heart_rate: Option<i32>,
+}
+
+fn default_eorm_formula() -> EormFormula {
+    EormFormula::Table
+}
+
+fn default_weight_unit() -> WeightUnit {
+    WeightUnit::Kg
+}
+
+fn default_distance_unit() -> DistanceUnit {
+    DistanceUnit::Km
+}
+
+fn default_timezone() -> String {
+    "UTC".to_owned()
 }